@@ -22,9 +22,11 @@ use adw::subclass::prelude::*;
 use gtk::prelude::*;
 use gtk::{gio, glib};
 
+use std::collections::HashMap;
+
 use crate::config::{APP_ID, VERSION};
-use crate::ui;
 use crate::PrismaTimerWindow;
+use crate::{data, ui};
 
 #[doc(hidden)]
 mod imp {
@@ -118,6 +120,13 @@ impl PrismaTimerApplication {
                 obj.update_color_scheme();
             }),
         );
+
+        settings.connect_changed(
+            Some("shortcuts"),
+            glib::clone!(@weak self as obj => move |_, _| {
+                obj.load_custom_shortcuts();
+            }),
+        );
     }
 
     fn setup_gactions(&self) {
@@ -138,6 +147,24 @@ impl PrismaTimerApplication {
         self.set_accels_for_action("app.quit", &["<primary>q"]);
         self.set_accels_for_action("app.preferences", &["<primary>comma"]);
         self.set_accels_for_action("win.show-help-overlay", &["<primary>question"]);
+        self.load_custom_shortcuts();
+    }
+
+    /// Binds the actions listed in `data::SHORTCUTS` to their accelerators,
+    /// as customized in the settings.
+    fn load_custom_shortcuts(&self) {
+        let custom = self
+            .settings()
+            .value("shortcuts")
+            .get::<HashMap<String, String>>()
+            .unwrap_or_default();
+
+        for (action, accel) in data::shortcut_accels(&custom) {
+            match accel {
+                Some(accel) => self.set_accels_for_action(action, &[&accel]),
+                None => self.set_accels_for_action(action, &[]),
+            }
+        }
     }
 
     fn show_about(&self) {
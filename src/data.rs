@@ -1,18 +1,90 @@
+pub use block_solver::SolverTarget;
+pub use competition_round::{CompetitionRound, Cutoff, RoundFormat, TimeLimit};
+pub use cube::Cube;
+pub use date_statistics::{
+    format_time_spent, local_date, summarize_by_period, summarize_day, DatePeriod, PeriodSummary,
+};
+pub use fewest_moves_result::{
+    check_solution, FewestMovesResult, MoveCount, FEWEST_MOVES_TIME_LIMIT,
+};
+pub use goal::{Goal, GoalProgress};
+pub use inspection::{
+    format_inspection, inspection_cue, inspection_penalty, CueSound, InspectionCue,
+};
+pub use last_layer::{last_layer_cases, solve_last_layer, CaseSet, LastLayerCase, OLL_ALGORITHMS};
+pub use mock_competition::MockCompetition;
+pub use multi_blind_result::MultiBlindResult;
+pub use notation::{
+    format_moves, htm_count, invert_moves, normalize_scramble, parse_moves, simplify_moves,
+    to_face_turns, Axis, Face, Move, MoveKind, NotationError, Slice,
+};
+pub use pb_history::{pb_history, PbStatistic, PersonalBest};
 pub use penalty::Penalty;
+pub use practice::{PracticeSummary, DEFAULT_IDLE_CUTOFF};
+pub use recognition::{
+    confusion_pairs, format_recognition_answers, match_case_name, parse_recognition_answers,
+    CaseRecognition, NameMatch, RecognitionAnswer, RECOGNITION_HISTORY,
+};
+pub use relay::{
+    cube_name, format_puzzle_times, format_relay_scrambles, generate_relay_scrambles, puzzle_times,
+    RelayScramble,
+};
+pub use report::{Report, ReportFormat, ReportSolve};
+pub use running_display::RunningDisplay;
+pub use scramble::{
+    generate_case_scramble, generate_cube_scramble, generate_fewest_moves_scramble,
+    generate_scramble,
+};
 pub use session::Session;
+pub use session_comparison::SessionComparison;
 pub use session_item::SessionItem;
+pub use session_kind::SessionKind;
+pub use session_report::{Histogram, SessionReport, TimeGraph};
+pub use shortcuts::{shortcut_accels, SHORTCUTS};
 pub use solve_data::SolveData;
-pub use solve_time::SolveTime;
-pub use statistics::SolveStatistic;
+pub use solve_filter::{describe_filtered_solves, parse_date, SolveFilter, SolveSort};
+pub use solve_result::SolveResult;
+pub use solve_time::{parse_time, SolveTime, TimePrecision};
+pub use statistics::{mann_whitney_u, MannWhitneyU, SolveStatistic, TimeNeeded};
+pub use tags::{format_tags, parse_tags, tag_statistics, TagStatistics};
 pub use timer_state::TimerState;
-pub(crate) use timer_state::TimerStatePriv;
+pub(crate) use timer_state::{InspectionPriv, TimerStatePriv};
 pub use timer_state_machine::TimerStateMachine;
+pub use trainer::CaseTimes;
+pub use trigger_keys::{TriggerEvent, TriggerKeys};
 
+mod block_solver;
+mod competition_round;
+mod cube;
+mod date_statistics;
+mod fewest_moves_result;
+mod goal;
+mod inspection;
+mod last_layer;
+mod mock_competition;
+mod multi_blind_result;
+mod notation;
+mod pb_history;
 mod penalty;
+mod practice;
+mod recognition;
+mod relay;
+mod report;
+mod running_display;
+mod scramble;
 mod session;
+mod session_comparison;
 mod session_item;
+mod session_kind;
+mod session_report;
+mod shortcuts;
 mod solve_data;
+mod solve_filter;
+mod solve_result;
 mod solve_time;
 mod statistics;
+mod tags;
 mod timer_state;
 mod timer_state_machine;
+mod trainer;
+mod trigger_keys;
@@ -0,0 +1,356 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::data::{to_face_turns, Axis, Cube, Face, Move, MoveKind};
+
+/// Longest solution searched for, longer than any cross or 2x2x2 block
+/// needs.
+const MAX_DEPTH: u8 = 14;
+
+/// Number of places of stickers on the cube.
+const PLACES: usize = 54;
+
+/// Distance marking states the search of a pattern table has not reached.
+const UNREACHED: u8 = u8::MAX;
+
+/// A part of the cube solved first, searched for from a scramble.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolverTarget {
+    /// The four edges around the center of a face, as in CFOP.
+    Cross(Face),
+    /// The 2x2x2 block around a corner, as in Petrus, given by the faces of
+    /// the corner.
+    Block(Face, Face, Face),
+}
+
+impl SolverTarget {
+    /// The crosses of every face and the blocks around every corner.
+    pub const ALL: [SolverTarget; 14] = [
+        Self::Cross(Face::D),
+        Self::Cross(Face::U),
+        Self::Cross(Face::F),
+        Self::Cross(Face::B),
+        Self::Cross(Face::R),
+        Self::Cross(Face::L),
+        Self::Block(Face::D, Face::F, Face::L),
+        Self::Block(Face::D, Face::F, Face::R),
+        Self::Block(Face::D, Face::B, Face::L),
+        Self::Block(Face::D, Face::B, Face::R),
+        Self::Block(Face::U, Face::F, Face::L),
+        Self::Block(Face::U, Face::F, Face::R),
+        Self::Block(Face::U, Face::B, Face::L),
+        Self::Block(Face::U, Face::B, Face::R),
+    ];
+
+    /// Gets the name of this target, e.g. `Cross on D` or `Block at DFL`.
+    pub fn label(&self) -> String {
+        match self {
+            Self::Cross(face) => format!("Cross on {}", face.to_char()),
+            Self::Block(a, b, c) => {
+                format!("Block at {}{}{}", a.to_char(), b.to_char(), c.to_char())
+            }
+        }
+    }
+
+    /// Gets the rotation holding the cross on D, or `None` if it is already
+    /// there. Blocks are solved without rotating.
+    fn rotation(&self) -> Option<Move> {
+        let (axis, turns) = match self {
+            Self::Cross(Face::U) => (Axis::X, 2),
+            Self::Cross(Face::F) => (Axis::X, 3),
+            Self::Cross(Face::B) => (Axis::X, 1),
+            Self::Cross(Face::R) => (Axis::Z, 1),
+            Self::Cross(Face::L) => (Axis::Z, 3),
+            _ => return None,
+        };
+        Some(Move::new(MoveKind::Rotation(axis), turns))
+    }
+
+    /// Gets the four pieces of this target, each by the place of one of its
+    /// stickers on a solved cube.
+    fn pieces(&self) -> [u8; 4] {
+        let faces: Vec<Face> = match *self {
+            Self::Cross(face) => vec![face],
+            Self::Block(a, b, c) => vec![a, b, c],
+        };
+        let on_faces = |place: &usize| faces.contains(&Face::ALL[place / 9]);
+
+        let mut corners = Vec::new();
+        let mut edges = Vec::new();
+        for piece in Cube::pieces() {
+            let first = piece.iter().find(|place| on_faces(place)).copied();
+            match (piece.len(), first, self) {
+                (2, Some(place), Self::Cross(_)) => edges.push(place),
+                (2, Some(place), Self::Block(..)) if piece.iter().all(on_faces) => {
+                    edges.push(place)
+                }
+                (3, Some(place), Self::Block(..)) if piece.iter().all(on_faces) => {
+                    corners.push(place)
+                }
+                _ => {}
+            }
+        }
+
+        let mut pieces = [0; 4];
+        for (piece, place) in pieces.iter_mut().zip(corners.into_iter().chain(edges)) {
+            *piece = place as u8;
+        }
+        pieces
+    }
+
+    /// Searches for a shortest sequence of face turns solving this target
+    /// after a scramble, starting with a rotation holding a cross on D.
+    /// Returns `None` if the search is cancelled.
+    pub fn solve(&self, scramble: &[Move], cancelled: &AtomicBool) -> Option<Vec<Move>> {
+        let solver = Solver::new(self.pieces());
+
+        let mut cube = Cube::new();
+        cube.apply_moves(&to_face_turns(scramble));
+        let solved = Cube::new().sticker_places();
+        let places = cube.sticker_places();
+        let start = solver.goal.map(|goal| {
+            let i = solved.iter().position(|&place| place == goal as usize);
+            i.map_or(goal, |i| places[i] as u8)
+        });
+
+        let mut path = Vec::new();
+        let mut depth = solver.heuristic(&start);
+        while !solver.search(start, depth, None, &mut path, cancelled)? {
+            // Every target is solved well within the longest search.
+            if depth == MAX_DEPTH {
+                return None;
+            }
+            depth += 1;
+        }
+
+        let moves = path.iter().map(|&m| FACE_TURNS[m]);
+        let Some(rotation) = self.rotation() else {
+            return Some(moves.collect());
+        };
+        // Face turns keep their letter in the rotated cube if it names the
+        // same face, so each is renamed after the face now in its place.
+        let renamed: Vec<Move> = Face::ALL
+            .iter()
+            .map(|&face| {
+                let m = Move::new(MoveKind::Face(face), 1);
+                to_face_turns(&[rotation, m])[0]
+            })
+            .collect();
+        let mut solution = vec![rotation];
+        solution.extend(moves.map(|m| {
+            let name = Face::ALL
+                .iter()
+                .zip(&renamed)
+                .find(|(_, r)| r.kind == m.kind)
+                .map_or(m.kind, |(&face, _)| MoveKind::Face(face));
+            Move::new(name, m.turns)
+        }));
+        Some(solution)
+    }
+}
+
+/// Every face turn, by face in the order of `Face::ALL` and then by number
+/// of turns.
+const FACE_TURNS: [Move; 18] = {
+    let mut moves = [Move {
+        kind: MoveKind::Face(Face::U),
+        turns: 1,
+    }; 18];
+    let mut i = 0;
+    while i < 18 {
+        moves[i] = Move {
+            kind: MoveKind::Face(Face::ALL[i / 3]),
+            turns: (i % 3) as u8 + 1,
+        };
+        i += 1;
+    }
+    moves
+};
+
+/// An IDA* search for four pieces, with the distances of every three of
+/// them as estimates.
+struct Solver {
+    /// Where each face turn moves a sticker, by place.
+    move_table: Vec<[u8; PLACES]>,
+    /// Places of the tracked sticker of each piece when solved.
+    goal: [u8; 4],
+    /// Least number of moves solving each three of the pieces, by the
+    /// places of their stickers.
+    pattern_tables: Vec<([usize; 3], Vec<u8>)>,
+}
+
+impl Solver {
+    fn new(goal: [u8; 4]) -> Self {
+        let solved = Cube::new().sticker_places();
+        let move_table = FACE_TURNS
+            .iter()
+            .map(|m| {
+                let mut cube = Cube::new();
+                cube.apply_move(m);
+                let mut table = [0; PLACES];
+                for (&from, to) in solved.iter().zip(cube.sticker_places()) {
+                    table[from] = to as u8;
+                }
+                table
+            })
+            .collect();
+
+        let mut solver = Self {
+            move_table,
+            goal,
+            pattern_tables: Vec::new(),
+        };
+        solver.pattern_tables = [[0, 1, 2], [0, 1, 3], [0, 2, 3], [1, 2, 3]]
+            .into_iter()
+            .map(|pieces| (pieces, solver.pattern_table(pieces)))
+            .collect();
+        solver
+    }
+
+    fn pattern_index(places: [u8; 3]) -> usize {
+        (places[0] as usize * PLACES + places[1] as usize) * PLACES + places[2] as usize
+    }
+
+    /// Finds the distance of every state of three pieces from the goal, by
+    /// a breadth first search from it.
+    fn pattern_table(&self, pieces: [usize; 3]) -> Vec<u8> {
+        let mut table = vec![UNREACHED; PLACES * PLACES * PLACES];
+        let goal = pieces.map(|piece| self.goal[piece]);
+        table[Self::pattern_index(goal)] = 0;
+
+        let mut queue = VecDeque::from([goal]);
+        while let Some(places) = queue.pop_front() {
+            let distance = table[Self::pattern_index(places)];
+            for move_table in &self.move_table {
+                let next = places.map(|place| move_table[place as usize]);
+                let index = Self::pattern_index(next);
+                if table[index] == UNREACHED {
+                    table[index] = distance + 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+        table
+    }
+
+    /// Estimates the number of moves left, never more than needed.
+    fn heuristic(&self, state: &[u8; 4]) -> u8 {
+        self.pattern_tables
+            .iter()
+            .map(|(pieces, table)| table[Self::pattern_index(pieces.map(|piece| state[piece]))])
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Searches for a solution of exactly `depth` moves, adding it to
+    /// `path`. Returns `None` if cancelled.
+    fn search(
+        &self,
+        state: [u8; 4],
+        depth: u8,
+        previous: Option<usize>,
+        path: &mut Vec<usize>,
+        cancelled: &AtomicBool,
+    ) -> Option<bool> {
+        if cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+        let estimate = self.heuristic(&state);
+        if estimate > depth {
+            return Some(false);
+        }
+        if depth == 0 {
+            return Some(state == self.goal);
+        }
+
+        for (m, move_table) in self.move_table.iter().enumerate() {
+            let face = m / 3;
+            // Turning a face twice in a row is a single turn, and turns of
+            // opposite faces are only tried in one order.
+            if let Some(previous) = previous {
+                let previous = previous / 3;
+                if face == previous || (face / 2 == previous / 2 && face < previous) {
+                    continue;
+                }
+            }
+
+            let next = state.map(|place| move_table[place as usize]);
+            path.push(m);
+            if self.search(next, depth - 1, Some(m), path, cancelled)? {
+                return Some(true);
+            }
+            path.pop();
+        }
+        Some(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{format_moves, htm_count, parse_moves};
+
+    fn solve(target: SolverTarget, scramble: &str) -> Vec<Move> {
+        let scramble = parse_moves(scramble).unwrap();
+        let solution = target.solve(&scramble, &AtomicBool::new(false)).unwrap();
+
+        // The pieces of the target are back in place after the solution.
+        let mut cube = Cube::new();
+        cube.apply_moves(&to_face_turns(&scramble));
+        cube.apply_moves(&to_face_turns(&solution));
+        let solved = Cube::new().sticker_places();
+        let places = cube.sticker_places();
+        for goal in target.pieces() {
+            let i = solved.iter().position(|&p| p == goal as usize).unwrap();
+            assert_eq!(places[i], goal as usize, "{}", target.label());
+        }
+        solution
+    }
+
+    #[test]
+    fn pick_pieces() {
+        for target in SolverTarget::ALL {
+            let pieces = target.pieces();
+            let mut distinct = pieces.to_vec();
+            distinct.sort();
+            distinct.dedup();
+            assert_eq!(distinct.len(), 4, "{}", target.label());
+        }
+    }
+
+    #[test]
+    fn solve_short_scrambles() {
+        let cross = SolverTarget::Cross(Face::D);
+        assert_eq!(solve(cross, "").len(), 0);
+        assert_eq!(format_moves(&solve(cross, "F R")), "R' F'");
+        assert_eq!(format_moves(&solve(cross, "U R U'")), "R'");
+        assert_eq!(
+            format_moves(&solve(SolverTarget::Cross(Face::U), "D2 L")),
+            "x2 L'"
+        );
+
+        let block = SolverTarget::Block(Face::D, Face::B, Face::L);
+        assert_eq!(format_moves(&solve(block, "R U2 F")), "");
+        assert_eq!(format_moves(&solve(block, "L' U2 B")), "B' L");
+    }
+
+    #[test]
+    fn solve_every_target() {
+        let scramble = "D2 F2 U' R2 U' L2 D' F2 U' B' R2 U' L F2 R' F' U' B2 L'";
+        for target in SolverTarget::ALL {
+            let solution = solve(target, scramble);
+            let limit = match target {
+                SolverTarget::Cross(_) => 8,
+                SolverTarget::Block(..) => 11,
+            };
+            assert!(htm_count(&solution) <= limit, "{}", target.label());
+        }
+    }
+
+    #[test]
+    fn cancel_search() {
+        let scramble = parse_moves("R U F").unwrap();
+        let target = SolverTarget::Cross(Face::D);
+        assert_eq!(target.solve(&scramble, &AtomicBool::new(true)), None);
+    }
+}
@@ -0,0 +1,423 @@
+use std::time::Duration;
+
+use crate::data::{
+    format_moves, generate_scramble, Penalty, SolveStatistic, SolveTime, TimePrecision,
+};
+
+/// Format of a competition round.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RoundFormat {
+    /// Best of 1.
+    BestOf1,
+    /// Best of 2.
+    BestOf2,
+    /// Best of 3.
+    BestOf3,
+    /// Mean of 3.
+    MeanOf3,
+    /// Average of 5, the best and worst attempts are trimmed.
+    #[default]
+    AverageOf5,
+}
+
+impl RoundFormat {
+    /// All round formats.
+    pub const ALL: &'static [RoundFormat] = &[
+        Self::AverageOf5,
+        Self::MeanOf3,
+        Self::BestOf3,
+        Self::BestOf2,
+        Self::BestOf1,
+    ];
+
+    /// Gets the number of attempts of the round.
+    pub fn n_attempts(&self) -> usize {
+        match self {
+            Self::BestOf1 => 1,
+            Self::BestOf2 => 2,
+            Self::BestOf3 | Self::MeanOf3 => 3,
+            Self::AverageOf5 => 5,
+        }
+    }
+
+    /// Gets the human readable name of this format.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::BestOf1 => "Best of 1",
+            Self::BestOf2 => "Best of 2",
+            Self::BestOf3 => "Best of 3",
+            Self::MeanOf3 => "Mean of 3",
+            Self::AverageOf5 => "Average of 5",
+        }
+    }
+
+    /// Returns `true` if the round is ranked by a mean or an average rather
+    /// than by the best attempt.
+    pub fn has_average(&self) -> bool {
+        matches!(self, Self::MeanOf3 | Self::AverageOf5)
+    }
+}
+
+/// A cutoff of a round: one of the first `attempts` attempts must be faster
+/// than `time` to continue with the rest of the round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cutoff {
+    /// Number of attempts to make the cutoff.
+    pub attempts: usize,
+    /// Time to beat.
+    pub time: Duration,
+}
+
+/// A time limit of a round. Attempts that reach the limit are DNF.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeLimit {
+    /// Each attempt must be faster than the limit.
+    PerSolve(Duration),
+    /// All attempts together must be faster than the limit.
+    Cumulative(Duration),
+}
+
+/// The result of a round.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RoundResult {
+    /// The best attempt.
+    pub best: Option<SolveTime>,
+    /// The mean or average of the round, if the round is ranked by one and
+    /// the cutoff has been made.
+    pub average: Option<SolveTime>,
+}
+
+/// A competition round with its scrambles, following the WCA regulations.
+///
+/// The round does not store the attempts, which are passed to its methods
+/// in order, so penalties can be changed after an attempt is recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompetitionRound {
+    pub format: RoundFormat,
+    pub cutoff: Option<Cutoff>,
+    pub time_limit: Option<TimeLimit>,
+    /// Scrambles of the group, one for each attempt.
+    pub scrambles: Vec<String>,
+}
+
+impl CompetitionRound {
+    /// Creates a new round, generating the scrambles for its group.
+    pub fn new(format: RoundFormat, cutoff: Option<Cutoff>, time_limit: Option<TimeLimit>) -> Self {
+        let scrambles = (0..format.n_attempts())
+            .map(|_| format_moves(&generate_scramble()))
+            .collect();
+        Self::with_scrambles(format, cutoff, time_limit, scrambles)
+    }
+
+    /// Creates a new round with these scrambles.
+    pub fn with_scrambles(
+        format: RoundFormat,
+        cutoff: Option<Cutoff>,
+        time_limit: Option<TimeLimit>,
+        scrambles: Vec<String>,
+    ) -> Self {
+        Self {
+            format,
+            cutoff: cutoff.filter(|c| c.attempts < format.n_attempts()),
+            time_limit,
+            scrambles,
+        }
+    }
+
+    /// Gets the penalty an attempt should get after the `previous` attempts,
+    /// which is DNF if it reaches the time limit.
+    pub fn penalty_for(&self, previous: &[SolveTime], time: SolveTime) -> Penalty {
+        let over_limit = match self.time_limit {
+            Some(TimeLimit::PerSolve(limit)) => time_spent(&time) >= limit,
+            Some(TimeLimit::Cumulative(limit)) => {
+                previous.iter().map(time_spent).sum::<Duration>() + time_spent(&time) >= limit
+            }
+            None => false,
+        };
+        if over_limit {
+            Penalty::Dnf
+        } else {
+            time.penalty
+        }
+    }
+
+    /// Returns `Some(true)` if the cutoff has been made with these attempts,
+    /// `Some(false)` if it has been missed, or `None` if it is not yet
+    /// decided. Rounds without a cutoff are always made.
+    pub fn made_cutoff(&self, times: &[SolveTime]) -> Option<bool> {
+        let Some(cutoff) = self.cutoff else {
+            return Some(true);
+        };
+
+        let attempts = &times[..times.len().min(cutoff.attempts)];
+        if attempts
+            .iter()
+            .filter_map(SolveTime::recorded_time)
+            .any(|t| t < cutoff.time)
+        {
+            Some(true)
+        } else if attempts.len() == cutoff.attempts {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Gets the number of attempts the competitor gets with these attempts.
+    pub fn n_attempts(&self, times: &[SolveTime]) -> usize {
+        match (self.made_cutoff(times), self.cutoff) {
+            (Some(false), Some(cutoff)) => cutoff.attempts,
+            _ => self.format.n_attempts(),
+        }
+    }
+
+    /// Returns `true` if the round is over after these attempts, either
+    /// because every attempt is done, the cutoff is missed, or the
+    /// cumulative time limit is used up.
+    pub fn is_finished(&self, times: &[SolveTime]) -> bool {
+        if times.len() >= self.n_attempts(times) {
+            return true;
+        }
+        match self.time_limit {
+            Some(TimeLimit::Cumulative(limit)) => {
+                times.iter().map(time_spent).sum::<Duration>() >= limit
+            }
+            _ => false,
+        }
+    }
+
+    /// Gets the scramble of the next attempt after these attempts.
+    pub fn next_scramble(&self, times: &[SolveTime]) -> Option<&str> {
+        if self.is_finished(times) {
+            return None;
+        }
+        self.scrambles.get(times.len()).map(String::as_str)
+    }
+
+    /// Gets the result of the round. Attempts that were not done because the
+    /// cumulative time limit is used up count as DNF.
+    pub fn result(&self, times: &[SolveTime]) -> RoundResult {
+        let times = self.completed_times(times);
+        let best = times.best_of_n();
+        let average =
+            if self.made_cutoff(&times) != Some(true) || times.len() < self.format.n_attempts() {
+                None
+            } else {
+                match self.format {
+                    RoundFormat::AverageOf5 => times.average_of_n(),
+                    RoundFormat::MeanOf3 => times.mean_of_n(),
+                    _ => None,
+                }
+            };
+        RoundResult { best, average }
+    }
+
+    /// Formats a results sheet of the round with this precision, with
+    /// trimmed attempts of an average in parentheses.
+    pub fn results_sheet(&self, times: &[SolveTime], precision: TimePrecision) -> String {
+        let completed = self.completed_times(times);
+        let result = self.result(times);
+        let mut sheet = vec![format!("Format: {}", self.format.label())];
+
+        if let Some(cutoff) = self.cutoff {
+            let time = SolveTime::new(cutoff.time, Penalty::Ok);
+            sheet.push(format!(
+                "Cutoff: {} attempts to get < {}",
+                cutoff.attempts,
+                time.format(precision)
+            ));
+        }
+        match self.time_limit {
+            Some(TimeLimit::PerSolve(limit)) => {
+                sheet.push(format!(
+                    "Time limit: {}",
+                    SolveTime::new(limit, Penalty::Ok).format(precision)
+                ));
+            }
+            Some(TimeLimit::Cumulative(limit)) => sheet.push(format!(
+                "Time limit: {} cumulative",
+                SolveTime::new(limit, Penalty::Ok).format(precision)
+            )),
+            None => {}
+        }
+        sheet.push(String::new());
+
+        let trimmed = if self.format == RoundFormat::AverageOf5 && result.average.is_some() {
+            [completed.best_solve_index(), completed.worst_solve_index()]
+        } else {
+            [None, None]
+        };
+        for (i, time) in completed.iter().enumerate() {
+            let time = if trimmed.contains(&Some(i)) {
+                format!("({})", time.format(precision))
+            } else {
+                time.format(precision)
+            };
+            let scramble = self.scrambles.get(i).map_or("", String::as_str);
+            sheet.push(format!("{}. {}   {}", i + 1, time, scramble));
+        }
+
+        sheet.push(String::new());
+        if self.made_cutoff(&completed) == Some(false) {
+            sheet.push("Cutoff not made".to_string());
+        }
+        sheet.push(format!(
+            "Best: {}",
+            result
+                .best
+                .map_or(String::from("-"), |t| t.format(precision))
+        ));
+        if self.format.has_average() {
+            sheet.push(format!(
+                "{}: {}",
+                self.format.label(),
+                result
+                    .average
+                    .map_or(String::from("-"), |t| t.format_average(precision))
+            ));
+        }
+        sheet.join("\n")
+    }
+
+    /// Gets the attempts, followed by DNFs for the attempts that are left
+    /// when the round is finished early by the cumulative time limit.
+    fn completed_times(&self, times: &[SolveTime]) -> Vec<SolveTime> {
+        let mut times = times.to_vec();
+        if self.is_finished(&times) {
+            let n_attempts = self.n_attempts(&times);
+            times.resize(n_attempts.max(times.len()), SolveTime::DNF);
+        }
+        times
+    }
+}
+
+/// Gets the time an attempt counts towards a time limit, which includes +2
+/// penalties and the measured time of DNF attempts.
+fn time_spent(time: &SolveTime) -> Duration {
+    time.recorded_time().unwrap_or(time.measured_time())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok(millis: u64) -> SolveTime {
+        SolveTime::new(Duration::from_millis(millis), Penalty::Ok)
+    }
+
+    fn round(
+        format: RoundFormat,
+        cutoff: Option<Cutoff>,
+        time_limit: Option<TimeLimit>,
+    ) -> CompetitionRound {
+        let scrambles = (0..format.n_attempts())
+            .map(|i| format!("R U{}", i))
+            .collect();
+        CompetitionRound::with_scrambles(format, cutoff, time_limit, scrambles)
+    }
+
+    #[test]
+    fn average_of_5() {
+        let round = round(RoundFormat::AverageOf5, None, None);
+        let times = [ok(12_000), ok(11_000), ok(15_000), ok(13_000), ok(10_000)];
+
+        assert!(!round.is_finished(&times[..4]));
+        assert!(round.is_finished(&times));
+        let result = round.result(&times);
+        assert_eq!(result.best, Some(ok(10_000)));
+        assert!(result.average.unwrap().eq_aprrox(&ok(12_000), 10));
+    }
+
+    #[test]
+    fn cutoff_made() {
+        let cutoff = Cutoff {
+            attempts: 2,
+            time: Duration::from_secs(20),
+        };
+        let round = round(RoundFormat::AverageOf5, Some(cutoff), None);
+
+        assert_eq!(round.made_cutoff(&[ok(21_000)]), None);
+        assert_eq!(round.made_cutoff(&[ok(21_000), ok(19_000)]), Some(true));
+        assert_eq!(round.n_attempts(&[ok(21_000), ok(19_000)]), 5);
+    }
+
+    #[test]
+    fn cutoff_missed() {
+        let cutoff = Cutoff {
+            attempts: 2,
+            time: Duration::from_secs(20),
+        };
+        let round = round(RoundFormat::AverageOf5, Some(cutoff), None);
+        let times = [
+            ok(21_000),
+            SolveTime::new(Duration::from_millis(18_500), Penalty::Plus2),
+        ];
+
+        assert_eq!(round.made_cutoff(&times), Some(false));
+        assert!(round.is_finished(&times));
+        assert_eq!(round.next_scramble(&times), None);
+        let result = round.result(&times);
+        assert_eq!(result.best, Some(times[1]));
+        assert_eq!(result.average, None);
+    }
+
+    #[test]
+    fn per_solve_time_limit() {
+        let limit = TimeLimit::PerSolve(Duration::from_secs(60));
+        let round = round(RoundFormat::MeanOf3, None, Some(limit));
+
+        assert_eq!(round.penalty_for(&[], ok(59_000)), Penalty::Ok);
+        assert_eq!(round.penalty_for(&[], ok(60_000)), Penalty::Dnf);
+        assert_eq!(
+            round.penalty_for(
+                &[],
+                SolveTime::new(Duration::from_millis(58_500), Penalty::Plus2)
+            ),
+            Penalty::Dnf
+        );
+    }
+
+    #[test]
+    fn cumulative_time_limit() {
+        let limit = TimeLimit::Cumulative(Duration::from_secs(120));
+        let round = round(RoundFormat::BestOf3, None, Some(limit));
+        let times = [ok(50_000), ok(50_000)];
+
+        assert_eq!(round.penalty_for(&times[..1], ok(50_000)), Penalty::Ok);
+        assert_eq!(round.penalty_for(&times, ok(20_000)), Penalty::Dnf);
+
+        let times = [
+            ok(50_000),
+            ok(50_000),
+            SolveTime::new(Duration::from_secs(20), Penalty::Dnf),
+        ];
+        assert!(round.is_finished(&times));
+
+        let times = [
+            ok(70_000),
+            SolveTime::new(Duration::from_secs(50), Penalty::Dnf),
+        ];
+        assert!(round.is_finished(&times));
+        assert_eq!(round.result(&times).best, Some(ok(70_000)));
+    }
+
+    #[test]
+    fn scrambles_follow_attempts() {
+        let round = round(RoundFormat::MeanOf3, None, None);
+
+        assert_eq!(round.next_scramble(&[]), Some("R U0"));
+        assert_eq!(round.next_scramble(&[ok(10_000)]), Some("R U1"));
+    }
+
+    #[test]
+    fn results_sheet_trims_average() {
+        let round = round(RoundFormat::AverageOf5, None, None);
+        let times = [ok(12_000), ok(11_000), ok(15_000), ok(13_000), ok(10_000)];
+        let sheet = round.results_sheet(&times, TimePrecision::Centiseconds);
+
+        assert!(sheet.contains("3. (15.00)   R U2"));
+        assert!(sheet.contains("5. (10.00)   R U4"));
+        assert!(sheet.contains("1. 12.00   R U0"));
+        assert!(sheet.ends_with("Average of 5: 12.00"));
+    }
+}
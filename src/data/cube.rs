@@ -0,0 +1,408 @@
+use crate::data::{Axis, Face, Move, MoveKind, Slice};
+
+/// A vector with coordinates between -1 and 1. The x axis points to R, the
+/// y axis to U and the z axis to F.
+type Vector = [i8; 3];
+
+/// A sticker on the cube.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Sticker {
+    /// Position of the piece holding the sticker.
+    position: Vector,
+    /// Direction the sticker is facing.
+    normal: Vector,
+    /// The face the sticker belongs to on a solved cube.
+    color: Face,
+}
+
+/// A simulated 3x3x3 cube.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cube {
+    stickers: Vec<Sticker>,
+}
+
+impl Cube {
+    /// Creates a solved cube.
+    pub fn new() -> Self {
+        let mut stickers = Vec::with_capacity(54);
+        for face in Face::ALL {
+            let normal = face_normal(face);
+            let axis = axis_index(face.axis());
+            for a in -1..=1 {
+                for b in -1..=1 {
+                    let mut position = [0; 3];
+                    position[axis] = normal[axis];
+                    position[(axis + 1) % 3] = a;
+                    position[(axis + 2) % 3] = b;
+                    stickers.push(Sticker {
+                        position,
+                        normal,
+                        color: face,
+                    });
+                }
+            }
+        }
+        Self { stickers }
+    }
+
+    /// Applies a move to this cube.
+    pub fn apply_move(&mut self, m: &Move) {
+        // The turning layers are found by their distance along the axis.
+        let (axis, in_layer): (Vector, fn(i8) -> bool) = match m.kind {
+            MoveKind::Face(face) => (face_normal(face), |d| d == 1),
+            MoveKind::Wide(face) => (face_normal(face), |d| d >= 0),
+            MoveKind::Slice(slice) => (slice_normal(slice), |d| d == 0),
+            MoveKind::Rotation(axis) => (axis_normal(axis), |_| true),
+        };
+
+        for sticker in self.stickers.iter_mut() {
+            if !in_layer(dot(&sticker.position, &axis)) {
+                continue;
+            }
+            for _ in 0..m.turns {
+                sticker.position = rotate(&sticker.position, &axis);
+                sticker.normal = rotate(&sticker.normal, &axis);
+            }
+        }
+    }
+
+    /// Applies a sequence of moves to this cube.
+    pub fn apply_moves(&mut self, moves: &[Move]) {
+        for m in moves {
+            self.apply_move(m);
+        }
+    }
+
+    /// Returns `true` if every face of the cube has a single color, in any
+    /// orientation of the whole cube.
+    pub fn is_solved(&self) -> bool {
+        Face::ALL.iter().all(|&face| {
+            let normal = face_normal(face);
+            let mut colors = self
+                .stickers
+                .iter()
+                .filter(|s| s.normal == normal)
+                .map(|s| s.color);
+            let first = colors.next();
+            colors.all(|c| Some(c) == first)
+        })
+    }
+
+    /// Returns `true` if the first two layers are solved, with the centers
+    /// in place.
+    pub fn is_f2l_solved(&self) -> bool {
+        self.stickers
+            .iter()
+            .filter(|s| s.position[1] < 1)
+            .all(|s| face_normal(s.color) == s.normal)
+    }
+
+    /// Returns `true` if every sticker facing U has the color of U.
+    pub fn is_last_layer_oriented(&self) -> bool {
+        let up = face_normal(Face::U);
+        self.stickers
+            .iter()
+            .filter(|s| s.normal == up)
+            .all(|s| s.color == Face::U)
+    }
+
+    /// Gets the colors of the stickers of a face, row by row as seen from
+    /// outside the cube. Side faces are seen with U at the top, U with B at
+    /// the top and D with F at the top.
+    pub fn face_colors(&self, face: Face) -> [Face; 9] {
+        let normal = face_normal(face);
+        let (right, down) = face_frame(face);
+
+        let mut colors = [face; 9];
+        for (i, color) in colors.iter_mut().enumerate() {
+            let (row, column) = (i as i8 / 3 - 1, i as i8 % 3 - 1);
+            let position = [0, 1, 2].map(|k| normal[k] + right[k] * column + down[k] * row);
+            if let Some(sticker) = self
+                .stickers
+                .iter()
+                .find(|s| s.position == position && s.normal == normal)
+            {
+                *color = sticker.color;
+            }
+        }
+        colors
+    }
+
+    /// Gets the place of every sticker, as `9 × f + i` for the sticker at
+    /// index `i` of the colors of `Face::ALL[f]`. Stickers are listed in the
+    /// same order on every cube, so comparing with a solved cube tells where
+    /// each sticker went.
+    pub fn sticker_places(&self) -> Vec<usize> {
+        self.stickers
+            .iter()
+            .map(|s| sticker_place(&s.position, &s.normal))
+            .collect()
+    }
+
+    /// Gets the places of the stickers of each piece of a solved cube,
+    /// centers included.
+    pub fn pieces() -> Vec<Vec<usize>> {
+        let mut pieces: Vec<(Vector, Vec<usize>)> = Vec::new();
+        for sticker in Self::new().stickers {
+            let place = sticker_place(&sticker.position, &sticker.normal);
+            match pieces.iter_mut().find(|(p, _)| *p == sticker.position) {
+                Some((_, places)) => places.push(place),
+                None => pieces.push((sticker.position, vec![place])),
+            }
+        }
+        pieces.into_iter().map(|(_, places)| places).collect()
+    }
+
+    /// Describes the last layer by the colors of the 20 stickers around its
+    /// center, in a fixed order of their places. Corners and edges can be left out, to tell
+    /// states apart only by the pieces that matter.
+    pub fn last_layer_colors(&self, corners: bool, edges: bool) -> String {
+        let mut stickers: Vec<&Sticker> = self
+            .stickers
+            .iter()
+            .filter(|s| s.position[1] == 1)
+            .filter(|s| {
+                let is_corner = s.position[0] != 0 && s.position[2] != 0;
+                let is_edge = (s.position[0] == 0) != (s.position[2] == 0);
+                (is_corner && corners) || (is_edge && edges)
+            })
+            .collect();
+        stickers.sort_by_key(|s| (s.position, s.normal));
+        stickers.iter().map(|s| s.color.to_char()).collect()
+    }
+}
+
+impl Default for Cube {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn axis_index(axis: Axis) -> usize {
+    match axis {
+        Axis::X => 0,
+        Axis::Y => 1,
+        Axis::Z => 2,
+    }
+}
+
+fn axis_normal(axis: Axis) -> Vector {
+    let mut v = [0; 3];
+    v[axis_index(axis)] = 1;
+    v
+}
+
+fn face_normal(face: Face) -> Vector {
+    match face {
+        Face::R => [1, 0, 0],
+        Face::L => [-1, 0, 0],
+        Face::U => [0, 1, 0],
+        Face::D => [0, -1, 0],
+        Face::F => [0, 0, 1],
+        Face::B => [0, 0, -1],
+    }
+}
+
+/// Gets the directions to the right and down of a face, as seen from
+/// outside the cube. Side faces are seen with U at the top, U with B at the
+/// top and D with F at the top.
+fn face_frame(face: Face) -> (Vector, Vector) {
+    match face {
+        Face::U => ([1, 0, 0], [0, 0, 1]),
+        Face::D => ([1, 0, 0], [0, 0, -1]),
+        _ => (cross(&[0, 1, 0], &face_normal(face)), [0, -1, 0]),
+    }
+}
+
+/// Gets the place of a sticker, as `9 × f + i` for the sticker at index `i`
+/// of the colors of `Face::ALL[f]`.
+fn sticker_place(position: &Vector, normal: &Vector) -> usize {
+    let (f, face) = Face::ALL
+        .iter()
+        .enumerate()
+        .find(|(_, &face)| face_normal(face) == *normal)
+        .map(|(f, &face)| (f, face))
+        .expect("a sticker should face a face of the cube");
+    let (right, down) = face_frame(face);
+    let row = (dot(position, &down) + 1) as usize;
+    let column = (dot(position, &right) + 1) as usize;
+    9 * f + 3 * row + column
+}
+
+fn slice_normal(slice: Slice) -> Vector {
+    match slice {
+        Slice::M => face_normal(Face::L),
+        Slice::E => face_normal(Face::D),
+        Slice::S => face_normal(Face::F),
+    }
+}
+
+fn dot(a: &Vector, b: &Vector) -> i8 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: &Vector, b: &Vector) -> Vector {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Rotates `v` a quarter turn clockwise, as seen when looking at the cube
+/// from the direction of `axis`.
+fn rotate(v: &Vector, axis: &Vector) -> Vector {
+    let c = cross(axis, v);
+    let d = dot(axis, v);
+    [axis[0] * d - c[0], axis[1] * d - c[1], axis[2] * d - c[2]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{invert_moves, parse_moves, to_face_turns};
+
+    fn cube_after(moves: &str) -> Cube {
+        let mut cube = Cube::new();
+        cube.apply_moves(&parse_moves(moves).unwrap());
+        cube
+    }
+
+    #[test]
+    fn new_cube_is_solved() {
+        let cube = Cube::new();
+        assert_eq!(cube.stickers.len(), 54);
+        assert!(cube.is_solved());
+    }
+
+    #[test]
+    fn single_move_unsolves() {
+        assert!(!cube_after("R").is_solved());
+        assert!(!cube_after("M").is_solved());
+        assert!(!cube_after("Rw").is_solved());
+    }
+
+    #[test]
+    fn rotations_keep_solved() {
+        assert!(cube_after("x y2 z'").is_solved());
+    }
+
+    #[test]
+    fn sexy_move_has_order_six() {
+        let sexy = "R U R' U' ".repeat(6);
+        assert!(cube_after(&sexy).is_solved());
+        assert!(!cube_after(&"R U R' U' ".repeat(3)).is_solved());
+    }
+
+    #[test]
+    fn scramble_and_inverse_solve() {
+        let scramble = parse_moves("R' U' F D2 L2 B' R2 U' F2 R U2 B2 L' Rw M2 x").unwrap();
+        let mut cube = Cube::new();
+        cube.apply_moves(&scramble);
+        assert!(!cube.is_solved());
+        cube.apply_moves(&parse_moves("x' M2 Rw' L B2 U2 R' F2 U R2 B L2 D2 F' U R").unwrap());
+        assert!(cube.is_solved());
+    }
+
+    #[test]
+    fn equivalent_moves() {
+        assert_eq!(cube_after("Rw"), cube_after("R M'"));
+        assert_eq!(cube_after("Rw"), cube_after("L x"));
+        assert_eq!(cube_after("y"), cube_after("U E' D'"));
+        assert_eq!(cube_after("z"), cube_after("F S B'"));
+    }
+
+    #[test]
+    fn face_turns_keep_state() {
+        let moves = parse_moves("Rw U x' M2 U M2 y S R S' y' E2 U E2").unwrap();
+        let mut cube = Cube::new();
+        cube.apply_moves(&moves);
+        cube.apply_moves(&invert_moves(&to_face_turns(&moves)));
+        assert!(cube.is_solved());
+    }
+
+    #[test]
+    fn read_face_colors() {
+        assert_eq!(Cube::new().face_colors(Face::R), [Face::R; 9]);
+
+        // R brings F stickers to the right column of U, and U stickers to
+        // the right column of B, which is its left column seen from behind.
+        let cube = cube_after("R");
+        let up = cube.face_colors(Face::U);
+        assert_eq!([up[2], up[5], up[8]], [Face::F; 3]);
+        assert_eq!(up[0], Face::U);
+        let back = cube.face_colors(Face::B);
+        assert_eq!([back[0], back[3], back[6]], [Face::U; 3]);
+
+        // U brings R stickers to the top row of F.
+        let front = cube_after("U").face_colors(Face::F);
+        assert_eq!(&front[..3], &[Face::R; 3]);
+        assert_eq!(&front[3..], &[Face::F; 6]);
+    }
+
+    #[test]
+    fn follow_stickers() {
+        let solved = Cube::new().sticker_places();
+        let mut places = solved.clone();
+        places.sort();
+        places.dedup();
+        assert_eq!(places.len(), 54);
+
+        let pieces = Cube::pieces();
+        assert_eq!(pieces.len(), 26);
+        assert_eq!(pieces.iter().filter(|p| p.len() == 2).count(), 12);
+        assert_eq!(pieces.iter().filter(|p| p.len() == 3).count(), 8);
+
+        // U turns the sticker of UF at index 7 of U to index 3 of U, where
+        // UL was.
+        let turned = cube_after("U").sticker_places();
+        let i = solved.iter().position(|&p| p == 7).unwrap();
+        assert_eq!(turned[i], 3);
+    }
+
+    #[test]
+    fn describe_last_layer() {
+        let sune = cube_after("R U R' U R U2 R'");
+        assert!(sune.is_f2l_solved());
+        assert!(!sune.is_last_layer_oriented());
+        assert!(!cube_after("R").is_f2l_solved());
+        assert!(cube_after("U").is_last_layer_oriented());
+
+        let solved = Cube::new();
+        assert_eq!(solved.last_layer_colors(true, true).len(), 20);
+        assert_eq!(solved.last_layer_colors(true, false).len(), 12);
+        let h_perm = cube_after("M2 U M2 U2 M2 U M2");
+        assert!(h_perm.is_f2l_solved());
+        assert_eq!(
+            h_perm.last_layer_colors(true, false),
+            solved.last_layer_colors(true, false)
+        );
+        assert_ne!(
+            h_perm.last_layer_colors(true, true),
+            solved.last_layer_colors(true, true)
+        );
+    }
+
+    #[test]
+    fn move_directions() {
+        // R brings the F sticker to U.
+        let mut cube = Cube::new();
+        cube.apply_move(&parse_moves("R").unwrap()[0]);
+        let sticker = cube
+            .stickers
+            .iter()
+            .find(|s| s.position == [1, 1, 1] && s.normal == [0, 1, 0])
+            .unwrap();
+        assert_eq!(sticker.color, Face::F);
+
+        // U brings the R sticker to F.
+        let mut cube = Cube::new();
+        cube.apply_move(&parse_moves("U").unwrap()[0]);
+        let sticker = cube
+            .stickers
+            .iter()
+            .find(|s| s.position == [0, 1, 1] && s.normal == [0, 0, 1])
+            .unwrap();
+        assert_eq!(sticker.color, Face::R);
+    }
+}
@@ -0,0 +1,210 @@
+use std::{collections::BTreeMap, time::Duration, time::SystemTime};
+
+use chrono::{Datelike, Days, NaiveDate};
+
+use crate::data::{SolveStatistic, SolveTime, TimePrecision};
+
+/// A calendar period that solves are grouped by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatePeriod {
+    Day,
+    /// A week, starting on Monday.
+    Week,
+    Month,
+}
+
+impl DatePeriod {
+    /// Gets the first day of the period containing `date`.
+    pub fn start_of(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Day => date,
+            Self::Week => date - Days::new(date.weekday().num_days_from_monday() as u64),
+            Self::Month => date.with_day(1).unwrap_or(date),
+        }
+    }
+
+    /// Formats the name of the period starting on `start`, e.g.
+    /// `Mon, 2024-03-04`, `Week of 2024-03-04` or `March 2024`.
+    pub fn format(&self, start: NaiveDate) -> String {
+        match self {
+            Self::Day => start.format("%a, %Y-%m-%d").to_string(),
+            Self::Week => start.format("Week of %Y-%m-%d").to_string(),
+            Self::Month => start.format("%B %Y").to_string(),
+        }
+    }
+}
+
+/// Statistics of the solves in a calendar period.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PeriodSummary {
+    /// The first day of the period.
+    pub start: NaiveDate,
+    pub n_solves: usize,
+    /// The mean of the solves that are not DNF.
+    pub mean: Option<SolveTime>,
+    pub best: Option<SolveTime>,
+    /// The best average of 5 consecutive solves within the period.
+    pub best_ao5: Option<SolveTime>,
+    /// The total measured time of the solves, including DNFs.
+    pub time_spent: Duration,
+}
+
+impl PeriodSummary {
+    /// Summarizes these solves, done in the period starting on `start`.
+    pub fn new(start: NaiveDate, times: &[SolveTime]) -> Self {
+        Self {
+            start,
+            n_solves: times.len(),
+            mean: times.mean_of_successes(),
+            best: times.best_of_n().filter(|t| !t.is_dnf()),
+            best_ao5: times
+                .windows(5)
+                .filter_map(|solves| solves.average_of_n())
+                .filter(|t| !t.is_dnf())
+                .min(),
+            time_spent: times.iter().map(SolveTime::measured_time).sum(),
+        }
+    }
+
+    /// Formats the statistics on one line with this precision, e.g.
+    /// `12 solves · Mean 13.20 · Best 11.02 · Best Ao5 12.80 · 2:41 spent`.
+    pub fn description(&self, precision: TimePrecision) -> String {
+        let mut parts = vec![if self.n_solves == 1 {
+            "1 solve".to_string()
+        } else {
+            format!("{} solves", self.n_solves)
+        }];
+        if let Some(mean) = self.mean {
+            parts.push(format!("Mean {}", mean.format_average(precision)));
+        }
+        if let Some(best) = self.best {
+            parts.push(format!("Best {}", best.format(precision)));
+        }
+        if let Some(best_ao5) = self.best_ao5 {
+            parts.push(format!("Best Ao5 {}", best_ao5.format_average(precision)));
+        }
+        parts.push(format!("{} spent", format_time_spent(self.time_spent)));
+        parts.join(" · ")
+    }
+}
+
+/// Formats a time spent solving as `h:mm:ss` or `m:ss`.
+pub fn format_time_spent(time: Duration) -> String {
+    let seconds = time.as_secs();
+    if seconds >= 3600 {
+        format!(
+            "{}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    } else {
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+/// Gets the calendar day of a timestamp in local time.
+pub fn local_date(timestamp: SystemTime) -> NaiveDate {
+    chrono::DateTime::<chrono::Local>::from(timestamp).date_naive()
+}
+
+/// Groups solves by calendar period and summarizes each period, oldest
+/// first. Solves are given in the order they were done, with their local
+/// date.
+pub fn summarize_by_period(
+    solves: &[(NaiveDate, SolveTime)],
+    period: DatePeriod,
+) -> Vec<PeriodSummary> {
+    let mut groups: BTreeMap<NaiveDate, Vec<SolveTime>> = BTreeMap::new();
+    for &(date, time) in solves {
+        groups.entry(period.start_of(date)).or_default().push(time);
+    }
+    groups
+        .iter()
+        .map(|(&start, times)| PeriodSummary::new(start, times))
+        .collect()
+}
+
+/// Summarizes the solves done on `date`, if any.
+pub fn summarize_day(solves: &[(NaiveDate, SolveTime)], date: NaiveDate) -> Option<PeriodSummary> {
+    let times: Vec<SolveTime> = solves
+        .iter()
+        .filter(|(d, _)| *d == date)
+        .map(|&(_, time)| time)
+        .collect();
+    if times.is_empty() {
+        None
+    } else {
+        Some(PeriodSummary::new(date, &times))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Penalty;
+
+    fn ok(millis: u64) -> SolveTime {
+        SolveTime::new(Duration::from_millis(millis), Penalty::Ok)
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn period_starts() {
+        // 2024-03-07 is a Thursday.
+        let day = date(2024, 3, 7);
+        assert_eq!(DatePeriod::Day.start_of(day), day);
+        assert_eq!(DatePeriod::Week.start_of(day), date(2024, 3, 4));
+        assert_eq!(DatePeriod::Month.start_of(day), date(2024, 3, 1));
+        assert_eq!(
+            DatePeriod::Week.format(date(2024, 3, 4)),
+            "Week of 2024-03-04"
+        );
+        assert_eq!(DatePeriod::Month.format(date(2024, 3, 1)), "March 2024");
+    }
+
+    #[test]
+    fn summarize_days_and_weeks() {
+        let solves = [
+            (date(2024, 3, 3), ok(14_000)),
+            (date(2024, 3, 4), ok(12_000)),
+            (date(2024, 3, 4), SolveTime::DNF),
+            (date(2024, 3, 5), ok(13_000)),
+        ];
+
+        let days = summarize_by_period(&solves, DatePeriod::Day);
+        assert_eq!(days.len(), 3);
+        assert_eq!(days[1].start, date(2024, 3, 4));
+        assert_eq!(days[1].n_solves, 2);
+        assert_eq!(days[1].mean, Some(ok(12_000)));
+        assert_eq!(days[1].best, Some(ok(12_000)));
+
+        let weeks = summarize_by_period(&solves, DatePeriod::Week);
+        assert_eq!(weeks.len(), 2);
+        assert_eq!(weeks[1].start, date(2024, 3, 4));
+        assert_eq!(weeks[1].n_solves, 3);
+        assert_eq!(weeks[1].mean, Some(ok(12_500)));
+        assert_eq!(weeks[1].best_ao5, None);
+    }
+
+    #[test]
+    fn summary_description() {
+        let times = [ok(12_000), ok(13_000), ok(14_000), ok(11_000), ok(15_000)];
+        let summary = PeriodSummary::new(date(2024, 3, 4), &times);
+        assert_eq!(summary.best_ao5, Some(ok(13_000)));
+        assert_eq!(summary.time_spent, Duration::from_secs(65));
+        assert_eq!(
+            summary.description(TimePrecision::Centiseconds),
+            "5 solves · Mean 13.00 · Best 11.00 · Best Ao5 13.00 · 1:05 spent"
+        );
+        assert!(summarize_day(&[(date(2024, 3, 4), ok(1_000))], date(2024, 3, 5)).is_none());
+    }
+
+    #[test]
+    fn format_long_time_spent() {
+        assert_eq!(format_time_spent(Duration::from_secs(3_725)), "1:02:05");
+    }
+}
@@ -0,0 +1,264 @@
+use std::cmp::Ordering;
+use std::{fmt::Display, time::Duration};
+
+use crate::data::{htm_count, parse_moves, Cube, Move, MoveKind, NotationError};
+
+/// Time limit of a Fewest Moves attempt.
+pub const FEWEST_MOVES_TIME_LIMIT: Duration = Duration::from_secs(60 * 60);
+/// Maximum number of moves of a Fewest Moves solution.
+pub const MAX_SOLUTION_MOVES: u32 = 80;
+
+/// Error when checking a Fewest Moves solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FewestMovesError {
+    /// The solution is not valid notation.
+    Notation(NotationError),
+    /// The solution contains a slice move, which is not allowed.
+    SliceMove { index: usize },
+    /// The solution is longer than allowed.
+    TooManyMoves { count: u32 },
+    /// The solution does not solve the scramble.
+    NotSolved,
+}
+
+impl Display for FewestMovesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Notation(e) => write!(f, "{}", e),
+            Self::SliceMove { index } => write!(f, "Move {} is a slice move", index + 1),
+            Self::TooManyMoves { count } => write!(
+                f,
+                "Solution has {} moves, more than {}",
+                count, MAX_SOLUTION_MOVES
+            ),
+            Self::NotSolved => write!(f, "Solution does not solve the cube"),
+        }
+    }
+}
+
+impl std::error::Error for FewestMovesError {}
+
+impl From<NotationError> for FewestMovesError {
+    fn from(value: NotationError) -> Self {
+        Self::Notation(value)
+    }
+}
+
+/// Checks a Fewest Moves solution against the WCA regulations and returns
+/// its move count.
+///
+/// Face turns and wide moves count as one move and rotations are not
+/// counted. Slice moves are not allowed.
+pub fn check_solution(scramble: &[Move], solution: &str) -> Result<u32, FewestMovesError> {
+    let moves = parse_moves(solution)?;
+    if let Some(index) = moves
+        .iter()
+        .position(|m| matches!(m.kind, MoveKind::Slice(_)))
+    {
+        return Err(FewestMovesError::SliceMove { index });
+    }
+
+    let count = htm_count(&moves);
+    if count > MAX_SOLUTION_MOVES {
+        return Err(FewestMovesError::TooManyMoves { count });
+    }
+
+    let mut cube = Cube::new();
+    cube.apply_moves(scramble);
+    cube.apply_moves(&moves);
+    if !cube.is_solved() {
+        return Err(FewestMovesError::NotSolved);
+    }
+
+    Ok(count)
+}
+
+/// Result of a Fewest Moves attempt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FewestMovesResult {
+    /// The solution as written.
+    pub solution: String,
+    /// Number of moves of the solution, or `None` if the solution is
+    /// invalid and the attempt is DNF.
+    pub move_count: Option<u32>,
+}
+
+impl FewestMovesResult {
+    /// Creates a new `FewestMovesResult` by checking `solution` against
+    /// `scramble`.
+    pub fn new(scramble: &[Move], solution: &str) -> Self {
+        Self {
+            solution: solution.to_string(),
+            move_count: check_solution(scramble, solution).ok(),
+        }
+    }
+
+    /// Returns `true` if the attempt is DNF.
+    pub fn is_dnf(&self) -> bool {
+        self.move_count.is_none()
+    }
+}
+
+impl Display for FewestMovesResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.move_count {
+            Some(count) => write!(f, "{}", count),
+            None => write!(f, "DNF"),
+        }
+    }
+}
+
+/// A number of moves of a Fewest Moves attempt, or a mean of them.
+///
+/// Means are kept to hundredths of a move, as the WCA does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MoveCount {
+    /// Hundredths of a move, or `None` if DNF.
+    hundredths: Option<u32>,
+}
+
+impl MoveCount {
+    pub const DNF: Self = Self { hundredths: None };
+
+    pub fn new(moves: u32) -> Self {
+        Self {
+            hundredths: Some(moves * 100),
+        }
+    }
+
+    pub fn is_dnf(&self) -> bool {
+        self.hundredths.is_none()
+    }
+
+    /// Calculates the mean of 3 move counts, which is DNF if any of them is.
+    /// Returns `None` if there are not exactly 3 move counts.
+    pub fn mean_of_3(counts: &[MoveCount]) -> Option<Self> {
+        if counts.len() != 3 {
+            return None;
+        }
+        let total: Option<u32> = counts.iter().map(|count| count.hundredths).sum();
+        Some(Self {
+            hundredths: total.map(|total| (total as f64 / 3.0).round() as u32),
+        })
+    }
+
+    /// Formats a mean of move counts, e.g. `28.33`.
+    pub fn format_mean(&self) -> String {
+        match self.hundredths {
+            Some(hundredths) => format!("{}.{:02}", hundredths / 100, hundredths % 100),
+            None => "DNF".to_string(),
+        }
+    }
+}
+
+impl From<&FewestMovesResult> for MoveCount {
+    fn from(result: &FewestMovesResult) -> Self {
+        result.move_count.map_or(Self::DNF, Self::new)
+    }
+}
+
+/// DNF move counts are ranked after every other count.
+impl Ord for MoveCount {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.hundredths, other.hundredths) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+impl PartialOrd for MoveCount {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Formats a single move count, e.g. `28`.
+impl Display for MoveCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.hundredths {
+            Some(hundredths) => write!(f, "{}", hundredths / 100),
+            None => write!(f, "DNF"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRAMBLE: &str = "R' U' F D2 L2 B' R2 U' F2 R U2 B2 L' R' U' F";
+    const INVERSE: &str = "F' U R L B2 U2 R' F2 U R2 B L2 D2 F' U R";
+
+    fn scramble() -> Vec<Move> {
+        parse_moves(SCRAMBLE).unwrap()
+    }
+
+    #[test]
+    fn accept_valid_solution() {
+        assert_eq!(check_solution(&scramble(), INVERSE), Ok(16));
+    }
+
+    #[test]
+    fn rotations_are_not_counted() {
+        let scramble = parse_moves("Rw").unwrap();
+        assert_eq!(check_solution(&scramble, "x' L'"), Ok(1));
+        assert_eq!(check_solution(&scramble, "y y' Rw'"), Ok(1));
+    }
+
+    #[test]
+    fn reject_invalid_solutions() {
+        assert_eq!(
+            check_solution(&scramble(), "R U M"),
+            Err(FewestMovesError::SliceMove { index: 2 })
+        );
+        assert_eq!(
+            check_solution(&scramble(), "R U"),
+            Err(FewestMovesError::NotSolved)
+        );
+        assert!(matches!(
+            check_solution(&scramble(), "R U K"),
+            Err(FewestMovesError::Notation(_))
+        ));
+        assert_eq!(
+            check_solution(&scramble(), &"R U ".repeat(41)),
+            Err(FewestMovesError::TooManyMoves { count: 82 })
+        );
+    }
+
+    #[test]
+    fn mean_of_move_counts() {
+        let results = [
+            FewestMovesResult {
+                solution: String::new(),
+                move_count: Some(28),
+            },
+            FewestMovesResult {
+                solution: String::new(),
+                move_count: Some(30),
+            },
+            FewestMovesResult {
+                solution: String::new(),
+                move_count: Some(27),
+            },
+        ];
+        let counts: Vec<MoveCount> = results.iter().map(MoveCount::from).collect();
+        assert_eq!(counts[0].to_string(), "28");
+        assert_eq!(
+            MoveCount::mean_of_3(&counts).unwrap().format_mean(),
+            "28.33"
+        );
+        assert_eq!(MoveCount::mean_of_3(&counts[..2]), None);
+
+        let dnf = FewestMovesResult::new(&scramble(), "R");
+        assert!(dnf.is_dnf());
+        assert_eq!(dnf.to_string(), "DNF");
+
+        let counts = [counts[0], MoveCount::from(&dnf), counts[2]];
+        assert!(counts[0] < counts[1]);
+        assert_eq!(MoveCount::mean_of_3(&counts), Some(MoveCount::DNF));
+        assert_eq!(MoveCount::DNF.format_mean(), "DNF");
+    }
+}
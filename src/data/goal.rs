@@ -0,0 +1,216 @@
+use std::time::Duration;
+
+use crate::data::{parse_time, Penalty, SolveStatistic, SolveTime, TimePrecision};
+
+/// A practice target of a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    /// The latest average of `n` solves is below `target`.
+    Average { n: usize, target: Duration },
+    /// At least `count` solves are done today.
+    SolvesPerDay { count: usize },
+    /// Less than `percent` percent of the solves of the session are DNF.
+    DnfRate { percent: u32 },
+}
+
+impl Goal {
+    /// Parses a goal written as `sub-12 ao100`, `100 solves per day` or
+    /// `DNF rate below 10%`, ignoring case. Labels of goals are parsed back
+    /// into the same goal.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().to_lowercase().replace("sub-", "sub ");
+        let words: Vec<&str> = text
+            .split_whitespace()
+            .filter(|word| !matches!(*word, "sub" | "below" | "under" | "<"))
+            .collect();
+
+        match words.as_slice() {
+            ["dnf", "rate", percent] | ["dnf", percent] => {
+                let percent = percent.trim_end_matches('%').parse().ok()?;
+                (1..=100)
+                    .contains(&percent)
+                    .then_some(Self::DnfRate { percent })
+            }
+            [count, "solve" | "solves", "per" | "a", "day"] | [count, "solves", "daily"] => {
+                let count = count.parse().ok().filter(|&count| count > 0)?;
+                Some(Self::SolvesPerDay { count })
+            }
+            [first, second] => {
+                let (average, target) = if first.starts_with("ao") {
+                    (first, second)
+                } else {
+                    (second, first)
+                };
+                let n = average
+                    .strip_prefix("ao")?
+                    .parse()
+                    .ok()
+                    .filter(|&n| n >= 3)?;
+                let target = parse_time(target).filter(|target| !target.is_zero())?;
+                Some(Self::Average { n, target })
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets the name of this goal, e.g. `Sub-12 Ao100`.
+    pub fn label(&self) -> String {
+        match self {
+            Self::Average { n, target } => format!("Sub-{} Ao{}", format_target(*target), n),
+            Self::SolvesPerDay { count: 1 } => "1 Solve per Day".to_string(),
+            Self::SolvesPerDay { count } => format!("{} Solves per Day", count),
+            Self::DnfRate { percent } => format!("DNF Rate below {}%", percent),
+        }
+    }
+
+    /// Evaluates the progress towards this goal, given the times of the
+    /// session in the order they were done and the number of solves done
+    /// today. Times are described with this precision.
+    pub fn progress(
+        &self,
+        times: &[SolveTime],
+        solves_today: usize,
+        precision: TimePrecision,
+    ) -> GoalProgress {
+        match *self {
+            Self::Average { n, target } => {
+                if times.len() < n {
+                    return GoalProgress {
+                        fraction: times.len() as f64 / n as f64,
+                        met: false,
+                        description: format!("{} of {n} solves for a first Ao{n}", times.len()),
+                    };
+                }
+                let average = times[times.len() - n..]
+                    .average_of_n()
+                    .unwrap_or(SolveTime::DNF);
+                let target_time = SolveTime::new(target, Penalty::Ok);
+                GoalProgress {
+                    fraction: average.recorded_time().map_or(0.0, |average| {
+                        (target.as_secs_f64() / average.as_secs_f64()).min(1.0)
+                    }),
+                    met: average < target_time,
+                    description: format!(
+                        "Ao{} {} · Target below {}",
+                        n,
+                        average.format_average(precision),
+                        target_time.format(precision)
+                    ),
+                }
+            }
+            Self::SolvesPerDay { count } => GoalProgress {
+                fraction: (solves_today as f64 / count as f64).min(1.0),
+                met: solves_today >= count,
+                description: format!("{} of {} solves today", solves_today, count),
+            },
+            Self::DnfRate { percent } => {
+                if times.is_empty() {
+                    return GoalProgress {
+                        fraction: 0.0,
+                        met: false,
+                        description: "No solves yet".to_string(),
+                    };
+                }
+                let n_dnf = times.iter().filter(|time| time.is_dnf()).count();
+                let rate = 100.0 * n_dnf as f64 / times.len() as f64;
+                let met = rate < percent as f64;
+                GoalProgress {
+                    fraction: if met { 1.0 } else { percent as f64 / rate },
+                    met,
+                    description: format!("DNF rate {:.1}% · Target below {}%", rate, percent),
+                }
+            }
+        }
+    }
+}
+
+/// How close a session is to a goal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoalProgress {
+    /// Progress between 0 and 1, reaching 1 when the goal is met.
+    pub fraction: f64,
+    pub met: bool,
+    pub description: String,
+}
+
+/// Formats the target time of a goal without trailing zeros, e.g. `12` or
+/// `1:30.5`.
+fn format_target(target: Duration) -> String {
+    SolveTime::new(target, Penalty::Ok)
+        .format(TimePrecision::Milliseconds)
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok(millis: u64) -> SolveTime {
+        SolveTime::new(Duration::from_millis(millis), Penalty::Ok)
+    }
+
+    #[test]
+    fn parse_goals() {
+        let sub12 = Goal::Average {
+            n: 100,
+            target: Duration::from_secs(12),
+        };
+        assert_eq!(Goal::parse("sub-12 ao100"), Some(sub12));
+        assert_eq!(Goal::parse("Ao100 under 12.00"), Some(sub12));
+        assert_eq!(
+            Goal::parse("100 solves per day"),
+            Some(Goal::SolvesPerDay { count: 100 })
+        );
+        assert_eq!(
+            Goal::parse("DNF rate below 10%"),
+            Some(Goal::DnfRate { percent: 10 })
+        );
+        assert_eq!(Goal::parse("sub-12 ao2"), None);
+        assert_eq!(Goal::parse("dnf rate below 0%"), None);
+        assert_eq!(Goal::parse("faster"), None);
+
+        let goals = [
+            sub12,
+            Goal::Average {
+                n: 5,
+                target: Duration::from_millis(90_500),
+            },
+            Goal::SolvesPerDay { count: 1 },
+            Goal::DnfRate { percent: 25 },
+        ];
+        assert_eq!(goals[1].label(), "Sub-1:30.5 Ao5");
+        for goal in goals {
+            assert_eq!(Goal::parse(&goal.label()), Some(goal));
+        }
+    }
+
+    #[test]
+    fn evaluate_progress() {
+        let precision = TimePrecision::Centiseconds;
+        let goal = Goal::parse("sub-12 ao5").unwrap();
+        let times = [ok(11_000), ok(12_000), ok(13_000)];
+        let progress = goal.progress(&times, 3, precision);
+        assert_eq!(progress.fraction, 0.6);
+        assert!(!progress.met);
+
+        let times = [ok(11_000), ok(12_000), ok(13_000), ok(11_500), ok(11_800)];
+        let progress = goal.progress(&times, 5, precision);
+        assert!(progress.met);
+        assert_eq!(progress.fraction, 1.0);
+        assert_eq!(progress.description, "Ao5 11.77 · Target below 12.00");
+
+        let goal = Goal::SolvesPerDay { count: 4 };
+        assert_eq!(goal.progress(&times, 2, precision).fraction, 0.5);
+        assert!(goal.progress(&times, 4, precision).met);
+
+        let goal = Goal::DnfRate { percent: 10 };
+        let times = [ok(60_000), SolveTime::DNF, ok(70_000), ok(65_000)];
+        let progress = goal.progress(&times, 4, precision);
+        assert!(!progress.met);
+        assert_eq!(progress.fraction, 0.4);
+        assert_eq!(progress.description, "DNF rate 25.0% · Target below 10%");
+        assert!(!goal.progress(&[], 0, precision).met);
+    }
+}
@@ -0,0 +1,190 @@
+use std::time::Duration;
+
+use crate::data::Penalty;
+
+/// Time allowed to inspect the puzzle before starting the solve.
+pub const INSPECTION_TIME: Duration = Duration::from_secs(15);
+/// Time after which starting the solve makes it DNF. Solves started between
+/// `INSPECTION_TIME` and this time get a +2.
+pub const INSPECTION_LIMIT: Duration = Duration::from_secs(17);
+
+/// A warning called during inspection, as judges do in competitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InspectionCue {
+    /// 8 seconds of inspection have passed.
+    EightSeconds,
+    /// 12 seconds of inspection have passed.
+    TwelveSeconds,
+}
+
+impl InspectionCue {
+    /// All cues, in the order they are called.
+    pub const ALL: &'static [InspectionCue] = &[Self::EightSeconds, Self::TwelveSeconds];
+
+    /// Gets the cue with this nick.
+    pub fn from_nick(nick: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|cue| cue.nick() == nick)
+    }
+
+    /// Gets the nick of this cue.
+    pub fn nick(&self) -> &'static str {
+        match self {
+            Self::EightSeconds => "eight-seconds",
+            Self::TwelveSeconds => "twelve-seconds",
+        }
+    }
+
+    /// Gets the inspection time at which this cue is called.
+    pub fn time(&self) -> Duration {
+        match self {
+            Self::EightSeconds => Duration::from_secs(8),
+            Self::TwelveSeconds => Duration::from_secs(12),
+        }
+    }
+}
+
+/// Gets the cue to call when inspection goes from `previous` to `elapsed`,
+/// if one was reached in between. If several were, only the last one is
+/// called.
+pub fn inspection_cue(previous: Duration, elapsed: Duration) -> Option<InspectionCue> {
+    InspectionCue::ALL
+        .iter()
+        .rev()
+        .copied()
+        .find(|cue| previous < cue.time() && cue.time() <= elapsed)
+}
+
+/// Gets the penalty of a solve started after this much inspection.
+pub fn inspection_penalty(elapsed: Duration) -> Penalty {
+    if elapsed <= INSPECTION_TIME {
+        Penalty::Ok
+    } else if elapsed <= INSPECTION_LIMIT {
+        Penalty::Plus2
+    } else {
+        Penalty::Dnf
+    }
+}
+
+/// Formats the seconds of inspection left, counting down from 15, or the
+/// penalty once inspection is over.
+pub fn format_inspection(elapsed: Duration) -> String {
+    match inspection_penalty(elapsed) {
+        Penalty::Ok => {
+            let left = INSPECTION_TIME.as_secs() - elapsed.as_secs();
+            left.max(1).to_string()
+        }
+        Penalty::Plus2 => "+2".to_string(),
+        Penalty::Dnf => "DNF".to_string(),
+    }
+}
+
+/// Sounds played for inspection cues.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CueSound {
+    /// Short beeps, once at 8 seconds and twice at 12 seconds.
+    #[default]
+    Beeps,
+    /// Softer chimes, once at 8 seconds and twice at 12 seconds.
+    Chimes,
+    /// No sound, only the visual cue.
+    Silent,
+}
+
+impl CueSound {
+    /// All cue sounds.
+    pub const ALL: &'static [CueSound] = &[Self::Beeps, Self::Chimes, Self::Silent];
+
+    /// Gets the cue sound with this nick.
+    pub fn from_nick(nick: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|sound| sound.nick() == nick)
+    }
+
+    /// Gets the nick of this cue sound.
+    pub fn nick(&self) -> &'static str {
+        match self {
+            Self::Beeps => "beeps",
+            Self::Chimes => "chimes",
+            Self::Silent => "silent",
+        }
+    }
+
+    /// Gets the human readable name of this cue sound.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Beeps => "Beeps",
+            Self::Chimes => "Chimes",
+            Self::Silent => "Silent",
+        }
+    }
+
+    /// Gets the name of the bundled file played for this cue, e.g.
+    /// `beeps-12.wav`, or `None` if nothing is played.
+    pub fn file_name(&self, cue: InspectionCue) -> Option<String> {
+        match self {
+            Self::Silent => None,
+            _ => Some(format!("{}-{}.wav", self.nick(), cue.time().as_secs())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(secs: f64) -> Duration {
+        Duration::from_secs_f64(secs)
+    }
+
+    #[test]
+    fn call_cues() {
+        assert_eq!(
+            inspection_cue(secs(7.9), secs(8.0)),
+            Some(InspectionCue::EightSeconds)
+        );
+        assert_eq!(inspection_cue(secs(8.0), secs(8.1)), None);
+        assert_eq!(
+            inspection_cue(secs(11.95), secs(12.05)),
+            Some(InspectionCue::TwelveSeconds)
+        );
+        // Only the last cue is called after a long pause between ticks.
+        assert_eq!(
+            inspection_cue(secs(5.0), secs(13.0)),
+            Some(InspectionCue::TwelveSeconds)
+        );
+        assert_eq!(inspection_cue(secs(12.5), secs(16.0)), None);
+    }
+
+    #[test]
+    fn penalize_long_inspection() {
+        assert_eq!(inspection_penalty(secs(14.2)), Penalty::Ok);
+        assert_eq!(inspection_penalty(secs(15.0)), Penalty::Ok);
+        assert_eq!(inspection_penalty(secs(15.3)), Penalty::Plus2);
+        assert_eq!(inspection_penalty(secs(17.0)), Penalty::Plus2);
+        assert_eq!(inspection_penalty(secs(17.01)), Penalty::Dnf);
+    }
+
+    #[test]
+    fn count_down() {
+        assert_eq!(format_inspection(Duration::ZERO), "15");
+        assert_eq!(format_inspection(secs(7.9)), "8");
+        assert_eq!(format_inspection(secs(14.99)), "1");
+        assert_eq!(format_inspection(secs(15.0)), "1");
+        assert_eq!(format_inspection(secs(16.0)), "+2");
+        assert_eq!(format_inspection(secs(20.0)), "DNF");
+    }
+
+    #[test]
+    fn cue_sound_files() {
+        assert_eq!(
+            CueSound::Beeps.file_name(InspectionCue::TwelveSeconds),
+            Some("beeps-12.wav".to_string())
+        );
+        assert_eq!(
+            CueSound::Silent.file_name(InspectionCue::EightSeconds),
+            None
+        );
+        for &cue in InspectionCue::ALL {
+            assert_eq!(InspectionCue::from_nick(cue.nick()), Some(cue));
+        }
+    }
+}
@@ -0,0 +1,422 @@
+use std::collections::BTreeMap;
+
+use crate::data::{invert_moves, parse_moves, simplify_moves, Cube, Face, Move, MoveKind};
+
+/// Algorithms of the 57 OLL cases, in the usual numbering.
+pub const OLL_ALGORITHMS: [&str; 57] = [
+    "R U2 R2 F R F' U2 R' F R F'",
+    "r U r' U2 r U2 R' U2 R U' r'",
+    "r' R2 U R' U r U2 r' U M'",
+    "M U' r U2 r' U' R U' R' M'",
+    "l' U2 L U L' U l",
+    "r U2 R' U' R U' r'",
+    "r U R' U R U2 r'",
+    "l' U' L U' L' U2 l",
+    "R U R' U' R' F R2 U R' U' F'",
+    "R U R' U R' F R F' R U2 R'",
+    "r U R' U R' F R F' R U2 r'",
+    "M' R' U' R U' R' U2 R U' R r'",
+    "F U R U' R2 F' R U R U' R'",
+    "R' F R U R' F' R F U' F'",
+    "l' U' l L' U' L U l' U l",
+    "r U r' R U R' U' r U' r'",
+    "F R' F' R2 r' U R U' R' U' M'",
+    "r U R' U R U2 r2 U' R U' R' U2 r",
+    "r' R U R U R' U' M' R' F R F'",
+    "r U R' U' M2 U R U' R' U' M'",
+    "R U2 R' U' R U R' U' R U' R'",
+    "R U2 R2 U' R2 U' R2 U2 R",
+    "R2 D' R U2 R' D R U2 R",
+    "r U R' U' r' F R F'",
+    "F' r U R' U' r' F R",
+    "R U2 R' U' R U' R'",
+    "R U R' U R U2 R'",
+    "r U R' U' r' R U R U' R'",
+    "R U R' U' R U' R' F' U' F R U R'",
+    "F R' F R2 U' R' U' R U R' F2",
+    "R' U' F U R U' R' F' R",
+    "L U F' U' L' U L F L'",
+    "R U R' U' R' F R F'",
+    "R U R2 U' R' F R U R U' F'",
+    "R U2 R2 F R F' R U2 R'",
+    "L' U' L U' L' U L U L F' L' F",
+    "F R' F' R U R U' R'",
+    "R U R' U R U' R' U' R' F R F'",
+    "L F' L' U' L U F U' L'",
+    "R' F R U R' U' F' U R",
+    "R U R' U R U2 R' F R U R' U' F'",
+    "R' U' R U' R' U2 R F R U R' U' F'",
+    "F' U' L' U L F",
+    "F U R U' R' F'",
+    "F R U R' U' F'",
+    "R' U' R' F R F' U R",
+    "R' U' R' F R F' R' F R F' U R",
+    "F R U R' U' R U R' U' F'",
+    "r U' r2 U r2 U r2 U' r",
+    "r' U r2 U' r2 U' r2 U r'",
+    "F U R U' R' U R U' R' F'",
+    "R U R' U R U' B U' B' R'",
+    "l' U2 L U L' U' L U L' U l",
+    "r U2 R' U' R U R' U' R U' r'",
+    "R' F R U R U' R2 F' R2 U' R' U R U R'",
+    "r' U' r U' R' U R U' R' U R r' U r",
+    "R U R' U' M' U R U' r'",
+];
+
+/// Names and algorithms of the 21 PLL cases.
+pub const PLL_ALGORITHMS: [(&str, &str); 21] = [
+    ("Aa", "x R' U R' D2 R U' R' D2 R2 x'"),
+    ("Ab", "x R2 D2 R U R' D2 R U' R x'"),
+    ("E", "x' R U' R' D R U R' D' R U R' D R U' R' D' x"),
+    ("F", "R' U' F' R U R' U' R' F R2 U' R' U' R U R' U R"),
+    ("Ga", "R2 U R' U R' U' R U' R2 U' D R' U R D'"),
+    ("Gb", "R' U' R U D' R2 U R' U R U' R U' R2 D"),
+    ("Gc", "R2 U' R U' R U R' U R2 U D' R U' R' D"),
+    ("Gd", "R U R' U' D R2 U' R U' R' U R' U R2 D'"),
+    ("H", "M2 U M2 U2 M2 U M2"),
+    ("Ja", "x R2 F R F' R U2 r' U r U2 x'"),
+    ("Jb", "R U R' F' R U R' U' R' F R2 U' R'"),
+    (
+        "Na",
+        "R U R' U R U R' F' R U R' U' R' F R2 U' R' U2 R U' R'",
+    ),
+    ("Nb", "R' U R U' R' F' U' F R U R' F R' F' R U' R"),
+    ("Ra", "R U' R' U' R U R D R' U' R D' R' U2 R'"),
+    ("Rb", "R2 F R U R U' R' F' R U2 R' U2 R"),
+    ("T", "R U R' U' R' F R2 U' R' U' R U R' F'"),
+    ("Ua", "M2 U M U2 M' U M2"),
+    ("Ub", "M2 U' M U2 M' U' M2"),
+    ("V", "R' U R' U' B' R' B2 U' B' U B' R B R"),
+    ("Y", "F R U' R' U' R U R' F' R U R' U' R' F R F'"),
+    ("Z", "M' U M2 U M2 U M' U2 M2"),
+];
+
+/// Shapes of the oriented corners when the edges are oriented, with the
+/// number of the OLL case of each shape.
+const CORNER_SHAPES: [(&str, usize); 7] = [
+    ("H", 21),
+    ("Pi", 22),
+    ("U", 23),
+    ("T", 24),
+    ("L", 25),
+    ("AS", 26),
+    ("S", 27),
+];
+
+/// PLL cases that only move edges. Together with their conjugates by U,
+/// they reach every permutation of the edges that keeps the corners.
+const EDGE_PERMUTATIONS: [&str; 4] = ["Ua", "Ub", "H", "Z"];
+
+/// A set of last layer cases that are trained together.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CaseSet {
+    /// Orienting the last layer.
+    Oll,
+    /// Permuting the last layer once it is oriented.
+    #[default]
+    Pll,
+    /// Solving the corners of the last layer when its edges are oriented.
+    Coll,
+    /// Solving the whole last layer when its edges are oriented.
+    Zbll,
+}
+
+impl CaseSet {
+    /// All sets of cases.
+    pub const ALL: &'static [CaseSet] = &[Self::Oll, Self::Pll, Self::Coll, Self::Zbll];
+
+    /// Gets the set with this nick.
+    pub fn from_nick(nick: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|set| set.nick() == nick)
+    }
+
+    /// Gets the nick of this set.
+    pub fn nick(&self) -> &'static str {
+        match self {
+            Self::Oll => "oll",
+            Self::Pll => "pll",
+            Self::Coll => "coll",
+            Self::Zbll => "zbll",
+        }
+    }
+
+    /// Gets the human readable name of this set.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Oll => "OLL",
+            Self::Pll => "PLL",
+            Self::Coll => "COLL",
+            Self::Zbll => "ZBLL",
+        }
+    }
+
+    /// Describes a state of the last layer so that the states of a case,
+    /// and only those, can be told apart from others.
+    fn describe(&self, cube: &Cube) -> String {
+        match self {
+            Self::Oll => cube
+                .last_layer_colors(true, true)
+                .chars()
+                .map(|c| if c == Face::U.to_char() { c } else { '-' })
+                .collect(),
+            Self::Coll => cube.last_layer_colors(true, false),
+            Self::Pll | Self::Zbll => cube.last_layer_colors(true, true),
+        }
+    }
+}
+
+/// A case of the last layer, with the first two layers solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LastLayerCase {
+    pub set: CaseSet,
+    /// Cases of a set that look alike, such as ZBLL cases with the same
+    /// shape of oriented corners.
+    pub group: &'static str,
+    /// Name of the case within its set, e.g. `Ja` or `T 12`.
+    pub name: String,
+    /// Moves from a solved cube to a state of this case.
+    setup: Vec<Move>,
+}
+
+impl LastLayerCase {
+    /// Gets the identifier of this case among every set, e.g. `PLL Ja`.
+    pub fn id(&self) -> String {
+        format!("{} {}", self.set.label(), self.name)
+    }
+
+    /// Gets moves from a solved cube to a random state of this case. The
+    /// last layer is turned randomly before and after, and pieces the set
+    /// does not solve are placed randomly.
+    pub fn random_setup(&self, mut random: impl FnMut(u32) -> u32) -> Vec<Move> {
+        let mut moves = u_turns(random(4) as u8);
+        match self.set {
+            CaseSet::Oll => {
+                if let Some((_, algorithm)) = PLL_ALGORITHMS.get(random(22) as usize) {
+                    moves.extend(invert_moves(&parse_algorithm(algorithm)));
+                    moves.extend(u_turns(random(4) as u8));
+                }
+            }
+            CaseSet::Coll => {
+                let name = EDGE_PERMUTATIONS.get(random(5) as usize);
+                if let Some((_, algorithm)) = PLL_ALGORITHMS.iter().find(|(n, _)| Some(n) == name) {
+                    let turns = random(4) as u8;
+                    moves.extend(u_turns(turns));
+                    moves.extend(parse_algorithm(algorithm));
+                    moves.extend(u_turns(4 - turns));
+                }
+            }
+            CaseSet::Pll | CaseSet::Zbll => {}
+        }
+        moves.extend(self.setup.iter().copied());
+        moves.extend(u_turns(random(4) as u8));
+        simplify_moves(&moves)
+    }
+}
+
+/// Gets every case of a set. Cases of a group are numbered in a fixed order
+/// when they have no usual name.
+pub fn last_layer_cases(set: CaseSet) -> Vec<LastLayerCase> {
+    match set {
+        CaseSet::Oll => OLL_ALGORITHMS
+            .iter()
+            .enumerate()
+            .map(|(i, algorithm)| LastLayerCase {
+                set,
+                group: set.label(),
+                name: (i + 1).to_string(),
+                setup: invert_moves(&parse_algorithm(algorithm)),
+            })
+            .collect(),
+        CaseSet::Pll => PLL_ALGORITHMS
+            .iter()
+            .map(|(name, algorithm)| LastLayerCase {
+                set,
+                group: set.label(),
+                name: name.to_string(),
+                setup: invert_moves(&parse_algorithm(algorithm)),
+            })
+            .collect(),
+        CaseSet::Coll | CaseSet::Zbll => {
+            let mut cases = Vec::new();
+            for (group, number) in CORNER_SHAPES {
+                // Every state with oriented edges is solved by orienting the
+                // corners, turning U and then permuting.
+                let orientation = parse_algorithm(OLL_ALGORITHMS[number - 1]);
+                let mut setups: BTreeMap<String, Vec<Move>> = BTreeMap::new();
+                for permutation in std::iter::once("").chain(PLL_ALGORITHMS.map(|(_, a)| a)) {
+                    for turns in 0..4 {
+                        let mut solution = orientation.clone();
+                        solution.extend(u_turns(turns));
+                        solution.extend(parse_algorithm(permutation));
+                        let setup = invert_moves(&solution);
+                        setups.entry(case_key(set, &setup)).or_insert(setup);
+                    }
+                }
+                cases.extend(
+                    setups
+                        .into_values()
+                        .enumerate()
+                        .map(|(i, setup)| LastLayerCase {
+                            set,
+                            group,
+                            name: format!("{} {}", group, i + 1),
+                            setup,
+                        }),
+                );
+            }
+            cases
+        }
+    }
+}
+
+/// Solves the last layer of a cube with its first two layers solved, by
+/// orienting it with an OLL algorithm and permuting it with a PLL one.
+/// Returns `None` if the first two layers are not solved.
+pub fn solve_last_layer(cube: &Cube) -> Option<Vec<Move>> {
+    if !cube.is_f2l_solved() {
+        return None;
+    }
+    let orientations: Vec<Vec<Move>> = std::iter::once(Vec::new())
+        .chain(OLL_ALGORITHMS.iter().map(|a| parse_algorithm(a)))
+        .collect();
+    let permutations: Vec<Vec<Move>> = std::iter::once(Vec::new())
+        .chain(PLL_ALGORITHMS.iter().map(|(_, a)| parse_algorithm(a)))
+        .collect();
+
+    for turns in 0..4 {
+        for orientation in &orientations {
+            let mut solution = u_turns(turns);
+            solution.extend(orientation.iter().copied());
+            let mut oriented = cube.clone();
+            oriented.apply_moves(&solution);
+            if !oriented.is_last_layer_oriented() {
+                continue;
+            }
+
+            for turns in 0..4 {
+                for permutation in &permutations {
+                    let mut permuted = oriented.clone();
+                    permuted.apply_moves(&u_turns(turns));
+                    permuted.apply_moves(permutation);
+                    for last_turns in 0..4 {
+                        if permuted.is_solved() {
+                            solution.extend(u_turns(turns));
+                            solution.extend(permutation.iter().copied());
+                            solution.extend(u_turns(last_turns));
+                            return Some(simplify_moves(&solution));
+                        }
+                        permuted.apply_moves(&u_turns(1));
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Parses an algorithm of the tables above.
+fn parse_algorithm(algorithm: &str) -> Vec<Move> {
+    parse_moves(algorithm).expect("algorithms should be valid notation")
+}
+
+/// Gets `turns` quarter turns of U as a single move, if any.
+fn u_turns(turns: u8) -> Vec<Move> {
+    match turns % 4 {
+        0 => Vec::new(),
+        turns => vec![Move::new(MoveKind::Face(Face::U), turns)],
+    }
+}
+
+/// Describes the case reached by these moves from a solved cube, the same
+/// for every turn of the last layer before and after them.
+fn case_key(set: CaseSet, setup: &[Move]) -> String {
+    let mut keys = Vec::with_capacity(16);
+    for before in 0..4 {
+        let mut cube = Cube::new();
+        cube.apply_moves(&u_turns(before));
+        cube.apply_moves(setup);
+        for _ in 0..4 {
+            keys.push(set.describe(&cube));
+            cube.apply_moves(&u_turns(1));
+        }
+    }
+    keys.into_iter().min().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    /// A predictable sequence of numbers standing in for random numbers.
+    fn sequence() -> impl FnMut(u32) -> u32 {
+        let mut state: u32 = 4321;
+        move |n| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (state >> 16) % n
+        }
+    }
+
+    #[test]
+    fn algorithms_keep_first_two_layers() {
+        for algorithm in OLL_ALGORITHMS {
+            let mut cube = Cube::new();
+            cube.apply_moves(&parse_algorithm(algorithm));
+            assert!(cube.is_f2l_solved(), "{}", algorithm);
+        }
+        for (name, algorithm) in PLL_ALGORITHMS {
+            let mut cube = Cube::new();
+            cube.apply_moves(&parse_algorithm(algorithm));
+            assert!(cube.is_f2l_solved(), "{}", name);
+            assert!(cube.is_last_layer_oriented(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn cases_are_distinct() {
+        for (set, count) in [
+            (CaseSet::Oll, 57),
+            (CaseSet::Pll, 21),
+            (CaseSet::Coll, 40),
+            (CaseSet::Zbll, 472),
+        ] {
+            let cases = last_layer_cases(set);
+            assert_eq!(cases.len(), count, "{}", set.label());
+            let keys: HashSet<String> = cases
+                .iter()
+                .map(|case| case_key(set, &case.setup))
+                .collect();
+            assert_eq!(keys.len(), count, "{}", set.label());
+        }
+    }
+
+    #[test]
+    fn solve_any_last_layer() {
+        let mut cube = Cube::new();
+        cube.apply_moves(&parse_moves("R U R' U R U2 R' U' F R U R' U' F' U2").unwrap());
+        let solution = solve_last_layer(&cube).unwrap();
+        cube.apply_moves(&solution);
+        assert!(cube.is_solved());
+
+        cube.apply_moves(&parse_moves("R").unwrap());
+        assert_eq!(solve_last_layer(&cube), None);
+    }
+
+    #[test]
+    fn random_setups_reach_last_layer() {
+        let mut random = sequence();
+        for set in CaseSet::ALL {
+            for case in last_layer_cases(*set).iter().step_by(11) {
+                let mut cube = Cube::new();
+                cube.apply_moves(&case.random_setup(&mut random));
+                if *set == CaseSet::Pll {
+                    assert!(cube.is_last_layer_oriented(), "{}", case.id());
+                }
+                let solution = solve_last_layer(&cube).unwrap();
+                cube.apply_moves(&solution);
+                assert!(cube.is_solved(), "{}", case.id());
+            }
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use crate::data::{CompetitionRound, Penalty, SessionItem, SolveTime, TimePrecision};
+use gtk::glib;
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+
+#[doc(hidden)]
+mod imp {
+    use std::{cell::RefCell, marker::PhantomData};
+
+    use once_cell::sync::OnceCell;
+
+    use super::*;
+
+    #[derive(Debug, Default, glib::Properties)]
+    #[properties(wrapper_type = super::MockCompetition)]
+    pub struct MockCompetition {
+        pub(super) round: OnceCell<CompetitionRound>,
+        pub(super) attempts: RefCell<Vec<SessionItem>>,
+        pub(super) handler_list: RefCell<Vec<glib::SignalHandlerId>>,
+
+        #[property(get = Self::status_string)]
+        status_string: PhantomData<String>,
+        #[property(get = Self::scramble)]
+        scramble: PhantomData<String>,
+        #[property(get = Self::finished)]
+        finished: PhantomData<bool>,
+    }
+
+    impl MockCompetition {
+        fn status_string(&self) -> String {
+            let obj = self.obj();
+            let round = obj.round();
+            let times = obj.times();
+
+            if round.is_finished(&times) {
+                return "Round finished".to_string();
+            }
+
+            let mut status = format!(
+                "Attempt {} of {}",
+                times.len() + 1,
+                round.n_attempts(&times)
+            );
+            if let (Some(cutoff), None) = (round.cutoff, round.made_cutoff(&times)) {
+                status.push_str(&format!(
+                    " · Cutoff < {}",
+                    SolveTime::new(cutoff.time, Penalty::Ok)
+                ));
+            }
+            status
+        }
+
+        fn scramble(&self) -> String {
+            let obj = self.obj();
+            obj.round()
+                .next_scramble(&obj.times())
+                .unwrap_or_default()
+                .to_string()
+        }
+
+        fn finished(&self) -> bool {
+            let obj = self.obj();
+            obj.round().is_finished(&obj.times())
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for MockCompetition {
+        const NAME: &'static str = "PtMockCompetition";
+        type Type = super::MockCompetition;
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for MockCompetition {
+        fn dispose(&self) {
+            let attempts = self.attempts.borrow();
+            for (item, handler) in attempts.iter().zip(self.handler_list.take()) {
+                item.disconnect(handler);
+            }
+        }
+    }
+}
+
+glib::wrapper! {
+    /// A mock competition running a single round. The attempts of the round
+    /// are added to a session as usual and recorded here.
+    pub struct MockCompetition(ObjectSubclass<imp::MockCompetition>);
+}
+
+impl MockCompetition {
+    /// Creates a new mock competition running this round.
+    pub fn new(round: CompetitionRound) -> Self {
+        let obj: Self = glib::Object::builder().build();
+        obj.imp()
+            .round
+            .set(round)
+            .expect("`round` should only be set once");
+        obj
+    }
+
+    /// Gets the round of the competition.
+    pub fn round(&self) -> &CompetitionRound {
+        self.imp()
+            .round
+            .get()
+            .expect("`round` should be set by `new`")
+    }
+
+    /// Gets the times of the recorded attempts.
+    pub fn times(&self) -> Vec<SolveTime> {
+        self.imp()
+            .attempts
+            .borrow()
+            .iter()
+            .map(SessionItem::time)
+            .collect()
+    }
+
+    /// Records a solve as the next attempt of the round, making it DNF if it
+    /// reached the time limit.
+    pub fn record(&self, item: &SessionItem) {
+        let imp = self.imp();
+        if self.finished() {
+            return;
+        }
+
+        let penalty = self.round().penalty_for(&self.times(), item.time());
+        if penalty != item.penalty() {
+            item.set_penalty(penalty);
+        }
+
+        let handler =
+            item.connect_solve_time_string_notify(glib::clone!(@weak self as obj => move |_| {
+                obj.notify_attempts_changed();
+            }));
+        imp.attempts.borrow_mut().push(item.clone());
+        imp.handler_list.borrow_mut().push(handler);
+        self.notify_attempts_changed();
+    }
+
+    /// Formats the results sheet of the round with this precision.
+    pub fn results_sheet(&self, precision: TimePrecision) -> String {
+        self.round().results_sheet(&self.times(), precision)
+    }
+
+    fn notify_attempts_changed(&self) {
+        self.notify_status_string();
+        self.notify_scramble();
+        self.notify_finished();
+    }
+}
@@ -0,0 +1,228 @@
+use std::{cmp::Ordering, fmt::Display, time::Duration};
+
+use crate::data::{Penalty, SolveTime};
+
+/// Time limit of a Multi-Blind attempt for each attempted cube.
+const TIME_LIMIT_PER_CUBE: Duration = Duration::from_secs(10 * 60);
+/// Maximum time limit of a Multi-Blind attempt.
+const MAX_TIME_LIMIT: Duration = Duration::from_secs(60 * 60);
+
+/// Error when creating a `MultiBlindResult`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultiBlindError {
+    /// Less than two cubes are attempted.
+    TooFewCubes,
+    /// More cubes are solved than attempted.
+    TooManySolved,
+}
+
+impl Display for MultiBlindError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooFewCubes => write!(f, "At least 2 cubes must be attempted"),
+            Self::TooManySolved => write!(f, "Solved cubes exceed attempted cubes"),
+        }
+    }
+}
+
+impl std::error::Error for MultiBlindError {}
+
+/// Result of a Multi-Blind attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MultiBlindResult {
+    /// Number of cubes attempted.
+    pub attempted: u32,
+    /// Number of cubes solved.
+    pub solved: u32,
+    /// Time of the attempt.
+    pub time: Duration,
+}
+
+impl MultiBlindResult {
+    /// Creates a new `MultiBlindResult`, checking it against the WCA
+    /// regulations. A time over the time limit is cut to the limit, as the
+    /// attempt is stopped there and scored with the cubes solved by then.
+    pub fn new(attempted: u32, solved: u32, time: Duration) -> Result<Self, MultiBlindError> {
+        if attempted < 2 {
+            return Err(MultiBlindError::TooFewCubes);
+        }
+        if solved > attempted {
+            return Err(MultiBlindError::TooManySolved);
+        }
+
+        Ok(Self {
+            attempted,
+            solved,
+            time: time.min(Self::time_limit_for(attempted)),
+        })
+    }
+
+    /// Gets the time limit of an attempt with this number of cubes, which is
+    /// 10 minutes per cube, up to 60 minutes.
+    pub fn time_limit_for(attempted: u32) -> Duration {
+        (TIME_LIMIT_PER_CUBE * attempted).min(MAX_TIME_LIMIT)
+    }
+
+    /// Gets the time limit of this attempt.
+    pub fn time_limit(&self) -> Duration {
+        Self::time_limit_for(self.attempted)
+    }
+
+    /// Gets the number of cubes that are not solved.
+    pub fn unsolved(&self) -> u32 {
+        self.attempted - self.solved
+    }
+
+    /// Gets the points of this attempt, which is the number of solved cubes
+    /// minus the number of unsolved cubes.
+    pub fn points(&self) -> i32 {
+        self.solved as i32 - self.unsolved() as i32
+    }
+
+    /// Returns `true` if the attempt is DNF, which is when fewer than 2 cubes
+    /// are solved or the points are negative.
+    pub fn is_dnf(&self) -> bool {
+        self.solved < 2 || self.points() < 0
+    }
+
+    /// Gets the time of this attempt as a `SolveTime`.
+    pub fn solve_time(&self) -> SolveTime {
+        SolveTime::new(
+            self.time,
+            if self.is_dnf() {
+                Penalty::Dnf
+            } else {
+                Penalty::Ok
+            },
+        )
+    }
+}
+
+impl PartialOrd for MultiBlindResult {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MultiBlindResult {
+    /// Orders the results from the best to the worst: more points, then less
+    /// time, then fewer unsolved cubes. DNF results are ordered last.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.is_dnf()
+            .cmp(&other.is_dnf())
+            .then_with(|| other.points().cmp(&self.points()))
+            .then_with(|| self.time.as_secs().cmp(&other.time.as_secs()))
+            .then_with(|| self.unsolved().cmp(&other.unsolved()))
+    }
+}
+
+impl Display for MultiBlindResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let result = format!(
+            "{}/{} {}",
+            self.solved,
+            self.attempted,
+            display_minutes(&self.time)
+        );
+        if self.is_dnf() {
+            write!(f, "DNF ({})", result)
+        } else {
+            write!(f, "{}", result)
+        }
+    }
+}
+
+/// Formats the time in minutes and whole seconds, as Multi-Blind results are
+/// recorded.
+fn display_minutes(time: &Duration) -> String {
+    let seconds = time.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(attempted: u32, solved: u32, secs: u64) -> MultiBlindResult {
+        MultiBlindResult::new(attempted, solved, Duration::from_secs(secs)).unwrap()
+    }
+
+    #[test]
+    fn calculate_points() {
+        assert_eq!(result(6, 5, 2_700).points(), 4);
+        assert_eq!(result(4, 2, 2_000).points(), 0);
+    }
+
+    #[test]
+    fn calculate_dnf() {
+        assert!(!result(4, 2, 2_000).is_dnf());
+        assert!(result(5, 2, 2_000).is_dnf());
+        assert!(result(2, 1, 600).is_dnf());
+        assert!(result(2, 1, 600).solve_time().is_dnf());
+    }
+
+    #[test]
+    fn calculate_time_limit() {
+        assert_eq!(
+            MultiBlindResult::time_limit_for(2),
+            Duration::from_secs(20 * 60)
+        );
+        assert_eq!(
+            MultiBlindResult::time_limit_for(6),
+            Duration::from_secs(60 * 60)
+        );
+        assert_eq!(
+            MultiBlindResult::time_limit_for(12),
+            Duration::from_secs(60 * 60)
+        );
+    }
+
+    #[test]
+    fn reject_invalid_results() {
+        assert_eq!(
+            MultiBlindResult::new(1, 1, Duration::from_secs(300)),
+            Err(MultiBlindError::TooFewCubes)
+        );
+        assert_eq!(
+            MultiBlindResult::new(3, 4, Duration::from_secs(300)),
+            Err(MultiBlindError::TooManySolved)
+        );
+    }
+
+    #[test]
+    fn stop_at_time_limit() {
+        assert_eq!(result(3, 3, 30 * 60 + 1).time, Duration::from_secs(30 * 60));
+        assert_eq!(result(8, 5, 75 * 60).to_string(), "5/8 60:00");
+        assert_eq!(result(3, 2, 25 * 60).time, Duration::from_secs(25 * 60));
+    }
+
+    #[test]
+    fn rank_results() {
+        let mut results = vec![
+            result(2, 1, 400),
+            result(4, 3, 2_400),
+            result(3, 3, 1_500),
+            result(3, 3, 1_200),
+            result(4, 4, 2_300),
+            result(5, 4, 2_400),
+        ];
+        results.sort();
+        assert_eq!(
+            results,
+            vec![
+                result(4, 4, 2_300),
+                result(3, 3, 1_200),
+                result(3, 3, 1_500),
+                result(5, 4, 2_400),
+                result(4, 3, 2_400),
+                result(2, 1, 400),
+            ]
+        );
+    }
+
+    #[test]
+    fn display_result() {
+        assert_eq!(result(6, 5, 2_712).to_string(), "5/6 45:12");
+        assert_eq!(result(2, 1, 600).to_string(), "DNF (1/2 10:00)");
+    }
+}
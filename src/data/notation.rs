@@ -0,0 +1,632 @@
+use std::fmt::Display;
+
+/// A face of the cube.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Face {
+    U,
+    D,
+    L,
+    R,
+    F,
+    B,
+}
+
+impl Face {
+    /// All faces of the cube.
+    pub const ALL: [Face; 6] = [Self::U, Self::D, Self::L, Self::R, Self::F, Self::B];
+
+    /// Gets the face opposite to this face.
+    pub fn opposite(&self) -> Self {
+        match self {
+            Self::U => Self::D,
+            Self::D => Self::U,
+            Self::L => Self::R,
+            Self::R => Self::L,
+            Self::F => Self::B,
+            Self::B => Self::F,
+        }
+    }
+
+    /// Gets the axis this face turns around.
+    pub fn axis(&self) -> Axis {
+        match self {
+            Self::L | Self::R => Axis::X,
+            Self::U | Self::D => Axis::Y,
+            Self::F | Self::B => Axis::Z,
+        }
+    }
+
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            'U' => Some(Self::U),
+            'D' => Some(Self::D),
+            'L' => Some(Self::L),
+            'R' => Some(Self::R),
+            'F' => Some(Self::F),
+            'B' => Some(Self::B),
+            _ => None,
+        }
+    }
+
+    /// Gets the letter of this face in notation.
+    pub fn to_char(self) -> char {
+        match self {
+            Self::U => 'U',
+            Self::D => 'D',
+            Self::L => 'L',
+            Self::R => 'R',
+            Self::F => 'F',
+            Self::B => 'B',
+        }
+    }
+}
+
+/// A middle slice of the cube.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Slice {
+    /// The slice between L and R, turning like L.
+    M,
+    /// The slice between U and D, turning like D.
+    E,
+    /// The slice between F and B, turning like F.
+    S,
+}
+
+impl Slice {
+    fn to_char(self) -> char {
+        match self {
+            Self::M => 'M',
+            Self::E => 'E',
+            Self::S => 'S',
+        }
+    }
+
+    /// Gets the face this slice turns like.
+    fn face(self) -> Face {
+        match self {
+            Self::M => Face::L,
+            Self::E => Face::D,
+            Self::S => Face::F,
+        }
+    }
+}
+
+/// An axis of the whole cube.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    /// The axis through L and R, turning like R.
+    X,
+    /// The axis through U and D, turning like U.
+    Y,
+    /// The axis through F and B, turning like F.
+    Z,
+}
+
+impl Axis {
+    fn to_char(self) -> char {
+        match self {
+            Self::X => 'x',
+            Self::Y => 'y',
+            Self::Z => 'z',
+        }
+    }
+
+    /// Gets the face the whole cube turns like around this axis.
+    fn positive_face(self) -> Face {
+        match self {
+            Self::X => Face::R,
+            Self::Y => Face::U,
+            Self::Z => Face::F,
+        }
+    }
+
+    /// Gets the faces in the order a clockwise quarter turn around this axis
+    /// moves them.
+    fn cycle(self) -> [Face; 4] {
+        match self {
+            Self::X => [Face::F, Face::U, Face::B, Face::D],
+            Self::Y => [Face::F, Face::L, Face::B, Face::R],
+            Self::Z => [Face::U, Face::R, Face::D, Face::L],
+        }
+    }
+}
+
+/// What a move turns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MoveKind {
+    /// The outer layer of a face, e.g. `R`.
+    Face(Face),
+    /// The outer two layers of a face, e.g. `Rw` or `r`.
+    Wide(Face),
+    /// A middle slice, e.g. `M`.
+    Slice(Slice),
+    /// The whole cube, e.g. `x`.
+    Rotation(Axis),
+}
+
+/// A move on a 3x3x3 cube.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Move {
+    /// What the move turns.
+    pub kind: MoveKind,
+    /// Number of clockwise quarter turns, from 1 to 3.
+    pub turns: u8,
+}
+
+impl Move {
+    /// Creates a new move. `turns` is taken modulo 4 and must not be a
+    /// multiple of 4.
+    pub fn new(kind: MoveKind, turns: u8) -> Self {
+        let turns = turns % 4;
+        debug_assert!(turns != 0, "a move must turn at least once");
+        Self { kind, turns }
+    }
+
+    /// Gets the move that undoes this move.
+    pub fn inverse(&self) -> Self {
+        Self {
+            kind: self.kind,
+            turns: 4 - self.turns,
+        }
+    }
+
+    /// Gets the number of moves this move counts as in the half turn
+    /// metric. Rotations are free and slices count as two moves.
+    pub fn htm(&self) -> u32 {
+        match self.kind {
+            MoveKind::Face(_) | MoveKind::Wide(_) => 1,
+            MoveKind::Slice(_) => 2,
+            MoveKind::Rotation(_) => 0,
+        }
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            MoveKind::Face(face) => write!(f, "{}", face.to_char())?,
+            MoveKind::Wide(face) => write!(f, "{}w", face.to_char())?,
+            MoveKind::Slice(slice) => write!(f, "{}", slice.to_char())?,
+            MoveKind::Rotation(axis) => write!(f, "{}", axis.to_char())?,
+        }
+        match self.turns {
+            2 => write!(f, "2"),
+            3 => write!(f, "'"),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// What is wrong in a move sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotationErrorKind {
+    /// A character that does not belong there.
+    Unexpected(char),
+    /// The text ends in the middle of a move.
+    UnfinishedMove,
+    /// A wide move turns a number of layers the cube does not have, e.g.
+    /// `4Rw` on a 4x4x4.
+    InvalidLayers(u32),
+}
+
+/// Error when parsing a move sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotationError {
+    /// Position of the error, counted in characters.
+    pub position: usize,
+    pub kind: NotationErrorKind,
+}
+
+impl Display for NotationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let position = self.position + 1;
+        match self.kind {
+            NotationErrorKind::Unexpected(c) => {
+                write!(f, "Unexpected “{}” at position {}", c, position)
+            }
+            NotationErrorKind::UnfinishedMove => {
+                write!(f, "Unfinished move at position {}", position)
+            }
+            NotationErrorKind::InvalidLayers(layers) => {
+                write!(f, "Cannot turn {} layers at position {}", layers, position)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+/// Parses a sequence of moves in WCA notation, e.g. `R U R' U'`.
+///
+/// Moves may be written without spaces, and text after `//` is ignored
+/// until the end of the line, so solutions can be annotated.
+pub fn parse_moves(text: &str) -> Result<Vec<Move>, NotationError> {
+    let moves = parse_scramble(3, text)?;
+    Ok(moves.into_iter().map(|m| m.m).collect())
+}
+
+/// Formats a sequence of moves separated by spaces.
+pub fn format_moves(moves: &[Move]) -> String {
+    moves
+        .iter()
+        .map(|m| m.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Gets the sequence of moves that undoes a sequence of moves.
+pub fn invert_moves(moves: &[Move]) -> Vec<Move> {
+    moves.iter().rev().map(Move::inverse).collect()
+}
+
+/// Rewrites a sequence of moves with face turns only, keeping the cube in
+/// the same orientation. Wide moves and slices are turned as the outer
+/// layers that move with them, so the sequence leaves the pieces in the same
+/// places relative to the centers. Rotations are dropped.
+pub fn to_face_turns(moves: &[Move]) -> Vec<Move> {
+    // The face of the unrotated cube found at each place, by index in
+    // `Face::ALL`.
+    let mut at = Face::ALL;
+    let index = |face: Face| Face::ALL.iter().position(|&f| f == face).unwrap_or(0);
+    let mut face_turns = Vec::new();
+
+    for m in moves {
+        let (faces, rotation) = match m.kind {
+            MoveKind::Face(face) => (vec![(face, m.turns)], None),
+            MoveKind::Wide(face) => (vec![(face.opposite(), m.turns)], Some((face, m.turns))),
+            MoveKind::Slice(slice) => {
+                let face = slice.face();
+                (
+                    vec![(face, 4 - m.turns), (face.opposite(), m.turns)],
+                    Some((face, m.turns)),
+                )
+            }
+            MoveKind::Rotation(axis) => (Vec::new(), Some((axis.positive_face(), m.turns))),
+        };
+
+        for (face, turns) in faces {
+            face_turns.push(Move::new(MoveKind::Face(at[index(face)]), turns));
+        }
+        if let Some((face, turns)) = rotation {
+            let axis = face.axis();
+            let turns = if face == axis.positive_face() {
+                turns
+            } else {
+                4 - turns
+            };
+            let cycle = axis.cycle();
+            for _ in 0..turns {
+                let previous = at;
+                for i in 0..4 {
+                    at[index(cycle[(i + 1) % 4])] = previous[index(cycle[i])];
+                }
+            }
+        }
+    }
+
+    face_turns
+}
+
+/// Merges moves of the same layer that follow each other, or that are only
+/// separated by moves of the opposite layer, e.g. `R L R'` becomes `L`.
+pub fn simplify_moves(moves: &[Move]) -> Vec<Move> {
+    let mut simplified: Vec<Move> = Vec::new();
+
+    'moves: for &m in moves {
+        for i in (0..simplified.len()).rev() {
+            let previous = simplified[i];
+            if previous.kind == m.kind {
+                let turns = (previous.turns + m.turns) % 4;
+                if turns == 0 {
+                    simplified.remove(i);
+                } else {
+                    simplified[i] = Move::new(m.kind, turns);
+                }
+                continue 'moves;
+            }
+            let commutes = match (previous.kind, m.kind) {
+                (MoveKind::Face(a), MoveKind::Face(b)) => a.axis() == b.axis(),
+                _ => false,
+            };
+            if !commutes {
+                break;
+            }
+        }
+        simplified.push(m);
+    }
+
+    simplified
+}
+
+/// Counts the moves of a sequence in the half turn metric.
+pub fn htm_count(moves: &[Move]) -> u32 {
+    moves.iter().map(Move::htm).sum()
+}
+
+/// A move of an NxNxN cube in a scramble.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScrambleMove {
+    pub m: Move,
+    /// Number of outer layers turned by a wide move, e.g. 3 for `3Rw`, and
+    /// 1 for other moves.
+    pub layers: u8,
+}
+
+impl Display for ScrambleMove {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.layers > 2 {
+            write!(f, "{}{}", self.layers, self.m)
+        } else {
+            write!(f, "{}", self.m)
+        }
+    }
+}
+
+/// Reads moves from text one character at a time.
+struct MoveReader {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl MoveReader {
+    fn new(text: &str) -> Self {
+        Self {
+            chars: text.chars().collect(),
+            position: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    /// Skips whitespace and comments, returning `false` at the end of the
+    /// text.
+    fn skip_space(&mut self) -> bool {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                self.position += 1;
+            } else if c == '/' && self.chars.get(self.position + 1) == Some(&'/') {
+                while self.peek().is_some_and(|c| c != '\n') {
+                    self.position += 1;
+                }
+            } else {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Gets an error for the character at the current position.
+    fn error(&self) -> NotationError {
+        self.error_at(
+            self.position,
+            self.peek().map_or(
+                NotationErrorKind::UnfinishedMove,
+                NotationErrorKind::Unexpected,
+            ),
+        )
+    }
+
+    fn error_at(&self, position: usize, kind: NotationErrorKind) -> NotationError {
+        NotationError { position, kind }
+    }
+
+    /// Skips the next character if it is `c`.
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(c);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    /// Reads a number without sign, if there is one.
+    fn number(&mut self) -> Option<u32> {
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.position += 1;
+        }
+        let digits: String = self.chars[start..self.position].iter().collect();
+        // Numbers too large to read are more layers than any cube has.
+        (start < self.position).then(|| digits.parse().unwrap_or(u32::MAX))
+    }
+
+    /// Reads a move of an NxNxN cube, e.g. `R2`, `3Rw'` or `M`.
+    fn cube_move(&mut self, size: u8) -> Result<ScrambleMove, NotationError> {
+        let start = self.position;
+        let layers = self.number();
+        let Some(c) = self.peek() else {
+            return Err(self.error());
+        };
+
+        let kind = if let Some(face) = Face::from_char(c) {
+            self.position += 1;
+            if self.eat('w') {
+                MoveKind::Wide(face)
+            } else if layers.is_some() {
+                return Err(self.error());
+            } else {
+                MoveKind::Face(face)
+            }
+        } else if let Some(face) = Face::from_char(c.to_ascii_uppercase()) {
+            self.position += 1;
+            MoveKind::Wide(face)
+        } else {
+            let kind = match c {
+                // Only odd cubes have a middle slice.
+                'M' if size % 2 == 1 => MoveKind::Slice(Slice::M),
+                'E' if size % 2 == 1 => MoveKind::Slice(Slice::E),
+                'S' if size % 2 == 1 => MoveKind::Slice(Slice::S),
+                'x' => MoveKind::Rotation(Axis::X),
+                'y' => MoveKind::Rotation(Axis::Y),
+                'z' => MoveKind::Rotation(Axis::Z),
+                _ => return Err(self.error()),
+            };
+            if layers.is_some() {
+                return Err(self.error());
+            }
+            self.position += 1;
+            kind
+        };
+
+        let layers = match kind {
+            MoveKind::Wide(_) => {
+                let layers = layers.unwrap_or(2);
+                if layers < 2 || layers >= size as u32 {
+                    return Err(self.error_at(start, NotationErrorKind::InvalidLayers(layers)));
+                }
+                layers as u8
+            }
+            _ => 1,
+        };
+
+        let mut turns = 1;
+        if self.eat('2') {
+            turns = 2;
+        }
+        if self.eat('\'') || self.eat('’') {
+            turns = 4 - turns;
+        }
+        Ok(ScrambleMove {
+            m: Move::new(kind, turns),
+            layers,
+        })
+    }
+}
+
+/// Parses a scramble of an NxNxN cube of this size in WCA notation, e.g.
+/// `3Rw2 U` for big cubes. Middle slices are only read on odd cubes.
+///
+/// Moves may be written without spaces, and text after `//` is ignored
+/// until the end of the line.
+pub fn parse_scramble(size: u8, text: &str) -> Result<Vec<ScrambleMove>, NotationError> {
+    let mut reader = MoveReader::new(text);
+    let mut moves = Vec::new();
+    while reader.skip_space() {
+        moves.push(reader.cube_move(size)?);
+    }
+    Ok(moves)
+}
+
+/// Formats a scramble separated by spaces.
+pub fn format_scramble(moves: &[ScrambleMove]) -> String {
+    moves
+        .iter()
+        .map(|m| m.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Rewrites a scramble of an NxNxN cube in the usual form of its notation,
+/// e.g. `r2'` as `Rw2`.
+pub fn normalize_scramble(size: u8, text: &str) -> Result<String, NotationError> {
+    Ok(format_scramble(&parse_scramble(size, text)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_face_moves() {
+        let moves = parse_moves("R U2 F' D2'").unwrap();
+        assert_eq!(
+            moves,
+            vec![
+                Move::new(MoveKind::Face(Face::R), 1),
+                Move::new(MoveKind::Face(Face::U), 2),
+                Move::new(MoveKind::Face(Face::F), 3),
+                Move::new(MoveKind::Face(Face::D), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_without_spaces() {
+        let moves = parse_moves("RUR'U'").unwrap();
+        assert_eq!(format_moves(&moves), "R U R' U'");
+    }
+
+    #[test]
+    fn parse_wide_slice_and_rotation() {
+        let moves = parse_moves("Rw r' M2 x y' z2").unwrap();
+        assert_eq!(format_moves(&moves), "Rw Rw' M2 x y' z2");
+    }
+
+    #[test]
+    fn ignore_comments() {
+        let moves = parse_moves("R U // EO\nF2 // F2L").unwrap();
+        assert_eq!(format_moves(&moves), "R U F2");
+    }
+
+    #[test]
+    fn report_position_of_error() {
+        assert_eq!(
+            parse_moves("R U Q"),
+            Err(NotationError {
+                position: 4,
+                kind: NotationErrorKind::Unexpected('Q')
+            })
+        );
+    }
+
+    #[test]
+    fn count_htm() {
+        let moves = parse_moves("x R U2 Rw' M y").unwrap();
+        assert_eq!(htm_count(&moves), 5);
+    }
+
+    #[test]
+    fn rewrite_with_face_turns() {
+        let moves = parse_moves("Rw U x' M2 y S").unwrap();
+        assert_eq!(format_moves(&to_face_turns(&moves)), "L F L2 R2 R' L");
+    }
+
+    #[test]
+    fn simplify_sequence() {
+        let moves = parse_moves("R U U' R L R2 D D2 M M").unwrap();
+        assert_eq!(format_moves(&simplify_moves(&moves)), "L D' M2");
+    }
+
+    #[test]
+    fn invert_sequence() {
+        let moves = parse_moves("R U2 F'").unwrap();
+        assert_eq!(format_moves(&invert_moves(&moves)), "F U2 R'");
+    }
+
+    fn error(size: u8, text: &str) -> (usize, NotationErrorKind) {
+        let e = parse_scramble(size, text).unwrap_err();
+        (e.position, e.kind)
+    }
+
+    #[test]
+    fn parse_big_cube_moves() {
+        let moves = parse_scramble(7, "3Rw2 Rw' 3r u2 L").unwrap();
+        assert_eq!(
+            moves[0],
+            ScrambleMove {
+                m: Move::new(MoveKind::Wide(Face::R), 2),
+                layers: 3
+            }
+        );
+        assert_eq!(format_scramble(&moves), "3Rw2 Rw' 3Rw Uw2 L");
+
+        assert_eq!(error(4, "R 4Rw"), (2, NotationErrorKind::InvalidLayers(4)));
+        assert_eq!(error(2, "R Uw"), (2, NotationErrorKind::InvalidLayers(2)));
+        assert_eq!(error(4, "R M"), (2, NotationErrorKind::Unexpected('M')));
+        assert_eq!(error(5, "3R"), (2, NotationErrorKind::UnfinishedMove));
+    }
+
+    #[test]
+    fn normalize_notation() {
+        assert_eq!(
+            normalize_scramble(3, "r2'U  R’ // done").unwrap(),
+            "Rw2 U R'"
+        );
+        assert_eq!(
+            normalize_scramble(3, "R U X").unwrap_err().to_string(),
+            "Unexpected “X” at position 5"
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use std::fmt::Display;
+
+use crate::data::{SessionKind, SolveResult, TimePrecision};
+
+/// A statistic that personal bests are recorded for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PbStatistic {
+    /// A single solve.
+    Single,
+    /// A mean of 3 solves.
+    MeanOf3,
+    /// An average of this number of solves.
+    AverageOf(usize),
+}
+
+impl PbStatistic {
+    /// Statistics recorded for sessions ranked by averages.
+    const WITH_AVERAGES: &'static [PbStatistic] = &[
+        Self::Single,
+        Self::MeanOf3,
+        Self::AverageOf(5),
+        Self::AverageOf(12),
+        Self::AverageOf(50),
+        Self::AverageOf(100),
+    ];
+
+    /// Gets the statistics recorded for a session of this kind.
+    ///
+    /// Multi-Blind results are ranked by points rather than by time, so
+    /// they have no history.
+    pub fn for_kind(kind: SessionKind) -> &'static [PbStatistic] {
+        if kind.has_averages() {
+            Self::WITH_AVERAGES
+        } else if kind.has_mean_of_3() {
+            &Self::WITH_AVERAGES[..2]
+        } else {
+            &[]
+        }
+    }
+
+    /// Gets the number of solves the statistic is calculated from.
+    pub fn n_solves(&self) -> usize {
+        match self {
+            Self::Single => 1,
+            Self::MeanOf3 => 3,
+            Self::AverageOf(n) => *n,
+        }
+    }
+
+    /// Formats a value of this statistic with this precision. Means and
+    /// averages are rounded, as the WCA does.
+    pub fn format(&self, result: &SolveResult, precision: TimePrecision) -> String {
+        match self {
+            Self::Single => result.format(precision),
+            Self::MeanOf3 | Self::AverageOf(_) => result.format_average(precision),
+        }
+    }
+
+    /// Calculates the statistic of these results.
+    fn calculate(&self, results: &[SolveResult]) -> Option<SolveResult> {
+        match self {
+            Self::Single => results.first().copied(),
+            Self::MeanOf3 => SolveResult::mean_of_3(results),
+            Self::AverageOf(_) => SolveResult::average_of_n(results),
+        }
+    }
+}
+
+impl Display for PbStatistic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Single => write!(f, "Single"),
+            Self::MeanOf3 => write!(f, "Mo3"),
+            Self::AverageOf(n) => write!(f, "Ao{}", n),
+        }
+    }
+}
+
+/// An improvement of a personal best.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PersonalBest {
+    pub statistic: PbStatistic,
+    /// Index of the solve that completed the new personal best.
+    pub index: usize,
+    /// The previous personal best, if any.
+    pub old: Option<SolveResult>,
+    pub new: SolveResult,
+}
+
+/// Finds every improvement of a personal best in a sequence of results, in
+/// the order they happened.
+///
+/// DNF results are never personal bests.
+pub fn pb_history(results: &[SolveResult], statistics: &[PbStatistic]) -> Vec<PersonalBest> {
+    let mut history = Vec::new();
+    let mut bests: Vec<Option<SolveResult>> = vec![None; statistics.len()];
+
+    for index in 0..results.len() {
+        for (statistic, best) in statistics.iter().zip(bests.iter_mut()) {
+            let n = statistic.n_solves();
+            if index + 1 < n {
+                continue;
+            }
+            let Some(value) = statistic.calculate(&results[index + 1 - n..=index]) else {
+                continue;
+            };
+            if value.is_dnf() || best.is_some_and(|best| value >= best) {
+                continue;
+            }
+
+            history.push(PersonalBest {
+                statistic: *statistic,
+                index,
+                old: *best,
+                new: value,
+            });
+            *best = Some(value);
+        }
+    }
+    history
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::data::{Penalty, SolveTime};
+
+    fn ok(millis: u64) -> SolveResult {
+        SolveResult::Time(SolveTime::new(Duration::from_millis(millis), Penalty::Ok))
+    }
+
+    #[test]
+    fn record_improvements() {
+        let times = [
+            ok(15_000),
+            SolveResult::Time(SolveTime::DNF),
+            ok(14_000),
+            ok(16_000),
+            ok(13_000),
+        ];
+        let history = pb_history(&times, &[PbStatistic::Single, PbStatistic::MeanOf3]);
+
+        let summary: Vec<_> = history
+            .iter()
+            .map(|pb| (pb.statistic, pb.index, pb.old))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (PbStatistic::Single, 0, None),
+                (PbStatistic::Single, 2, Some(ok(15_000))),
+                (PbStatistic::Single, 4, Some(ok(14_000))),
+                (PbStatistic::MeanOf3, 4, None),
+            ]
+        );
+        assert_eq!(
+            PbStatistic::MeanOf3.format(&history[3].new, TimePrecision::default()),
+            "14.33"
+        );
+    }
+
+    #[test]
+    fn averages_need_enough_solves() {
+        let times = [ok(12_000), ok(13_000), ok(14_000), ok(11_000)];
+        assert!(pb_history(&times, &[PbStatistic::AverageOf(5)]).is_empty());
+
+        let times = [ok(12_000), ok(13_000), ok(14_000), ok(11_000), ok(15_000)];
+        let history = pb_history(&times, &[PbStatistic::AverageOf(5)]);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].index, 4);
+        assert_eq!(history[0].new, ok(13_000));
+    }
+
+    #[test]
+    fn statistics_for_kinds() {
+        assert_eq!(PbStatistic::for_kind(SessionKind::Standard).len(), 6);
+        assert_eq!(
+            PbStatistic::for_kind(SessionKind::FewestMoves),
+            &[PbStatistic::Single, PbStatistic::MeanOf3]
+        );
+        assert!(PbStatistic::for_kind(SessionKind::MultiBlind).is_empty());
+        assert_eq!(PbStatistic::AverageOf(12).to_string(), "Ao12");
+    }
+}
@@ -0,0 +1,192 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use chrono::{Days, NaiveDate, NaiveDateTime};
+
+use crate::data::format_time_spent;
+
+/// Idle cutoff used until one is chosen in the settings.
+pub const DEFAULT_IDLE_CUTOFF: Duration = Duration::from_secs(5 * 60);
+
+/// Streaks of consecutive days practiced and the time spent practicing on
+/// each day, over every session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PracticeSummary {
+    /// The day the summary was made on.
+    pub today: NaiveDate,
+    /// Consecutive days practiced up to today. Practicing yesterday keeps
+    /// the streak going until today ends.
+    pub current_streak: usize,
+    pub longest_streak: usize,
+    /// Time spent practicing on each day that has solves.
+    pub time_per_day: BTreeMap<NaiveDate, Duration>,
+}
+
+impl PracticeSummary {
+    /// Summarizes solves given by the local time they ended at and their
+    /// measured time, in any order. Breaks between solves count as practice
+    /// unless they are longer than `idle_cutoff`.
+    pub fn new(
+        solves: &[(NaiveDateTime, Duration)],
+        idle_cutoff: Duration,
+        today: NaiveDate,
+    ) -> Self {
+        let time_per_day = practice_time_per_day(solves, idle_cutoff);
+        let (current_streak, longest_streak) = practice_streaks(&time_per_day, today);
+        Self {
+            today,
+            current_streak,
+            longest_streak,
+            time_per_day,
+        }
+    }
+
+    /// Gets the time spent practicing today.
+    pub fn time_today(&self) -> Duration {
+        self.time_per_day
+            .get(&self.today)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Gets the time spent practicing over every day.
+    pub fn total_time(&self) -> Duration {
+        self.time_per_day.values().sum()
+    }
+
+    /// Describes the current streak and the time practiced today, e.g.
+    /// `3-day streak · 42:10 today`.
+    pub fn description(&self) -> String {
+        let streak = match self.current_streak {
+            0 => "No streak".to_string(),
+            n => format!("{}-day streak", n),
+        };
+        format!(
+            "{} · {} today",
+            streak,
+            format_time_spent(self.time_today())
+        )
+    }
+}
+
+/// Calculates the time spent practicing on each day. Each solve counts for
+/// its measured time, plus the break since the previous solve ended if it
+/// is no longer than `idle_cutoff`. Time is counted on the day each solve
+/// ended.
+pub fn practice_time_per_day(
+    solves: &[(NaiveDateTime, Duration)],
+    idle_cutoff: Duration,
+) -> BTreeMap<NaiveDate, Duration> {
+    let mut solves = solves.to_vec();
+    solves.sort_by_key(|&(end, _)| end);
+
+    let mut time_per_day: BTreeMap<NaiveDate, Duration> = BTreeMap::new();
+    let mut previous_end: Option<NaiveDateTime> = None;
+    for (end, time) in solves {
+        let mut spent = time;
+        if let Some(previous_end) = previous_end {
+            let start = chrono::Duration::from_std(time)
+                .ok()
+                .and_then(|time| end.checked_sub_signed(time))
+                .unwrap_or(end);
+            // Overlapping solves, e.g. in two windows, have no break between
+            // them.
+            let gap = (start - previous_end).to_std().unwrap_or_default();
+            if gap <= idle_cutoff {
+                spent += gap;
+            }
+        }
+        *time_per_day.entry(end.date()).or_default() += spent;
+        previous_end = Some(end);
+    }
+    time_per_day
+}
+
+/// Gets the current and longest streaks of consecutive days in `days`, as
+/// of `today`.
+pub fn practice_streaks<T>(days: &BTreeMap<NaiveDate, T>, today: NaiveDate) -> (usize, usize) {
+    let mut longest = 0;
+    let mut streak = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for &day in days.keys().filter(|&&day| day <= today) {
+        streak = match previous {
+            Some(previous) if previous + Days::new(1) == day => streak + 1,
+            _ => 1,
+        };
+        longest = longest.max(streak);
+        previous = Some(day);
+    }
+
+    let yesterday = today - Days::new(1);
+    let current = match previous {
+        Some(last) if last == today || last == yesterday => streak,
+        _ => 0,
+    };
+    (current, longest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u32, hour: u32, min: u32, sec: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 5, day)
+            .unwrap()
+            .and_hms_opt(hour, min, sec)
+            .unwrap()
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 5, day).unwrap()
+    }
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn count_practice_time() {
+        let solves = [
+            (at(1, 10, 0, 30), secs(15)),
+            // Started 15 seconds after the previous solve ended.
+            (at(1, 10, 1, 0), secs(15)),
+            // Started after a break longer than the cutoff.
+            (at(1, 11, 0, 20), secs(20)),
+            (at(2, 9, 0, 10), secs(10)),
+        ];
+        let time_per_day = practice_time_per_day(&solves, DEFAULT_IDLE_CUTOFF);
+        assert_eq!(time_per_day[&date(1)], secs(15 + 15 + 15 + 20));
+        assert_eq!(time_per_day[&date(2)], secs(10));
+
+        // Solves are sorted, and solves that overlap have no break.
+        let solves = [(at(1, 10, 0, 40), secs(30)), (at(1, 10, 0, 30), secs(30))];
+        let time_per_day = practice_time_per_day(&solves, DEFAULT_IDLE_CUTOFF);
+        assert_eq!(time_per_day[&date(1)], secs(60));
+    }
+
+    #[test]
+    fn count_streaks() {
+        let days: BTreeMap<NaiveDate, ()> = [1, 2, 3, 4, 7, 8, 9]
+            .into_iter()
+            .map(|day| (date(day), ()))
+            .collect();
+        assert_eq!(practice_streaks(&days, date(9)), (3, 4));
+        // The streak goes on until the end of the next day.
+        assert_eq!(practice_streaks(&days, date(10)), (3, 4));
+        assert_eq!(practice_streaks(&days, date(11)), (0, 4));
+        assert_eq!(practice_streaks(&days, date(5)), (4, 4));
+        assert_eq!(
+            practice_streaks(&BTreeMap::<NaiveDate, ()>::new(), date(1)),
+            (0, 0)
+        );
+    }
+
+    #[test]
+    fn summarize_practice() {
+        let solves = [(at(8, 10, 0, 30), secs(30)), (at(9, 10, 0, 45), secs(45))];
+        let summary = PracticeSummary::new(&solves, DEFAULT_IDLE_CUTOFF, date(9));
+        assert_eq!(summary.current_streak, 2);
+        assert_eq!(summary.time_today(), secs(45));
+        assert_eq!(summary.total_time(), secs(75));
+        assert_eq!(summary.description(), "2-day streak · 0:45 today");
+    }
+}
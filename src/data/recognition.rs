@@ -0,0 +1,245 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::data::{Penalty, SolveStatistic, SolveTime, TimePrecision};
+
+/// Number of latest answers kept by the recognition trainer.
+pub const RECOGNITION_HISTORY: usize = 1000;
+
+/// An answer given in the recognition trainer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecognitionAnswer {
+    /// Name of the case shown.
+    pub case: String,
+    /// Name of the case answered.
+    pub answer: String,
+    /// Time from showing the case to answering.
+    pub time: Duration,
+}
+
+impl RecognitionAnswer {
+    pub fn is_correct(&self) -> bool {
+        self.case == self.answer
+    }
+}
+
+/// Wrong answers count as DNF, so the statistics of solves give the
+/// accuracy and the response times of correct answers.
+impl From<&RecognitionAnswer> for SolveTime {
+    fn from(answer: &RecognitionAnswer) -> Self {
+        if answer.is_correct() {
+            SolveTime::new(answer.time, Penalty::Ok)
+        } else {
+            SolveTime::DNF
+        }
+    }
+}
+
+/// Statistics of the answers given for one case.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaseRecognition {
+    pub count: usize,
+    /// Ratio of correct answers, between 0 and 1.
+    pub accuracy: Option<f64>,
+    /// Mean response time of correct answers.
+    pub mean: Option<SolveTime>,
+    /// Average of the latest 5 answers, DNF if two of them are wrong.
+    pub ao5: Option<SolveTime>,
+}
+
+impl CaseRecognition {
+    /// Calculates the statistics of the answers given for a case, in the
+    /// order they were given.
+    pub fn new(answers: &[RecognitionAnswer], case: &str) -> Self {
+        let answers: Vec<RecognitionAnswer> = answers
+            .iter()
+            .filter(|answer| answer.case == case)
+            .cloned()
+            .collect();
+        Self {
+            count: answers.len(),
+            accuracy: answers.success_rate(),
+            mean: answers.mean_of_successes(),
+            ao5: answers
+                .len()
+                .checked_sub(5)
+                .and_then(|start| answers[start..].average_of_n()),
+        }
+    }
+
+    /// Describes the statistics with this precision, e.g.
+    /// `90% correct · Mean 1.23 · Ao5 1.10`.
+    pub fn description(&self, precision: TimePrecision) -> String {
+        let Some(accuracy) = self.accuracy else {
+            return "Not shown yet".to_string();
+        };
+        let mut parts = vec![format!("{:.0}% correct", accuracy * 100.0)];
+        if let Some(mean) = self.mean {
+            parts.push(format!("Mean {}", mean.format_average(precision)));
+        }
+        if let Some(ao5) = self.ao5 {
+            parts.push(format!("Ao5 {}", ao5.format_average(precision)));
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Writes answers as text to save them, one answer per line with the case
+/// shown, the case answered and the time in milliseconds separated by tabs.
+pub fn format_recognition_answers(answers: &[RecognitionAnswer]) -> String {
+    answers
+        .iter()
+        .map(|answer| {
+            format!(
+                "{}\t{}\t{}\n",
+                answer.case,
+                answer.answer,
+                answer.time.as_millis()
+            )
+        })
+        .collect()
+}
+
+/// Reads the answers saved by `format_recognition_answers`, skipping lines
+/// that are not answers.
+pub fn parse_recognition_answers(text: &str) -> Vec<RecognitionAnswer> {
+    text.lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let case = fields.next()?.to_string();
+            let answer = fields.next()?.to_string();
+            let time = Duration::from_millis(fields.next()?.parse().ok()?);
+            Some(RecognitionAnswer { case, answer, time })
+        })
+        .collect()
+}
+
+/// Counts how often each case was taken for another, as the case shown,
+/// the case answered and the number of times, most frequent first.
+pub fn confusion_pairs(answers: &[RecognitionAnswer]) -> Vec<(String, String, usize)> {
+    let mut counts: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+    for answer in answers.iter().filter(|answer| !answer.is_correct()) {
+        *counts
+            .entry((answer.case.as_str(), answer.answer.as_str()))
+            .or_default() += 1;
+    }
+
+    let mut pairs: Vec<(String, String, usize)> = counts
+        .into_iter()
+        .map(|((case, answer), count)| (case.to_string(), answer.to_string(), count))
+        .collect();
+    pairs.sort_by_key(|pair| Reverse(pair.2));
+    pairs
+}
+
+/// How a typed name matches the names of the cases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameMatch {
+    /// Only the case at this index starts with the typed name, or the name
+    /// is typed in full.
+    Unique(usize),
+    /// Several cases start with the typed name.
+    Ambiguous,
+    /// No case starts with the typed name.
+    NoMatch,
+}
+
+/// Matches a name typed so far against the names of the cases, ignoring
+/// case. With `complete`, a name typed in full matches even if longer names
+/// start with it.
+pub fn match_case_name<S: AsRef<str>>(names: &[S], typed: &str, complete: bool) -> NameMatch {
+    let typed = typed.trim().to_lowercase();
+    if typed.is_empty() {
+        return NameMatch::Ambiguous;
+    }
+    let names: Vec<String> = names.iter().map(|n| n.as_ref().to_lowercase()).collect();
+    if complete {
+        if let Some(i) = names.iter().position(|name| *name == typed) {
+            return NameMatch::Unique(i);
+        }
+    }
+
+    let mut candidates = names
+        .iter()
+        .enumerate()
+        .filter(|(_, name)| name.starts_with(&typed));
+    match (candidates.next(), candidates.next()) {
+        (Some((i, _)), None) => NameMatch::Unique(i),
+        (Some(_), Some(_)) => NameMatch::Ambiguous,
+        (None, _) => NameMatch::NoMatch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer(case: &str, answer: &str, millis: u64) -> RecognitionAnswer {
+        RecognitionAnswer {
+            case: case.to_string(),
+            answer: answer.to_string(),
+            time: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn recognition_statistics() {
+        let answers = [
+            answer("Ga", "Ga", 1_000),
+            answer("Ga", "Gc", 2_000),
+            answer("T", "T", 500),
+            answer("Ga", "Ga", 1_500),
+            answer("Ga", "Ga", 1_200),
+            answer("Ga", "Ga", 1_300),
+            answer("Ga", "Gc", 900),
+            answer("Ja", "Jb", 800),
+        ];
+        let ga = CaseRecognition::new(&answers, "Ga");
+        assert_eq!(ga.count, 6);
+        assert_eq!(ga.accuracy, Some(4.0 / 6.0));
+        assert_eq!(ga.mean.unwrap().to_string(), "1.25");
+        assert_eq!(ga.ao5, Some(SolveTime::DNF));
+        assert_eq!(
+            ga.description(TimePrecision::Centiseconds),
+            "67% correct · Mean 1.25 · Ao5 DNF"
+        );
+        assert_eq!(
+            CaseRecognition::new(&answers, "H").description(TimePrecision::Centiseconds),
+            "Not shown yet"
+        );
+
+        assert_eq!(
+            confusion_pairs(&answers),
+            vec![
+                ("Ga".to_string(), "Gc".to_string(), 2),
+                ("Ja".to_string(), "Jb".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn read_saved_answers() {
+        let answers = vec![answer("Ga", "Ga", 1_250), answer("Ja", "Jb", 800)];
+        let text = format_recognition_answers(&answers);
+        assert_eq!(text, "Ga\tGa\t1250\nJa\tJb\t800\n");
+        assert_eq!(parse_recognition_answers(&text), answers);
+        assert_eq!(
+            parse_recognition_answers("T\tT\nT\tT\tabc\n\nT\tY\t900"),
+            vec![answer("T", "Y", 900)]
+        );
+    }
+
+    #[test]
+    fn match_typed_names() {
+        let names = ["Aa", "Ab", "E", "Ga", "Gb", "T"];
+        assert_eq!(match_case_name(&names, "t", false), NameMatch::Unique(5));
+        assert_eq!(match_case_name(&names, "G", false), NameMatch::Ambiguous);
+        assert_eq!(match_case_name(&names, "gb", false), NameMatch::Unique(4));
+        assert_eq!(match_case_name(&names, "x", false), NameMatch::NoMatch);
+
+        let names = ["1", "12", "2"];
+        assert_eq!(match_case_name(&names, "1", false), NameMatch::Ambiguous);
+        assert_eq!(match_case_name(&names, "1", true), NameMatch::Unique(0));
+    }
+}
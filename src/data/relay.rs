@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use crate::data::{generate_cube_scramble, Penalty, SolveTime, TimePrecision};
+
+/// Gets the name of the NxNxN cube of this size, e.g. `4x4x4`.
+pub fn cube_name(size: u8) -> String {
+    format!("{0}x{0}x{0}", size)
+}
+
+/// The scramble of one cube of a relay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelayScramble {
+    /// Size of the NxNxN cube.
+    pub size: u8,
+    pub scramble: String,
+}
+
+/// Generates a scramble for each cube of a relay.
+pub fn generate_relay_scrambles(sizes: &[u8]) -> Vec<RelayScramble> {
+    sizes
+        .iter()
+        .map(|&size| RelayScramble {
+            size,
+            scramble: generate_cube_scramble(size),
+        })
+        .collect()
+}
+
+/// Formats the scrambles of a relay, one line for each cube, e.g.
+/// `2x2x2: R U2 F'`.
+pub fn format_relay_scrambles(scrambles: &[RelayScramble]) -> String {
+    scrambles
+        .iter()
+        .map(|s| format!("{}: {}", cube_name(s.size), s.scramble))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Gets the time spent on each puzzle of a relay from the splits recorded
+/// when a puzzle was done and the total time.
+///
+/// Returns `None` unless there is a split between every two puzzles.
+pub fn puzzle_times(
+    n_puzzles: usize,
+    splits: &[Duration],
+    total: Duration,
+) -> Option<Vec<Duration>> {
+    if n_puzzles == 0 || splits.len() + 1 != n_puzzles {
+        return None;
+    }
+
+    let mut times = Vec::with_capacity(n_puzzles);
+    let mut previous = Duration::ZERO;
+    for &split in splits.iter().chain([&total]) {
+        times.push(split.saturating_sub(previous));
+        previous = split;
+    }
+    Some(times)
+}
+
+/// Formats the time spent on each cube of a relay with this precision, e.g.
+/// `2x2x2 4.12 · 3x3x3 12.50`.
+pub fn format_puzzle_times(sizes: &[u8], times: &[Duration], precision: TimePrecision) -> String {
+    sizes
+        .iter()
+        .zip(times)
+        .map(|(&size, &time)| {
+            let time = SolveTime::new(time, Penalty::Ok);
+            format!("{} {}", cube_name(size), time.format(precision))
+        })
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(s: f64) -> Duration {
+        Duration::from_secs_f64(s)
+    }
+
+    #[test]
+    fn split_relay_into_puzzles() {
+        assert_eq!(
+            puzzle_times(3, &[secs(5.0), secs(17.5)], secs(60.0)),
+            Some(vec![secs(5.0), secs(12.5), secs(42.5)])
+        );
+        assert_eq!(puzzle_times(3, &[secs(5.0)], secs(60.0)), None);
+        assert_eq!(puzzle_times(1, &[], secs(8.0)), Some(vec![secs(8.0)]));
+    }
+
+    #[test]
+    fn format_times() {
+        assert_eq!(
+            format_puzzle_times(
+                &[2, 3],
+                &[secs(4.12), secs(12.5)],
+                TimePrecision::Centiseconds
+            ),
+            "2x2x2 4.12 · 3x3x3 12.50"
+        );
+    }
+
+    #[test]
+    fn format_scrambles() {
+        let scrambles = [
+            RelayScramble {
+                size: 2,
+                scramble: "R U".to_string(),
+            },
+            RelayScramble {
+                size: 3,
+                scramble: "F2 D'".to_string(),
+            },
+        ];
+        assert_eq!(
+            format_relay_scrambles(&scrambles),
+            "2x2x2: R U\n3x3x3: F2 D'"
+        );
+    }
+}
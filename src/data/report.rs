@@ -0,0 +1,350 @@
+use crate::data::{SolveStatistic, SolveTime, TimePrecision};
+
+/// Text layout of a report.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// Statistics followed by a time list, as exported by csTimer.
+    #[default]
+    CsTimer,
+    /// Statistics followed by a solve list, with comments on their own line.
+    Plain,
+    /// Tables of the statistics and solves.
+    Markdown,
+    /// Bold statistics and the solve list in a code block, which keeps the
+    /// columns aligned in chat messages.
+    Discord,
+}
+
+impl ReportFormat {
+    /// All report formats.
+    pub const ALL: &'static [ReportFormat] =
+        &[Self::CsTimer, Self::Plain, Self::Markdown, Self::Discord];
+
+    /// Gets the format with this nick.
+    pub fn from_nick(nick: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|format| format.nick() == nick)
+    }
+
+    /// Gets the nick of this format.
+    pub fn nick(&self) -> &'static str {
+        match self {
+            Self::CsTimer => "cstimer",
+            Self::Plain => "plain",
+            Self::Markdown => "markdown",
+            Self::Discord => "discord",
+        }
+    }
+
+    /// Gets the human readable name of this format.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::CsTimer => "csTimer",
+            Self::Plain => "Plain Text",
+            Self::Markdown => "Markdown",
+            Self::Discord => "Discord",
+        }
+    }
+
+    /// Gets the extension of files saved in this format.
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            _ => "txt",
+        }
+    }
+
+    /// Gets the MIME type of files saved in this format.
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Markdown => "text/markdown",
+            _ => "text/plain",
+        }
+    }
+}
+
+/// A solve listed in a report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportSolve {
+    /// Position of the solve in its session, starting from 1.
+    pub number: usize,
+    pub time: SolveTime,
+    pub scramble: String,
+    pub comment: String,
+}
+
+impl From<&ReportSolve> for SolveTime {
+    fn from(value: &ReportSolve) -> Self {
+        value.time
+    }
+}
+
+/// A shareable summary of statistics and solves, which can be written in
+/// any `ReportFormat`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    pub title: String,
+    /// Name and value of each statistic, in order.
+    pub statistics: Vec<(String, String)>,
+    pub solves: Vec<ReportSolve>,
+    /// Positions in `solves` of the solves excluded from the average, which
+    /// are put in parentheses.
+    pub trimmed: Vec<usize>,
+    /// Precision the times of the solves are written with.
+    pub precision: TimePrecision,
+}
+
+impl Report {
+    /// Creates a report of these solves, with their mean, best and worst
+    /// time written with this precision. Reports of 3 solves or more have an
+    /// average, which excludes the best and worst solve.
+    pub fn of_solves(title: &str, solves: Vec<ReportSolve>, precision: TimePrecision) -> Self {
+        let mut statistics = Vec::new();
+        let mut trimmed = Vec::new();
+
+        if let Some(average) = solves.average_of_n() {
+            statistics.push(("Average".to_string(), average.format_average(precision)));
+            trimmed.extend(solves.best_solve_index());
+            trimmed.extend(solves.worst_solve_index());
+        }
+        if let Some(mean) = solves.mean_of_n() {
+            statistics.push(("Mean".to_string(), mean.format_average(precision)));
+        }
+        if let Some(best) = solves.best_of_n() {
+            statistics.push(("Best".to_string(), best.format(precision)));
+        }
+        if let Some(worst) = solves.worst_solve_index().map(|i| solves[i].time) {
+            statistics.push(("Worst".to_string(), worst.format(precision)));
+        }
+
+        Self {
+            title: title.to_string(),
+            statistics,
+            solves,
+            trimmed,
+            precision,
+        }
+    }
+
+    /// Creates a report of statistics only.
+    pub fn summary(title: &str, statistics: Vec<(String, String)>) -> Self {
+        Self {
+            title: title.to_string(),
+            statistics,
+            ..Default::default()
+        }
+    }
+
+    /// Writes the report in this format.
+    pub fn to_text(&self, format: ReportFormat) -> String {
+        let mut lines = Vec::new();
+        match format {
+            ReportFormat::CsTimer => {
+                lines.push(self.title.clone());
+                for (name, value) in &self.statistics {
+                    lines.push(format!("{}: {}", name, value));
+                }
+                if !self.solves.is_empty() {
+                    lines.push(String::new());
+                    lines.push("Time List:".to_string());
+                }
+                for (i, solve) in self.solves.iter().enumerate() {
+                    let mut line = format!("{}. {}", solve.number, self.time_text(i));
+                    if !solve.comment.is_empty() {
+                        line.push_str(&format!("[{}]", solve.comment));
+                    }
+                    push_scramble(&mut line, solve);
+                    lines.push(line);
+                }
+            }
+            ReportFormat::Plain => {
+                lines.push(self.title.clone());
+                lines.push(String::new());
+                for (name, value) in &self.statistics {
+                    lines.push(format!("{}: {}", name, value));
+                }
+                if !self.solves.is_empty() {
+                    lines.push(String::new());
+                }
+                for (i, solve) in self.solves.iter().enumerate() {
+                    let mut line = format!("{}. {}", solve.number, self.time_text(i));
+                    push_scramble(&mut line, solve);
+                    lines.push(line);
+                    if !solve.comment.is_empty() {
+                        lines.push(format!("   {}", solve.comment));
+                    }
+                }
+            }
+            ReportFormat::Markdown => {
+                lines.push(format!("## {}", self.title));
+                if !self.statistics.is_empty() {
+                    lines.push(String::new());
+                    lines.push("| Statistic | Value |".to_string());
+                    lines.push("| --- | --- |".to_string());
+                }
+                for (name, value) in &self.statistics {
+                    lines.push(format!("| {} | {} |", name, value));
+                }
+                if !self.solves.is_empty() {
+                    lines.push(String::new());
+                    lines.push("| # | Time | Scramble | Comment |".to_string());
+                    lines.push("| --- | --- | --- | --- |".to_string());
+                }
+                for (i, solve) in self.solves.iter().enumerate() {
+                    lines.push(format!(
+                        "| {} | {} | {} | {} |",
+                        solve.number,
+                        self.time_text(i),
+                        escape_markdown_cell(&one_line(&solve.scramble)),
+                        escape_markdown_cell(&one_line(&solve.comment)),
+                    ));
+                }
+            }
+            ReportFormat::Discord => {
+                lines.push(format!("**{}**", self.title));
+                if !self.statistics.is_empty() {
+                    lines.push(
+                        self.statistics
+                            .iter()
+                            .map(|(name, value)| format!("{}: **{}**", name, value))
+                            .collect::<Vec<_>>()
+                            .join(" · "),
+                    );
+                }
+                if !self.solves.is_empty() {
+                    lines.push("```".to_string());
+                }
+                for (i, solve) in self.solves.iter().enumerate() {
+                    let mut line = format!("{}. {}", solve.number, self.time_text(i));
+                    push_scramble(&mut line, solve);
+                    if !solve.comment.is_empty() {
+                        line.push_str(&format!("   # {}", one_line(&solve.comment)));
+                    }
+                    lines.push(line);
+                }
+                if !self.solves.is_empty() {
+                    lines.push("```".to_string());
+                }
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Gets the time of the solve at this position, in parentheses if it is
+    /// trimmed.
+    fn time_text(&self, i: usize) -> String {
+        let time = self.solves[i].time.format(self.precision);
+        if self.trimmed.contains(&i) {
+            format!("({})", time)
+        } else {
+            time
+        }
+    }
+}
+
+/// Appends the scramble of a solve to its line, if it has one.
+fn push_scramble(line: &mut String, solve: &ReportSolve) {
+    if !solve.scramble.is_empty() {
+        line.push_str("   ");
+        line.push_str(&one_line(&solve.scramble));
+    }
+}
+
+/// Joins the lines of a text, such as the scrambles of a relay.
+fn one_line(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+fn escape_markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::data::Penalty;
+
+    fn solve(number: usize, millis: u64, comment: &str) -> ReportSolve {
+        ReportSolve {
+            number,
+            time: SolveTime::new(Duration::from_millis(millis), Penalty::Ok),
+            scramble: format!("R U{}", number),
+            comment: comment.to_string(),
+        }
+    }
+
+    fn average_of_5() -> Report {
+        Report::of_solves(
+            "Average of 5",
+            vec![
+                solve(1, 12_000, ""),
+                solve(2, 11_000, "PLL skip"),
+                solve(3, 13_000, ""),
+                solve(4, 15_000, ""),
+                solve(5, 14_000, ""),
+            ],
+            TimePrecision::Centiseconds,
+        )
+    }
+
+    #[test]
+    fn trim_average() {
+        let report = average_of_5();
+        assert_eq!(report.trimmed, vec![1, 3]);
+        assert_eq!(
+            report.statistics[0],
+            ("Average".to_string(), "13.00".to_string())
+        );
+
+        let report = Report::of_solves(
+            "Solves",
+            vec![solve(1, 12_000, ""), solve(2, 11_000, "")],
+            TimePrecision::Centiseconds,
+        );
+        assert!(report.trimmed.is_empty());
+        assert_eq!(report.statistics[0].0, "Mean");
+    }
+
+    #[test]
+    fn format_cstimer() {
+        let text = average_of_5().to_text(ReportFormat::CsTimer);
+        assert!(text.starts_with("Average of 5\nAverage: 13.00\nMean: 13.00\n"));
+        assert!(text.contains("\n\nTime List:\n1. 12.00   R U1\n2. (11.00)[PLL skip]   R U2\n"));
+        assert!(text.ends_with("4. (15.00)   R U4\n5. 14.00   R U5"));
+    }
+
+    #[test]
+    fn format_markdown_and_discord() {
+        let mut report = average_of_5();
+        report.solves[0].comment = "a | b".to_string();
+
+        let text = report.to_text(ReportFormat::Markdown);
+        assert!(text.starts_with("## Average of 5\n\n| Statistic | Value |"));
+        assert!(text.contains("| 1 | 12.00 | R U1 | a \\| b |"));
+        assert!(text.contains("| 2 | (11.00) | R U2 | PLL skip |"));
+
+        let text = report.to_text(ReportFormat::Discord);
+        assert!(text.starts_with("**Average of 5**\nAverage: **13.00** · Mean: **13.00**"));
+        assert!(text.contains("```\n1. 12.00   R U1   # a | b\n"));
+        assert!(text.ends_with("5. 14.00   R U5\n```"));
+    }
+
+    #[test]
+    fn format_summary() {
+        let report = Report::summary("3x3", vec![("Solves".to_string(), "40".to_string())]);
+        assert_eq!(report.to_text(ReportFormat::Plain), "3x3\n\nSolves: 40");
+        assert_eq!(
+            ReportFormat::from_nick("markdown"),
+            Some(ReportFormat::Markdown)
+        );
+        assert_eq!(ReportFormat::Markdown.file_extension(), "md");
+    }
+}
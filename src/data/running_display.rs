@@ -0,0 +1,83 @@
+use crate::data::TimePrecision;
+
+/// How the time is shown while the timer is running.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RunningDisplay {
+    /// The time with every decimal of the display precision.
+    #[default]
+    Full,
+    /// The time with tenths of a second only.
+    Tenths,
+    /// Whole seconds only.
+    Seconds,
+    /// No time, only the word "Solving".
+    Hidden,
+}
+
+impl RunningDisplay {
+    /// All running display modes.
+    pub const ALL: &'static [RunningDisplay] =
+        &[Self::Full, Self::Tenths, Self::Seconds, Self::Hidden];
+
+    /// Gets the mode with this nick.
+    pub fn from_nick(nick: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|mode| mode.nick() == nick)
+    }
+
+    /// Gets the nick of this mode.
+    pub fn nick(&self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::Tenths => "tenths",
+            Self::Seconds => "seconds",
+            Self::Hidden => "hidden",
+        }
+    }
+
+    /// Gets the human readable name of this mode.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Full => "Full Time",
+            Self::Tenths => "Tenths",
+            Self::Seconds => "Seconds Only",
+            Self::Hidden => "Hidden",
+        }
+    }
+
+    /// Gets the number of decimals of the running time, or `None` if it is
+    /// hidden. Times are never shown more precisely than `precision`.
+    pub fn decimals(&self, precision: TimePrecision) -> Option<usize> {
+        match self {
+            Self::Full => Some(precision.decimals()),
+            Self::Tenths => Some(1.min(precision.decimals())),
+            Self::Seconds => Some(0),
+            Self::Hidden => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn running_decimals() {
+        let precision = TimePrecision::Milliseconds;
+        assert_eq!(RunningDisplay::Full.decimals(precision), Some(3));
+        assert_eq!(RunningDisplay::Tenths.decimals(precision), Some(1));
+        assert_eq!(RunningDisplay::Seconds.decimals(precision), Some(0));
+        assert_eq!(RunningDisplay::Hidden.decimals(precision), None);
+        assert_eq!(
+            RunningDisplay::Full.decimals(TimePrecision::Centiseconds),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn running_display_nicks() {
+        for &mode in RunningDisplay::ALL {
+            assert_eq!(RunningDisplay::from_nick(mode.nick()), Some(mode));
+        }
+        assert_eq!(RunningDisplay::from_nick("solving"), None);
+    }
+}
@@ -0,0 +1,267 @@
+use gtk::glib;
+
+use crate::data::{
+    format_moves, invert_moves, parse_moves, simplify_moves, solve_last_layer, to_face_turns, Cube,
+    Face, LastLayerCase, Move, MoveKind, OLL_ALGORITHMS,
+};
+
+/// Number of random moves in a 3x3x3 scramble.
+pub const SCRAMBLE_LENGTH: usize = 20;
+
+/// Faces turned in scrambles of the 2x2x2 cube, which keep the
+/// down-back-left corner in place.
+const POCKET_CUBE_FACES: &[Face] = &[Face::R, Face::U, Face::F];
+
+/// Moves that start and end every Fewest Moves scramble, as in WCA
+/// scrambles, so the scramble cannot be cancelled into the solution.
+const FEWEST_MOVES_PADDING: &str = "R' U' F";
+
+/// Generates a random move scramble for the 3x3x3 cube.
+pub fn generate_scramble() -> Vec<Move> {
+    random_moves(SCRAMBLE_LENGTH, &[], random_int)
+}
+
+/// Generates a random move scramble for the NxNxN cube of this size, from
+/// 2x2x2 to 7x7x7.
+pub fn generate_cube_scramble(size: u8) -> String {
+    match size {
+        3 => format_moves(&generate_scramble()),
+        _ => cube_scramble(size, random_int),
+    }
+}
+
+/// Gets the number of random moves in a scramble for the NxNxN cube of this
+/// size.
+fn cube_scramble_length(size: u8) -> usize {
+    match size {
+        0..=2 => 11,
+        3 => SCRAMBLE_LENGTH,
+        _ => (size as usize - 2) * 20,
+    }
+}
+
+/// Generates random moves for an NxNxN cube in WCA notation, where turns of
+/// the outer `n` layers are written `Rw` for two layers and `3Rw` for more.
+/// A layer is never turned twice in a row, and no more than two consecutive
+/// moves share an axis.
+fn cube_scramble(size: u8, mut random: impl FnMut(u32) -> u32) -> String {
+    let faces = if size <= 2 {
+        POCKET_CUBE_FACES
+    } else {
+        &Face::ALL
+    };
+    let max_depth = (size / 2).max(1);
+    let mut moves: Vec<(Face, u8, u8)> = Vec::new();
+
+    while moves.len() < cube_scramble_length(size) {
+        let face = faces[random(faces.len() as u32) as usize];
+        let depth = random(max_depth as u32) as u8 + 1;
+        let turns = random(3) as u8 + 1;
+
+        let n = moves.len();
+        if n >= 1 && moves[n - 1].0 == face && moves[n - 1].1 == depth {
+            continue;
+        }
+        if n >= 2 && moves[n - 2..].iter().all(|m| m.0.axis() == face.axis()) {
+            continue;
+        }
+        moves.push((face, depth, turns));
+    }
+
+    moves
+        .iter()
+        .map(|&(face, depth, turns)| {
+            let layers = if depth > 2 {
+                depth.to_string()
+            } else {
+                String::new()
+            };
+            let wide = if depth > 1 { "w" } else { "" };
+            let turns = match turns {
+                2 => "2",
+                3 => "'",
+                _ => "",
+            };
+            format!("{}{}{}{}", layers, face.to_char(), wide, turns)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Generates a scramble for the Fewest Moves Challenge, which is padded
+/// with `R' U' F` on both ends.
+pub fn generate_fewest_moves_scramble() -> Vec<Move> {
+    fewest_moves_scramble(random_int)
+}
+
+fn fewest_moves_scramble(mut random: impl FnMut(u32) -> u32) -> Vec<Move> {
+    let padding = parse_moves(FEWEST_MOVES_PADDING).expect("padding should be valid notation");
+    let mut scramble = padding.clone();
+    scramble.extend(random_moves(SCRAMBLE_LENGTH, &padding, &mut random));
+
+    // The last random move must not be on the axis of the final padding
+    // moves, or it could be cancelled or commuted with them.
+    while scramble
+        .last()
+        .and_then(face_of)
+        .is_some_and(|face| face.axis() == Face::R.axis())
+    {
+        scramble.pop();
+        scramble.extend(random_moves(1, &scramble, &mut random));
+    }
+
+    scramble.extend(padding);
+    scramble
+}
+
+/// Generates a scramble for a last layer case, which leaves the first two
+/// layers solved and the last layer in a random state of the case.
+pub fn generate_case_scramble(case: &LastLayerCase) -> Vec<Move> {
+    case_scramble(case, random_int)
+}
+
+fn case_scramble(case: &LastLayerCase, mut random: impl FnMut(u32) -> u32) -> Vec<Move> {
+    let setup = case.random_setup(&mut random);
+
+    // Undoing the setup itself would give the algorithm of the case away,
+    // so the scramble starts with a random OLL algorithm and the rest is
+    // found by solving what is left from there.
+    let algorithm = OLL_ALGORITHMS[random(OLL_ALGORITHMS.len() as u32) as usize];
+    let start = invert_moves(&parse_moves(algorithm).expect("algorithms should be valid notation"));
+    let mut cube = Cube::new();
+    cube.apply_moves(&invert_moves(&start));
+    cube.apply_moves(&setup);
+    let rest = solve_last_layer(&cube).expect("the first two layers should be solved");
+
+    let mut scramble = start;
+    scramble.extend(invert_moves(&rest));
+    simplify_moves(&to_face_turns(&scramble))
+}
+
+/// Generates `length` random face turns following `history`. A face is
+/// never turned twice in a row, and no more than two consecutive moves
+/// share an axis.
+fn random_moves(length: usize, history: &[Move], mut random: impl FnMut(u32) -> u32) -> Vec<Move> {
+    let mut moves: Vec<Move> = history.to_vec();
+
+    while moves.len() < history.len() + length {
+        let face = Face::ALL[random(Face::ALL.len() as u32) as usize];
+        let turns = random(3) as u8 + 1;
+
+        let last = moves.last().and_then(face_of);
+        let second_last = moves.len().checked_sub(2).and_then(|i| face_of(&moves[i]));
+        if last == Some(face) {
+            continue;
+        }
+        if last.is_some_and(|l| l == face.opposite())
+            && second_last.is_some_and(|s| s.axis() == face.axis())
+        {
+            continue;
+        }
+
+        moves.push(Move::new(MoveKind::Face(face), turns));
+    }
+
+    moves.split_off(history.len())
+}
+
+fn face_of(m: &Move) -> Option<Face> {
+    match m.kind {
+        MoveKind::Face(face) => Some(face),
+        _ => None,
+    }
+}
+
+/// Gets a random number between 0 and `n`, exclusive.
+fn random_int(n: u32) -> u32 {
+    glib::random_int_range(0, n as i32) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::notation::parse_scramble;
+    use crate::data::{last_layer_cases, CaseSet};
+
+    /// A predictable sequence of numbers standing in for random numbers.
+    fn sequence() -> impl FnMut(u32) -> u32 {
+        let mut state: u32 = 12345;
+        move |n| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (state >> 16) % n
+        }
+    }
+
+    fn assert_valid(moves: &[Move]) {
+        for window in moves.windows(2) {
+            assert_ne!(face_of(&window[0]), face_of(&window[1]));
+        }
+        for window in moves.windows(3) {
+            let axes: Vec<_> = window.iter().map(|m| face_of(m).unwrap().axis()).collect();
+            assert!(!(axes[0] == axes[1] && axes[1] == axes[2]));
+        }
+    }
+
+    #[test]
+    fn random_scramble_is_valid() {
+        let moves = random_moves(SCRAMBLE_LENGTH, &[], sequence());
+        assert_eq!(moves.len(), SCRAMBLE_LENGTH);
+        assert_valid(&moves);
+    }
+
+    #[test]
+    fn fewest_moves_scramble_is_padded() {
+        let mut random = sequence();
+        for _ in 0..20 {
+            let scramble = fewest_moves_scramble(&mut random);
+            let text = format_moves(&scramble);
+            assert!(text.starts_with("R' U' F "));
+            assert!(text.ends_with(" R' U' F"));
+            assert_eq!(scramble.len(), SCRAMBLE_LENGTH + 6);
+            assert_valid(&scramble);
+        }
+    }
+
+    #[test]
+    fn case_scramble_reaches_case() {
+        for set in CaseSet::ALL {
+            let case = &last_layer_cases(*set)[0];
+            let scramble = case_scramble(case, sequence());
+            assert!(scramble.iter().all(|m| matches!(m.kind, MoveKind::Face(_))));
+            let mut cube = Cube::new();
+            cube.apply_moves(&scramble);
+            let mut setup = Cube::new();
+            setup.apply_moves(&case.random_setup(sequence()));
+            assert_eq!(cube, setup, "{}", case.id());
+        }
+    }
+
+    #[test]
+    fn cube_scrambles_use_wca_notation() {
+        let pocket = cube_scramble(2, sequence());
+        assert_eq!(pocket.split(' ').count(), 11);
+        assert!(pocket
+            .split(' ')
+            .all(|m| matches!(m.chars().next(), Some('R' | 'U' | 'F'))));
+
+        let big = cube_scramble(7, sequence());
+        let moves: Vec<&str> = big.split(' ').collect();
+        assert_eq!(moves.len(), 100);
+        assert!(moves.iter().any(|m| m.starts_with('3')));
+        assert!(moves.iter().all(|m| !m.starts_with('4')));
+        assert!(moves.iter().any(|m| m.contains('w')));
+        assert!(moves
+            .windows(2)
+            .all(|w| w[0].trim_end_matches(['2', '\'']) != w[1].trim_end_matches(['2', '\''])));
+    }
+
+    #[test]
+    fn cube_scrambles_parse() {
+        let mut random = sequence();
+        for size in 2..=7 {
+            let scramble = cube_scramble(size, &mut random);
+            let moves = parse_scramble(size, &scramble).unwrap();
+            assert_eq!(moves.len(), cube_scramble_length(size));
+        }
+    }
+}
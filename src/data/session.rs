@@ -1,4 +1,14 @@
-use crate::data::{SessionItem, SolveData, SolveStatistic, SolveTime};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+};
+
+use crate::data::{
+    local_date, pb_history, summarize_by_period, summarize_day, tag_statistics, DatePeriod, Goal,
+    GoalProgress, PbStatistic, Penalty, PeriodSummary, PersonalBest, Report, SessionItem,
+    SessionKind, SessionReport, SolveData, SolveResult, SolveStatistic, SolveTime, TagStatistics,
+    TimeNeeded, TimePrecision,
+};
 use gtk::prelude::*;
 use gtk::subclass::prelude::*;
 use gtk::{gio, glib};
@@ -6,7 +16,10 @@ use gtk::{gio, glib};
 #[allow(clippy::enum_variant_names)]
 #[doc(hidden)]
 mod imp {
-    use std::{cell::RefCell, marker::PhantomData};
+    use std::{
+        cell::{Cell, RefCell},
+        marker::PhantomData,
+    };
 
     use gtk::glib::subclass::{Signal, SignalType};
     use once_cell::sync::Lazy;
@@ -18,6 +31,21 @@ mod imp {
     pub struct Session {
         pub(super) solve_list: RefCell<Vec<SessionItem>>,
         pub(super) handler_list: RefCell<Vec<glib::SignalHandlerId>>,
+        /// Every improvement of a personal best, recomputed when solves
+        /// change.
+        pub(super) pb_history: RefCell<Vec<PersonalBest>>,
+        /// Practice targets of the session.
+        pub(super) goals: RefCell<Vec<Goal>>,
+        /// Whether each goal was met when solves last changed, to celebrate
+        /// goals only when they are newly met.
+        pub(super) goals_met: RefCell<Vec<bool>>,
+        /// Precision times and statistics are displayed with.
+        pub(super) time_precision: Cell<TimePrecision>,
+
+        #[property(get, set)]
+        name: RefCell<String>,
+        #[property(get, set, builder(SessionKind::default()))]
+        kind: Cell<SessionKind>,
 
         #[property(get = Self::last_solve_string)]
         last_solve_string: PhantomData<String>,
@@ -35,6 +63,22 @@ mod imp {
         best_ao5_string: PhantomData<String>,
         #[property(get = Self::best_ao12_string)]
         best_ao12_string: PhantomData<String>,
+        #[property(get = Self::last_bo3_string)]
+        last_bo3_string: PhantomData<String>,
+        #[property(get = Self::success_rate_string)]
+        success_rate_string: PhantomData<String>,
+        #[property(get = Self::mean_of_successes_string)]
+        mean_of_successes_string: PhantomData<String>,
+        #[property(get = Self::bpa_string)]
+        bpa_string: PhantomData<String>,
+        #[property(get = Self::wpa_string)]
+        wpa_string: PhantomData<String>,
+        #[property(get = Self::ao5_needed_string)]
+        ao5_needed_string: PhantomData<String>,
+        #[property(get = Self::ao12_needed_string)]
+        ao12_needed_string: PhantomData<String>,
+        #[property(get = Self::today_summary_string)]
+        today_summary_string: PhantomData<String>,
     }
 
     impl Session {
@@ -42,16 +86,14 @@ mod imp {
             self.solve_list
                 .borrow()
                 .last()
-                .map(SessionItem::time)
-                .map_or(String::default(), |t| t.to_string())
+                .map_or(String::default(), |item| item.solve_time_string())
         }
 
         fn last_mo3_string(&self) -> String {
             self.solve_list
                 .borrow()
                 .last()
-                .and_then(SessionItem::mo3)
-                .map_or(String::default(), |t| t.to_string())
+                .map_or(String::default(), |item| item.mo3_string())
         }
 
         fn last_ao5_string(&self) -> String {
@@ -59,7 +101,9 @@ mod imp {
                 .borrow()
                 .last()
                 .and_then(SessionItem::ao5)
-                .map_or(String::default(), |t| t.to_string())
+                .map_or(String::default(), |t| {
+                    t.format_average(self.time_precision.get())
+                })
         }
 
         fn last_ao12_string(&self) -> String {
@@ -67,25 +111,26 @@ mod imp {
                 .borrow()
                 .last()
                 .and_then(SessionItem::ao12)
-                .map_or(String::default(), |t| t.to_string())
+                .map_or(String::default(), |t| {
+                    t.format_average(self.time_precision.get())
+                })
         }
 
         fn best_solve_string(&self) -> String {
+            let kind = self.kind.get();
             self.solve_list
                 .borrow()
                 .iter()
-                .map(SessionItem::time)
-                .min()
-                .map_or(String::default(), |t| t.to_string())
+                .min_by(|a, b| cmp_solves(kind, a, b))
+                .map_or(String::default(), |item| item.solve_time_string())
         }
 
         fn best_mo3_string(&self) -> String {
             self.solve_list
                 .borrow()
                 .iter()
-                .min_by_key(|item| item.mo3().unwrap_or(SolveTime::DNF))
-                .and_then(SessionItem::mo3)
-                .map_or(String::default(), |t| t.to_string())
+                .min_by_key(|item| mo3_key(item))
+                .map_or(String::default(), |item| item.mo3_string())
         }
 
         fn best_ao5_string(&self) -> String {
@@ -94,7 +139,9 @@ mod imp {
                 .iter()
                 .min_by_key(|item| item.ao5().unwrap_or(SolveTime::DNF))
                 .and_then(SessionItem::ao5)
-                .map_or(String::default(), |t| t.to_string())
+                .map_or(String::default(), |t| {
+                    t.format_average(self.time_precision.get())
+                })
         }
 
         fn best_ao12_string(&self) -> String {
@@ -103,7 +150,72 @@ mod imp {
                 .iter()
                 .min_by_key(|item| item.ao12().unwrap_or(SolveTime::DNF))
                 .and_then(SessionItem::ao12)
-                .map_or(String::default(), |t| t.to_string())
+                .map_or(String::default(), |t| {
+                    t.format_average(self.time_precision.get())
+                })
+        }
+
+        fn last_bo3_string(&self) -> String {
+            let list = self.solve_list.borrow();
+            if list.len() < 3 {
+                return String::default();
+            }
+            list[list.len() - 3..]
+                .best_of_n()
+                .map_or(String::default(), |t| t.format(self.time_precision.get()))
+        }
+
+        fn success_rate_string(&self) -> String {
+            let list = self.solve_list.borrow();
+            list.success_rate().map_or(String::default(), |rate| {
+                let n_success = (rate * list.len() as f64).round() as usize;
+                format!("{}/{} ({:.1}%)", n_success, list.len(), rate * 100.0)
+            })
+        }
+
+        fn mean_of_successes_string(&self) -> String {
+            self.solve_list
+                .borrow()
+                .mean_of_successes()
+                .map_or(String::default(), |t| {
+                    t.format_average(self.time_precision.get())
+                })
+        }
+
+        fn bpa_string(&self) -> String {
+            self.obj()
+                .possible_averages(5)
+                .map_or(String::default(), |(best, _)| {
+                    best.format_average(self.time_precision.get())
+                })
+        }
+
+        fn wpa_string(&self) -> String {
+            self.obj()
+                .possible_averages(5)
+                .map_or(String::default(), |(_, worst)| {
+                    worst.format_average(self.time_precision.get())
+                })
+        }
+
+        fn ao5_needed_string(&self) -> String {
+            self.obj()
+                .time_needed_for_best_average(5)
+                .map_or(String::default(), |t| t.format(self.time_precision.get()))
+        }
+
+        fn ao12_needed_string(&self) -> String {
+            self.obj()
+                .time_needed_for_best_average(12)
+                .map_or(String::default(), |t| t.format(self.time_precision.get()))
+        }
+
+        fn today_summary_string(&self) -> String {
+            self.obj()
+                .today_summary()
+                .map_or(String::default(), |summary| {
+                    summary.description(self.time_precision.get())
+                })
         }
     }
 
@@ -137,6 +249,18 @@ mod imp {
                     Signal::builder("new-best-ao12")
                         .param_types(Vec::<SignalType>::new())
                         .build(),
+                    Signal::builder("pb-history-changed")
+                        .param_types(Vec::<SignalType>::new())
+                        .build(),
+                    Signal::builder("tags-changed")
+                        .param_types(Vec::<SignalType>::new())
+                        .build(),
+                    Signal::builder("goals-changed")
+                        .param_types(Vec::<SignalType>::new())
+                        .build(),
+                    Signal::builder("goal-met")
+                        .param_types([String::static_type()])
+                        .build(),
                 ]
             });
             SIGNALS.as_ref()
@@ -174,6 +298,14 @@ impl Session {
         glib::Object::builder().build()
     }
 
+    /// Creates a new session of this kind.
+    pub fn with_kind(name: &str, kind: SessionKind) -> Self {
+        glib::Object::builder()
+            .property("name", name)
+            .property("kind", kind)
+            .build()
+    }
+
     /// Gets the nth solve of this session.
     pub fn get_solve(&self, index: usize) -> Option<SessionItem> {
         self.imp().solve_list.borrow().get(index).cloned()
@@ -203,10 +335,9 @@ impl Session {
     /// Adds a solve to this session.
     pub fn add_solve(&self, solve: SolveData) -> SessionItem {
         let item = SessionItem::new(solve);
-        let handler =
-            item.connect_solve_time_string_notify(glib::clone!(@weak self as obj => move |solve| {
-                obj.solve_updated_by_object(solve);
-            }));
+        item.set_index(self.n_items() as usize);
+        item.set_time_precision(self.time_precision());
+        let handler = self.connect_item(&item);
         self.imp().solve_list.borrow_mut().push(item.clone());
         self.imp().handler_list.borrow_mut().push(handler);
 
@@ -215,9 +346,154 @@ impl Session {
         self.solve_updated(index as usize);
         self.emit_by_name::<()>("solve-added", &[]);
         self.notify_new_best_times();
+        self.check_goals();
         item
     }
 
+    /// Adds solves to this session, e.g. when they are moved from another
+    /// session. The solves are placed among the others in the order they
+    /// were done.
+    pub fn add_solves(&self, solves: Vec<SolveData>) {
+        let imp = self.imp();
+        if solves.is_empty() {
+            return;
+        }
+        let old_len = self.n_items() as usize;
+
+        {
+            let mut list = imp.solve_list.borrow_mut();
+            let mut handlers = imp.handler_list.borrow_mut();
+            for solve in solves {
+                let item = SessionItem::new(solve);
+                // Marks the new solves as changed, wherever they end up.
+                item.set_index(usize::MAX);
+                item.set_time_precision(self.time_precision());
+                handlers.push(self.connect_item(&item));
+                list.push(item);
+            }
+
+            let mut entries: Vec<_> = list.drain(..).zip(handlers.drain(..)).collect();
+            entries.sort_by_key(|(item, _)| item.timestamp());
+            for (item, handler) in entries {
+                list.push(item);
+                handlers.push(handler);
+            }
+        }
+
+        let first_changed = imp
+            .solve_list
+            .borrow()
+            .iter()
+            .enumerate()
+            .position(|(i, item)| item.index() != i)
+            .unwrap_or(old_len);
+        self.solves_changed(first_changed, old_len);
+    }
+
+    /// Removes the solves at these indices in one step.
+    pub fn remove_solves(&self, indices: &[usize]) -> Vec<SessionItem> {
+        let imp = self.imp();
+        let old_len = self.n_items() as usize;
+        let mut indices: Vec<usize> = indices.iter().copied().filter(|&i| i < old_len).collect();
+        indices.sort_unstable();
+        indices.dedup();
+        let Some(&first) = indices.first() else {
+            return Vec::new();
+        };
+
+        let mut removed = Vec::with_capacity(indices.len());
+        for &index in indices.iter().rev() {
+            let solve = imp.solve_list.borrow_mut().remove(index);
+            let handler = imp.handler_list.borrow_mut().remove(index);
+            solve.disconnect(handler);
+            removed.push(solve);
+        }
+        removed.reverse();
+
+        self.solves_changed(first, old_len);
+        removed
+    }
+
+    /// Sets the penalty of the solves at these indices in one step.
+    pub fn set_penalties(&self, indices: &[usize], penalty: Penalty) {
+        let imp = self.imp();
+        let len = self.n_items() as usize;
+        let Some(first) = indices.iter().copied().filter(|&i| i < len).min() else {
+            return;
+        };
+
+        {
+            let list = imp.solve_list.borrow();
+            let handlers = imp.handler_list.borrow();
+            for &index in indices.iter().filter(|&&i| i < len) {
+                // Statistics are updated once for every solve below.
+                list[index].block_signal(&handlers[index]);
+                list[index].set_penalty(penalty);
+                list[index].unblock_signal(&handlers[index]);
+            }
+        }
+
+        self.solves_changed(first, len);
+    }
+
+    /// Updates the indices and averages of the solves from `index` after a
+    /// change to the list that had `old_len` solves, notifying the change
+    /// once.
+    fn solves_changed(&self, index: usize, old_len: usize) {
+        let len = self.n_items() as usize;
+        for (i, item) in self
+            .imp()
+            .solve_list
+            .borrow()
+            .iter()
+            .enumerate()
+            .skip(index)
+        {
+            item.set_index(i);
+        }
+        for i in index..len {
+            self.update_mo3(i);
+            self.update_ao5(i);
+            self.update_ao12(i);
+        }
+        self.items_changed(index as u32, (old_len - index) as u32, (len - index) as u32);
+        self.notify_statistics_changed();
+    }
+
+    /// Gets the precision times and statistics of this session are
+    /// displayed with.
+    pub fn time_precision(&self) -> TimePrecision {
+        self.imp().time_precision.get()
+    }
+
+    /// Sets the precision times and statistics of this session are
+    /// displayed with.
+    pub fn set_time_precision(&self, precision: TimePrecision) {
+        let imp = self.imp();
+        if imp.time_precision.replace(precision) == precision {
+            return;
+        }
+
+        {
+            let list = imp.solve_list.borrow();
+            let handlers = imp.handler_list.borrow();
+            for (item, handler) in list.iter().zip(handlers.iter()) {
+                // Only the text of the times changes, not the statistics.
+                item.block_signal(handler);
+                item.set_time_precision(precision);
+                item.unblock_signal(handler);
+            }
+        }
+        self.notify_statistics_changed();
+    }
+
+    /// Updates the statistics when the time of an item changes.
+    fn connect_item(&self, item: &SessionItem) -> glib::SignalHandlerId {
+        item.connect_solve_time_string_notify(glib::clone!(@weak self as obj => move |solve| {
+            obj.solve_updated_by_object(solve);
+        }))
+    }
+
     /// Remove the item at this index in this session.
     pub fn remove_solve(&self, index: usize) -> Option<SessionItem> {
         let imp = self.imp();
@@ -228,6 +504,9 @@ impl Session {
         let solve = imp.solve_list.borrow_mut().remove(index);
         let handler = imp.handler_list.borrow_mut().remove(index);
         solve.disconnect(handler);
+        for (i, item) in imp.solve_list.borrow().iter().enumerate().skip(index) {
+            item.set_index(i);
+        }
 
         self.items_changed(index as u32, 1, 0);
         self.solve_updated(index);
@@ -250,8 +529,8 @@ impl Session {
         self.last_solve().map(|s| s.time())
     }
 
-    /// Gets the last mean of 3 time of this session.
-    pub fn last_mo3(&self) -> Option<SolveTime> {
+    /// Gets the last mean of 3 of this session.
+    pub fn last_mo3(&self) -> Option<SolveResult> {
         self.last_solve().and_then(|s| s.mo3())
     }
 
@@ -267,31 +546,27 @@ impl Session {
 
     /// Gets the best solve item of this session.
     pub fn best_solve(&self) -> Option<SessionItem> {
+        let kind = self.kind();
         self.imp()
             .solve_list
             .borrow()
             .iter()
-            .min_by_key(|item| item.time())
+            .min_by(|a, b| cmp_solves(kind, a, b))
             .cloned()
     }
 
     /// Gets the best solve time of this session.
     pub fn best_solve_time(&self) -> Option<SolveTime> {
-        self.imp()
-            .solve_list
-            .borrow()
-            .iter()
-            .min_by_key(|item| item.time())
-            .map(SessionItem::time)
+        self.best_solve().map(|item| item.time())
     }
 
-    /// Gets the best mean of 3 time of this session.
-    pub fn best_mo3(&self) -> Option<SolveTime> {
+    /// Gets the best mean of 3 of this session.
+    pub fn best_mo3(&self) -> Option<SolveResult> {
         self.imp()
             .solve_list
             .borrow()
             .iter()
-            .min_by_key(|item| item.mo3().unwrap_or(SolveTime::DNF))
+            .min_by_key(|item| mo3_key(item))
             .and_then(SessionItem::mo3)
     }
 
@@ -317,12 +592,13 @@ impl Session {
 
     /// Gets the index of the best solve item of this session.
     pub fn best_solve_index(&self) -> Option<usize> {
+        let kind = self.kind();
         self.imp()
             .solve_list
             .borrow()
             .iter()
             .enumerate()
-            .min_by_key(|&(_, item)| item.time())
+            .min_by(|&(_, a), &(_, b)| cmp_solves(kind, a, b))
             .map(|(i, _)| i)
     }
 
@@ -337,7 +613,7 @@ impl Session {
             .borrow()
             .iter()
             .enumerate()
-            .min_by_key(|&(_, item)| item.mo3().unwrap_or(SolveTime::DNF))
+            .min_by_key(|&(_, item)| mo3_key(item))
             .map(|(i, _)| i)
     }
 
@@ -371,12 +647,70 @@ impl Session {
             .map(|(i, _)| i)
     }
 
+    /// Gets the best average of `n` solves in the session.
+    pub fn best_average(&self, n: usize) -> Option<SolveTime> {
+        match n {
+            5 => self.best_ao5(),
+            12 => self.best_ao12(),
+            _ => {
+                if !self.kind().has_averages() {
+                    return None;
+                }
+                self.imp()
+                    .solve_list
+                    .borrow()
+                    .windows(n)
+                    .filter_map(|solves| solves.average_of_n())
+                    .min()
+            }
+        }
+    }
+
+    /// Gets the solves that the next solves complete an average of `n`
+    /// with: the last `n - 1` solves, or every solve if there are fewer.
+    fn pending_average_solves(&self, n: usize) -> Vec<SolveTime> {
+        let list = self.imp().solve_list.borrow();
+        let start = list.len().saturating_sub(n.saturating_sub(1));
+        list[start..].iter().map(SessionItem::time).collect()
+    }
+
+    /// Gets the best and worst possible averages of `n` that the next solve
+    /// can reach, once it is the last solve of the average.
+    pub fn possible_averages(&self, n: usize) -> Option<(SolveTime, SolveTime)> {
+        if !self.kind().has_averages() {
+            return None;
+        }
+        let solves = self.pending_average_solves(n);
+        Some((
+            solves.best_possible_average(n)?,
+            solves.worst_possible_average(n)?,
+        ))
+    }
+
+    /// Gets the time the next solve needs for a new best average of `n`.
+    pub fn time_needed_for_best_average(&self, n: usize) -> Option<TimeNeeded> {
+        let best = self.best_average(n)?;
+        let solves = self.pending_average_solves(n);
+        if solves.len() + 1 < n {
+            return None;
+        }
+        solves.time_needed_for_average(best)
+    }
+
     /// Computes the mean of 3 of the solve at this index.
-    fn compute_mo3(&self, index: usize) -> Option<SolveTime> {
+    fn compute_mo3(&self, index: usize) -> Option<SolveResult> {
+        if !self.kind().has_mean_of_3() {
+            return None;
+        }
+
         let list = self.imp().solve_list.borrow();
         if index + 1 >= 3 {
-            list.get(index - 2..index + 1)
-                .and_then(|solves| solves.mean_of_n())
+            let results: Vec<SolveResult> = list
+                .get(index - 2..index + 1)?
+                .iter()
+                .map(SessionItem::result)
+                .collect();
+            SolveResult::mean_of_3(&results)
         } else {
             None
         }
@@ -384,6 +718,10 @@ impl Session {
 
     /// Computes the average of 5 of the solve at this index.
     fn compute_ao5(&self, index: usize) -> Option<SolveTime> {
+        if !self.kind().has_averages() {
+            return None;
+        }
+
         let list = self.imp().solve_list.borrow();
         if index + 1 >= 5 {
             list.get(index - 4..index + 1)
@@ -395,6 +733,10 @@ impl Session {
 
     /// Computes the average of 12 the solve at this index.
     fn compute_ao12(&self, index: usize) -> Option<SolveTime> {
+        if !self.kind().has_averages() {
+            return None;
+        }
+
         let list = self.imp().solve_list.borrow();
         if index + 1 >= 12 {
             list.get(index - 11..index + 1)
@@ -465,6 +807,290 @@ impl Session {
         self.notify_best_mo3_string();
         self.notify_best_ao5_string();
         self.notify_last_ao12_string();
+        self.notify_last_bo3_string();
+        self.notify_success_rate_string();
+        self.notify_mean_of_successes_string();
+        self.notify_bpa_string();
+        self.notify_wpa_string();
+        self.notify_ao5_needed_string();
+        self.notify_ao12_needed_string();
+        self.notify_today_summary_string();
+        self.update_pb_history();
+    }
+
+    /// Gets the time of every solve with the local date it was done on.
+    fn dated_solves(&self) -> Vec<(chrono::NaiveDate, SolveTime)> {
+        self.imp()
+            .solve_list
+            .borrow()
+            .iter()
+            .map(|item| (local_date(item.timestamp()), item.time()))
+            .collect()
+    }
+
+    /// Gets the times of the solves done between these days, inclusive, in
+    /// the order they were done. Days that are not set match every solve.
+    pub fn times_between(
+        &self,
+        from: Option<chrono::NaiveDate>,
+        to: Option<chrono::NaiveDate>,
+    ) -> Vec<SolveTime> {
+        self.dated_solves()
+            .into_iter()
+            .filter(|(date, _)| {
+                !(from.is_some_and(|from| *date < from) || to.is_some_and(|to| *date > to))
+            })
+            .map(|(_, time)| time)
+            .collect()
+    }
+
+    /// Gets the local time each solve ended at, with its measured time, to
+    /// account for the time spent practicing.
+    pub fn practice_solves(&self) -> Vec<(chrono::NaiveDateTime, std::time::Duration)> {
+        self.imp()
+            .solve_list
+            .borrow()
+            .iter()
+            .map(|item| {
+                let end = chrono::DateTime::<chrono::Local>::from(item.timestamp());
+                (end.naive_local(), item.time().measured_time())
+            })
+            .collect()
+    }
+
+    /// Summarizes the solves of each day, week or month, oldest first.
+    pub fn period_summaries(&self, period: DatePeriod) -> Vec<PeriodSummary> {
+        summarize_by_period(&self.dated_solves(), period)
+    }
+
+    /// Summarizes the solves done today, if any.
+    pub fn today_summary(&self) -> Option<PeriodSummary> {
+        summarize_day(&self.dated_solves(), chrono::Local::now().date_naive())
+    }
+
+    /// Counts the solves done on each day.
+    pub fn solves_per_day(&self) -> BTreeMap<chrono::NaiveDate, usize> {
+        self.period_summaries(DatePeriod::Day)
+            .iter()
+            .map(|summary| (summary.start, summary.n_solves))
+            .collect()
+    }
+
+    /// Sets the tags of the solve at this index.
+    pub fn set_tags(&self, index: usize, tags: BTreeSet<String>) {
+        let Some(item) = self.get_solve(index) else {
+            return;
+        };
+        item.set_tags(tags);
+        self.emit_by_name::<()>("tags-changed", &[]);
+    }
+
+    /// Calculates the statistics of the solves with each tag.
+    pub fn tag_statistics(&self) -> Vec<TagStatistics> {
+        let solves: Vec<_> = self
+            .imp()
+            .solve_list
+            .borrow()
+            .iter()
+            .map(|item| (item.tags(), item.time()))
+            .collect();
+        tag_statistics(&solves)
+    }
+
+    /// Gets the practice targets of the session.
+    pub fn goals(&self) -> Vec<Goal> {
+        self.imp().goals.borrow().clone()
+    }
+
+    /// Sets the practice targets of the session. Goals that are already met
+    /// are not celebrated.
+    pub fn set_goals(&self, goals: Vec<Goal>) {
+        let imp = self.imp();
+        imp.goals.replace(goals);
+        let met = self.goal_progress().iter().map(|(_, p)| p.met).collect();
+        imp.goals_met.replace(met);
+        self.emit_by_name::<()>("goals-changed", &[]);
+    }
+
+    /// Adds a practice target to the session, unless it already has it.
+    pub fn add_goal(&self, goal: Goal) {
+        let mut goals = self.goals();
+        if !goals.contains(&goal) {
+            goals.push(goal);
+            self.set_goals(goals);
+        }
+    }
+
+    /// Removes the practice target at this index.
+    pub fn remove_goal(&self, index: usize) {
+        let mut goals = self.goals();
+        if index < goals.len() {
+            goals.remove(index);
+            self.set_goals(goals);
+        }
+    }
+
+    /// Evaluates the progress towards each goal of the session.
+    pub fn goal_progress(&self) -> Vec<(Goal, GoalProgress)> {
+        let times: Vec<SolveTime> = self
+            .imp()
+            .solve_list
+            .borrow()
+            .iter()
+            .map(|item| item.time())
+            .collect();
+        let solves_today = self.today_summary().map_or(0, |summary| summary.n_solves);
+        self.imp()
+            .goals
+            .borrow()
+            .iter()
+            .map(|goal| {
+                (
+                    *goal,
+                    goal.progress(&times, solves_today, self.time_precision()),
+                )
+            })
+            .collect()
+    }
+
+    /// Emits `goal-met` for each goal met since solves last changed.
+    fn check_goals(&self) {
+        let progress = self.goal_progress();
+        let was_met = self
+            .imp()
+            .goals_met
+            .replace(progress.iter().map(|(_, p)| p.met).collect());
+        for (i, (goal, progress)) in progress.iter().enumerate() {
+            if progress.met && !was_met.get(i).copied().unwrap_or(false) {
+                self.emit_by_name::<()>("goal-met", &[&goal.label()]);
+            }
+        }
+    }
+
+    /// Gets every improvement of a personal best in the session, in the
+    /// order they happened.
+    pub fn pb_history(&self) -> Vec<PersonalBest> {
+        self.imp().pb_history.borrow().clone()
+    }
+
+    /// Recomputes the personal best history, e.g. after a solve is removed
+    /// or its penalty changes.
+    fn update_pb_history(&self) {
+        let results: Vec<SolveResult> = self
+            .imp()
+            .solve_list
+            .borrow()
+            .iter()
+            .map(SessionItem::result)
+            .collect();
+
+        let history = pb_history(&results, PbStatistic::for_kind(self.kind()));
+        if *self.imp().pb_history.borrow() != history {
+            self.imp().pb_history.replace(history);
+            self.emit_by_name::<()>("pb-history-changed", &[]);
+        }
+    }
+
+    /// Formats a personal best of this statistic with the precision of the
+    /// session.
+    pub fn format_personal_best(&self, statistic: PbStatistic, result: &SolveResult) -> String {
+        statistic.format(result, self.time_precision())
+    }
+
+    /// Formats the personal best history as CSV, with a header line.
+    pub fn pb_history_csv(&self) -> String {
+        let mut lines = vec!["Statistic,Old,New,Date,Solve".to_string()];
+        for pb in self.pb_history() {
+            let date = self
+                .get_solve(pb.index)
+                .map_or(String::default(), |item| item.timestamp_string());
+            let old = pb.old.map_or(String::default(), |old| {
+                self.format_personal_best(pb.statistic, &old)
+            });
+            lines.push(format!(
+                "{},{},{},{},{}",
+                pb.statistic,
+                old,
+                self.format_personal_best(pb.statistic, &pb.new),
+                date,
+                pb.index + 1
+            ));
+        }
+        lines.join("\n")
+    }
+
+    /// Creates a report of the solves at these indices.
+    pub fn solves_report(&self, indices: &[usize]) -> Report {
+        let list = self.imp().solve_list.borrow();
+        let solves = indices
+            .iter()
+            .filter_map(|&i| list.get(i))
+            .map(Into::into)
+            .collect();
+        Report::of_solves(
+            &format!("{} · Solves", self.name()),
+            solves,
+            self.time_precision(),
+        )
+    }
+
+    /// Creates a report of the average of `n` solves ending at this index.
+    pub fn average_report(&self, index: usize, n: usize) -> Option<Report> {
+        let solves = self.get_solve_slice(index, n)?;
+        Some(Report::of_solves(
+            &format!("{} · Average of {}", self.name(), n),
+            solves.iter().map(Into::into).collect(),
+            self.time_precision(),
+        ))
+    }
+
+    /// Creates a report of the statistics of this session, without the
+    /// solves.
+    pub fn summary_report(&self) -> Report {
+        let kind = self.kind();
+        let mut statistics = vec![
+            ("Solves", self.n_items().to_string()),
+            ("Best", self.best_solve_string()),
+            ("Mean", self.mean_of_successes_string()),
+            ("Success Rate", self.success_rate_string()),
+        ];
+        if kind.has_mean_of_3() {
+            statistics.push(("Best Mo3", self.best_mo3_string()));
+        }
+        if kind.has_averages() {
+            statistics.extend([
+                ("Best Ao5", self.best_ao5_string()),
+                ("Best Ao12", self.best_ao12_string()),
+                ("Current Ao5", self.last_ao5_string()),
+                ("Current Ao12", self.last_ao12_string()),
+            ]);
+        }
+
+        Report::summary(
+            &self.name(),
+            statistics
+                .into_iter()
+                .filter(|(_, value)| !value.is_empty())
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        )
+    }
+
+    /// Creates a printable report of this session, with every solve.
+    pub fn session_report(&self) -> SessionReport {
+        SessionReport {
+            title: self.name(),
+            statistics: self.summary_report().statistics,
+            personal_bests: self.pb_history(),
+            solves: self
+                .imp()
+                .solve_list
+                .borrow()
+                .iter()
+                .map(Into::into)
+                .collect(),
+            precision: self.time_precision(),
+        }
     }
 
     /// Notifies a new best time.
@@ -489,6 +1115,28 @@ impl Session {
     }
 }
 
+/// Compares two solves by their ranking in a session of this kind.
+fn cmp_solves(kind: SessionKind, a: &SessionItem, b: &SessionItem) -> Ordering {
+    match kind {
+        SessionKind::MultiBlind => a.time().is_dnf().cmp(&b.time().is_dnf()).then_with(|| {
+            match (a.multi_blind(), b.multi_blind()) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => a.time().cmp(&b.time()),
+            }
+        }),
+        _ => a.result().cmp(&b.result()),
+    }
+}
+
+/// Gets the key to find the best mean of 3 by. Solves without a mean of 3
+/// rank with the DNF ones.
+fn mo3_key(item: &SessionItem) -> (bool, Option<SolveResult>) {
+    let mo3 = item.mo3();
+    (mo3.is_none_or(|mo3| mo3.is_dnf()), mo3)
+}
+
 impl Default for Session {
     fn default() -> Self {
         Self::new()
@@ -497,18 +1145,22 @@ impl Default for Session {
 
 #[cfg(test)]
 mod tests {
-    use std::time::{Duration, SystemTime};
+    use std::time::Duration;
 
-    use crate::data::Penalty;
+    use crate::data::{FewestMovesResult, MultiBlindResult, Penalty, ReportFormat};
 
     use super::*;
 
     fn add_dummy_solve(session: &Session, solve_time: SolveTime) {
-        session.add_solve(SolveData {
-            time: solve_time,
-            timestamp: SystemTime::now(),
-            scramble: String::default(),
-        });
+        session.add_solve(SolveData::new(solve_time, String::default()));
+    }
+
+    /// Gets the time of a result that should be one.
+    fn time(result: Option<SolveResult>) -> SolveTime {
+        match result {
+            Some(SolveResult::Time(time)) => time,
+            result => panic!("Expected a time, got {:?}", result),
+        }
     }
 
     fn build_test_session() -> Session {
@@ -582,7 +1234,7 @@ mod tests {
         let session = build_test_session();
         let last_solve = session.last_solve().unwrap();
 
-        let last_mo3 = last_solve.mo3().unwrap();
+        let last_mo3 = time(last_solve.mo3());
 
         assert!(last_mo3.eq_aprrox(
             &SolveTime::new(Duration::from_millis(14_410), Penalty::Ok),
@@ -632,7 +1284,7 @@ mod tests {
     fn verify_best_mo3() {
         let session = build_test_session();
 
-        let best_mo3 = session.best_mo3().unwrap();
+        let best_mo3 = time(session.best_mo3());
 
         assert!(best_mo3.eq_aprrox(
             &SolveTime::new(Duration::from_millis(12_860), Penalty::Ok),
@@ -689,7 +1341,7 @@ mod tests {
         let session = build_and_modify_test_session();
         let last_solve = session.last_solve().unwrap();
 
-        let last_mo3 = last_solve.mo3().unwrap();
+        let last_mo3 = time(last_solve.mo3());
 
         assert!(last_mo3.eq_aprrox(
             &SolveTime::new(Duration::from_millis(14_410), Penalty::Ok),
@@ -739,7 +1391,7 @@ mod tests {
     fn verify_best_mo3_after_modification() {
         let session = build_and_modify_test_session();
 
-        let best_mo3 = session.best_mo3().unwrap();
+        let best_mo3 = time(session.best_mo3());
 
         assert!(best_mo3.eq_aprrox(
             &SolveTime::new(Duration::from_millis(13_420), Penalty::Ok),
@@ -795,7 +1447,7 @@ mod tests {
         let session = build_and_remove_from_test_session();
         let last_solve = session.last_solve().unwrap();
 
-        let last_mo3 = last_solve.mo3().unwrap();
+        let last_mo3 = time(last_solve.mo3());
 
         assert!(last_mo3.eq_aprrox(
             &SolveTime::new(Duration::from_millis(14_410), Penalty::Ok),
@@ -842,7 +1494,7 @@ mod tests {
     fn verify_best_mo3_after_remove_from() {
         let session = build_and_remove_from_test_session();
 
-        let best_mo3 = session.best_mo3().unwrap();
+        let best_mo3 = time(session.best_mo3());
 
         assert!(best_mo3.eq_aprrox(
             &SolveTime::new(Duration::from_millis(13_420), Penalty::Ok),
@@ -870,4 +1522,184 @@ mod tests {
 
         assert!(best_ao12.is_none())
     }
+
+    #[test]
+    fn verify_success_rate_after_modification() {
+        let session = build_and_modify_test_session();
+
+        assert_eq!(session.success_rate_string(), "11/12 (91.7%)");
+    }
+
+    #[test]
+    fn verify_mean_of_successes_after_modification() {
+        let session = build_and_modify_test_session();
+
+        let mean = session
+            .imp()
+            .solve_list
+            .borrow()
+            .mean_of_successes()
+            .unwrap();
+
+        assert!(mean.eq_aprrox(
+            &SolveTime::new(Duration::from_millis(14_150), Penalty::Ok),
+            10
+        ));
+    }
+
+    fn add_dummy_multi_blind(session: &Session, attempted: u32, solved: u32, secs: u64) {
+        let result = MultiBlindResult::new(attempted, solved, Duration::from_secs(secs)).unwrap();
+        session.add_solve(SolveData::from_multi_blind(result, String::default()));
+    }
+
+    #[test]
+    fn verify_best_multi_blind() {
+        let session = Session::with_kind("Multi-Blind", SessionKind::MultiBlind);
+        add_dummy_multi_blind(&session, 4, 3, 2_100);
+        add_dummy_multi_blind(&session, 6, 5, 3_500);
+        add_dummy_multi_blind(&session, 2, 1, 500);
+        add_dummy_multi_blind(&session, 5, 4, 2_900);
+
+        assert_eq!(session.best_solve_index(), Some(1));
+        assert_eq!(session.best_solve_string(), "5/6 58:20");
+        assert!(session.last_solve().unwrap().mo3().is_none());
+    }
+
+    fn add_dummy_fewest_moves(session: &Session, move_count: Option<u32>) {
+        let result = FewestMovesResult {
+            solution: String::default(),
+            move_count,
+        };
+        session.add_solve(SolveData::from_fewest_moves(
+            result,
+            Duration::from_secs(45 * 60),
+            String::default(),
+        ));
+    }
+
+    #[test]
+    fn verify_fewest_moves_statistics() {
+        let session = Session::with_kind("Fewest Moves", SessionKind::FewestMoves);
+        add_dummy_fewest_moves(&session, Some(31));
+        add_dummy_fewest_moves(&session, Some(28));
+        add_dummy_fewest_moves(&session, Some(30));
+        add_dummy_fewest_moves(&session, None);
+
+        assert_eq!(session.best_solve_index(), Some(1));
+        assert_eq!(session.best_solve_string(), "28");
+        assert_eq!(session.best_mo3_string(), "29.67");
+        assert_eq!(session.last_mo3_string(), "DNF");
+        assert!(session.last_solve().unwrap().ao5().is_none());
+
+        let csv = session.pb_history_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].starts_with("Single,,31,"));
+        assert!(lines[2].starts_with("Single,31,28,"));
+        assert!(lines[3].starts_with("Mo3,,29.67,"));
+    }
+
+    #[test]
+    fn verify_possible_averages() {
+        let session = build_test_session();
+        assert_eq!(session.bpa_string(), "13.44");
+        assert_eq!(session.wpa_string(), "14.41");
+        assert_eq!(session.ao5_needed_string(), "Not possible");
+        assert_eq!(session.ao12_needed_string(), "< 13.44");
+    }
+
+    #[test]
+    fn verify_pb_history() {
+        let session = build_test_session();
+        let singles = |session: &Session| -> Vec<usize> {
+            session
+                .pb_history()
+                .iter()
+                .filter(|pb| pb.statistic == PbStatistic::Single)
+                .map(|pb| pb.index)
+                .collect()
+        };
+        assert_eq!(singles(&session), vec![0, 3, 8]);
+
+        session.remove_solve(8);
+        assert_eq!(singles(&session), vec![0, 3]);
+
+        session.get_solve(3).unwrap().set_penalty(Penalty::Dnf);
+        assert_eq!(singles(&session), vec![0, 4, 7]);
+
+        let csv = session.pb_history_csv();
+        assert!(csv.starts_with("Statistic,Old,New,Date,Solve\nSingle,,13.44,"));
+    }
+
+    #[test]
+    fn verify_today_summary() {
+        let session = build_test_session();
+
+        let today = session.today_summary().unwrap();
+        assert_eq!(today.n_solves, 12);
+        assert!(today.best.unwrap().eq_aprrox(
+            &SolveTime::new(Duration::from_millis(12_110), Penalty::Ok),
+            10
+        ));
+        assert_eq!(session.period_summaries(DatePeriod::Month).len(), 1);
+        assert!(session.today_summary_string().starts_with("12 solves · "));
+    }
+
+    #[test]
+    fn verify_bulk_operations() {
+        let session = build_test_session();
+
+        session.set_penalties(&[8, 3], Penalty::Dnf);
+        assert!(session.get_solve(8).unwrap().time().is_dnf());
+        assert!(session.best_solve_time().unwrap().eq_aprrox(
+            &SolveTime::new(Duration::from_millis(12_940), Penalty::Ok),
+            10
+        ));
+
+        let removed = session.remove_solves(&[11, 0, 5]);
+        assert_eq!(removed.len(), 3);
+        assert_eq!(session.n_items(), 9);
+        assert_eq!(session.get_solve(5).unwrap().index(), 5);
+        assert!(session.last_solve().unwrap().ao5().is_some());
+
+        let other = Session::new();
+        other.add_solves(removed.iter().map(SessionItem::solve_data).collect());
+        assert_eq!(other.n_items(), 3);
+        assert!(other.get_solve(0).unwrap().time().eq_aprrox(
+            &SolveTime::new(Duration::from_millis(13_440), Penalty::Ok),
+            10
+        ));
+
+        let report = other.solves_report(&[0, 1]);
+        assert_eq!(report.solves.len(), 2);
+        assert_eq!(report.solves[0].number, 1);
+        assert_eq!(
+            report.statistics[0],
+            ("Mean".to_string(), "14.04".to_string())
+        );
+    }
+
+    #[test]
+    fn verify_time_precision() {
+        let session = Session::new();
+        add_dummy_solve(
+            &session,
+            SolveTime::new(Duration::from_millis(12_345), Penalty::Ok),
+        );
+        assert_eq!(session.last_solve_string(), "12.34");
+
+        session.set_time_precision(TimePrecision::Milliseconds);
+        assert_eq!(session.last_solve_string(), "12.345");
+        add_dummy_solve(
+            &session,
+            SolveTime::new(Duration::from_millis(9_876), Penalty::Plus2),
+        );
+        assert_eq!(session.last_solve().unwrap().solve_time_string(), "11.876+");
+        let report = session.solves_report(&[0]);
+        assert_eq!(
+            report.statistics[0],
+            ("Mean".to_string(), "12.345".to_string())
+        );
+        assert!(report.to_text(ReportFormat::Plain).ends_with("1. 12.345"));
+    }
 }
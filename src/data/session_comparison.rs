@@ -0,0 +1,153 @@
+use std::time::Duration;
+
+use crate::data::{
+    mann_whitney_u, MannWhitneyU, Penalty, SolveStatistic, SolveTime, TimePrecision,
+};
+
+/// Statistics of one of the sets of solves compared.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComparisonSide {
+    pub count: usize,
+    pub n_dnf: usize,
+    /// Mean of the solves that are not DNF.
+    pub mean: Option<SolveTime>,
+    pub median: Option<SolveTime>,
+    /// Standard deviation of the solves that are not DNF.
+    pub standard_deviation: Option<Duration>,
+    pub best_ao5: Option<SolveTime>,
+    pub best_ao12: Option<SolveTime>,
+}
+
+impl ComparisonSide {
+    /// Calculates the statistics of these times, in the order they were
+    /// done.
+    pub fn new(times: &[SolveTime]) -> Self {
+        Self {
+            count: times.len(),
+            n_dnf: times.iter().filter(|time| time.is_dnf()).count(),
+            mean: times.mean_of_successes(),
+            median: times.median(),
+            standard_deviation: times.standard_deviation(),
+            best_ao5: times.best_average_of(5),
+            best_ao12: times.best_average_of(12),
+        }
+    }
+}
+
+/// A comparison of two sets of solves, such as two sessions or two date
+/// ranges of a session.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SessionComparison {
+    pub first: ComparisonSide,
+    pub second: ComparisonSide,
+    /// Whether the difference between the sets is likely to be real.
+    pub test: Option<MannWhitneyU>,
+}
+
+impl SessionComparison {
+    /// Compares two sets of times, each in the order they were done.
+    pub fn new(first: &[SolveTime], second: &[SolveTime]) -> Self {
+        Self {
+            first: ComparisonSide::new(first),
+            second: ComparisonSide::new(second),
+            test: mann_whitney_u(first, second),
+        }
+    }
+
+    /// Gets the name of each statistic with its value for both sets written
+    /// with this precision, e.g. `("Median", "12.34", "11.90")`. Missing
+    /// values are shown as `-`.
+    pub fn rows(&self, precision: TimePrecision) -> Vec<(&'static str, String, String)> {
+        let time =
+            |time: Option<SolveTime>| time.map_or("-".to_string(), |t| t.format_average(precision));
+        let duration = |duration: Option<Duration>| {
+            time(duration.map(|duration| SolveTime::new(duration, Penalty::Ok)))
+        };
+        let [first, second] = [self.first, self.second];
+        vec![
+            ("Solves", first.count.to_string(), second.count.to_string()),
+            ("DNF", first.n_dnf.to_string(), second.n_dnf.to_string()),
+            ("Mean", time(first.mean), time(second.mean)),
+            ("Median", time(first.median), time(second.median)),
+            (
+                "Standard Deviation",
+                duration(first.standard_deviation),
+                duration(second.standard_deviation),
+            ),
+            ("Best Ao5", time(first.best_ao5), time(second.best_ao5)),
+            ("Best Ao12", time(first.best_ao12), time(second.best_ao12)),
+        ]
+    }
+
+    /// Describes the result of the significance test, e.g. `First is faster
+    /// (p = 0.012)`.
+    pub fn describe_test(&self) -> String {
+        let Some(test) = self.test else {
+            return "Not enough solves to compare".to_string();
+        };
+        let p_value = if test.p_value < 0.001 {
+            "p < 0.001".to_string()
+        } else {
+            format!("p = {:.3}", test.p_value)
+        };
+        if !test.is_significant() {
+            return format!("No significant difference ({})", p_value);
+        }
+        let faster = if test.first_faster > 0.5 {
+            "First"
+        } else {
+            "Second"
+        };
+        format!("{} is faster ({})", faster, p_value)
+    }
+
+    /// Describes how often a solve of the first set beats one of the
+    /// second, e.g. `A first solve beats a second one 80% of the time`.
+    pub fn describe_odds(&self) -> Option<String> {
+        self.test.map(|test| {
+            format!(
+                "A first solve beats a second one {:.0}% of the time",
+                test.first_faster * 100.0
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok(millis: u64) -> SolveTime {
+        SolveTime::new(Duration::from_millis(millis), Penalty::Ok)
+    }
+
+    #[test]
+    fn compare_sets() {
+        let old: Vec<SolveTime> = (0..12).map(|i| ok(15_000 + i * 100)).collect();
+        let mut new: Vec<SolveTime> = (0..11).map(|i| ok(12_000 + i * 100)).collect();
+        new.push(SolveTime::DNF);
+
+        let comparison = SessionComparison::new(&old, &new);
+        assert_eq!(comparison.second.count, 12);
+        assert_eq!(comparison.second.n_dnf, 1);
+        assert_eq!(comparison.second.mean, Some(ok(12_500)));
+        assert_eq!(comparison.second.best_ao12, Some(ok(12_550)));
+        assert_eq!(comparison.describe_test(), "Second is faster (p < 0.001)");
+        assert_eq!(
+            comparison.describe_odds().as_deref(),
+            Some("A first solve beats a second one 8% of the time")
+        );
+
+        let rows = comparison.rows(TimePrecision::Centiseconds);
+        assert_eq!(rows[1], ("DNF", "0".to_string(), "1".to_string()));
+        assert_eq!(
+            rows[3],
+            ("Median", "15.55".to_string(), "12.55".to_string())
+        );
+
+        let empty = SessionComparison::new(&old, &[]);
+        assert_eq!(empty.second.mean, None);
+        assert_eq!(empty.rows(TimePrecision::Centiseconds)[2].2, "-");
+        assert_eq!(empty.describe_test(), "Not enough solves to compare");
+    }
+}
@@ -1,10 +1,15 @@
-use std::time::SystemTime;
+use std::collections::BTreeSet;
+use std::time::{Duration, SystemTime};
 
 use gtk::glib;
 use gtk::prelude::*;
 use gtk::subclass::prelude::*;
 
-use crate::data::{Penalty, SolveData, SolveTime};
+use crate::data::{
+    format_puzzle_times, format_relay_scrambles, format_tags, puzzle_times, FewestMovesResult,
+    MoveCount, MultiBlindResult, Penalty, ReportSolve, SolveData, SolveResult, SolveTime,
+    TimePrecision,
+};
 
 const EXPECT_INITIALIZED: &str = "`SolveData` haven't yet initialized in `SessionItem`";
 
@@ -21,35 +26,53 @@ mod imp {
         #[property(name = "solve-time-string", type = String, get = Self::get_recorded_time_string)]
         #[property(name = "penalty", type = Penalty, get = Self::get_penalty, set = Self::set_penalty, builder(Penalty::default()))]
         #[property(name = "timestamp-string", type = String, get = Self::get_timestamp_string)]
+        #[property(name = "memo-string", type = String, get = Self::get_memo_string)]
+        #[property(name = "puzzle-times-string", type = String, get = Self::get_puzzle_times_string)]
+        #[property(name = "scramble", type = String, get = Self::get_scramble)]
+        #[property(name = "solution-string", type = String, get = Self::get_solution_string)]
+        #[property(name = "comment", type = String, get = Self::get_comment, set = Self::set_comment)]
+        #[property(name = "tags-string", type = String, get = Self::get_tags_string)]
         pub solve: RefCell<Option<SolveData>>,
         #[property(name = "mo3-string", type = String, get = Self::get_mo3_string)]
-        pub mo3: Cell<Option<SolveTime>>,
+        pub mo3: Cell<Option<SolveResult>>,
         #[property(name = "ao5-string", type = String, get = Self::get_ao5_string)]
         pub ao5: Cell<Option<SolveTime>>,
         #[property(name = "ao12-string", type = String, get = Self::get_ao12_string)]
         pub ao12: Cell<Option<SolveTime>>,
+        /// Position of the item in its session, kept up to date by the
+        /// session.
+        pub index: Cell<usize>,
+        /// Precision times are displayed with, set by the session.
+        pub time_precision: Cell<TimePrecision>,
     }
 
     impl SessionItem {
         fn get_recorded_time_string(&self) -> String {
-            self.solve
-                .borrow()
-                .as_ref()
-                .expect(EXPECT_INITIALIZED)
-                .time
-                .to_string()
+            let solve = self.solve.borrow();
+            let solve = solve.as_ref().expect(EXPECT_INITIALIZED);
+            match (&solve.multi_blind, &solve.fewest_moves) {
+                (Some(result), _) if !solve.time.is_dnf() || result.is_dnf() => result.to_string(),
+                (_, Some(result)) if !solve.time.is_dnf() => result.to_string(),
+                _ => solve.time.format(self.time_precision.get()),
+            }
         }
 
         fn get_mo3_string(&self) -> String {
-            self.mo3.get().map_or(String::default(), |t| t.to_string())
+            self.mo3.get().map_or(String::default(), |t| {
+                t.format_average(self.time_precision.get())
+            })
         }
 
         fn get_ao5_string(&self) -> String {
-            self.ao5.get().map_or(String::default(), |t| t.to_string())
+            self.ao5.get().map_or(String::default(), |t| {
+                t.format_average(self.time_precision.get())
+            })
         }
 
         fn get_ao12_string(&self) -> String {
-            self.ao12.get().map_or(String::default(), |t| t.to_string())
+            self.ao12.get().map_or(String::default(), |t| {
+                t.format_average(self.time_precision.get())
+            })
         }
 
         fn get_penalty(&self) -> Penalty {
@@ -71,6 +94,68 @@ mod imp {
             self.obj().notify_solve_time_string();
         }
 
+        fn get_memo_string(&self) -> String {
+            let solve = self.solve.borrow();
+            let solve = solve.as_ref().expect(EXPECT_INITIALIZED);
+            if !solve.relay_scrambles.is_empty() {
+                return String::default();
+            }
+            solve.splits.first().map_or(String::default(), |&memo| {
+                SolveTime::new(memo, Penalty::Ok).format(self.time_precision.get())
+            })
+        }
+
+        fn get_puzzle_times_string(&self) -> String {
+            let solve = self.solve.borrow();
+            let solve = solve.as_ref().expect(EXPECT_INITIALIZED);
+            let sizes: Vec<u8> = solve.relay_scrambles.iter().map(|s| s.size).collect();
+            puzzle_times(sizes.len(), &solve.splits, solve.time.measured_time())
+                .map_or(String::default(), |times| {
+                    format_puzzle_times(&sizes, &times, self.time_precision.get())
+                })
+        }
+
+        fn get_scramble(&self) -> String {
+            let solve = self.solve.borrow();
+            let solve = solve.as_ref().expect(EXPECT_INITIALIZED);
+            if solve.relay_scrambles.is_empty() {
+                solve.scramble.clone()
+            } else {
+                format_relay_scrambles(&solve.relay_scrambles)
+            }
+        }
+
+        fn get_solution_string(&self) -> String {
+            self.solve
+                .borrow()
+                .as_ref()
+                .expect(EXPECT_INITIALIZED)
+                .fewest_moves
+                .as_ref()
+                .map_or(String::default(), |result| result.solution.clone())
+        }
+
+        fn get_comment(&self) -> String {
+            self.solve
+                .borrow()
+                .as_ref()
+                .expect(EXPECT_INITIALIZED)
+                .comment
+                .clone()
+        }
+
+        fn set_comment(&self, v: String) {
+            self.solve
+                .borrow_mut()
+                .as_mut()
+                .expect(EXPECT_INITIALIZED)
+                .comment = v;
+        }
+
+        fn get_tags_string(&self) -> String {
+            format_tags(&self.solve.borrow().as_ref().expect(EXPECT_INITIALIZED).tags)
+        }
+
         fn get_timestamp_string(&self) -> String {
             let timestamp = self
                 .solve
@@ -116,8 +201,41 @@ impl SessionItem {
             .time
     }
 
+    /// Gets the Multi-Blind result of this item.
+    pub fn multi_blind(&self) -> Option<MultiBlindResult> {
+        self.imp()
+            .solve
+            .borrow()
+            .as_ref()
+            .expect(EXPECT_INITIALIZED)
+            .multi_blind
+    }
+
+    /// Gets the Fewest Moves result of this item.
+    pub fn fewest_moves(&self) -> Option<FewestMovesResult> {
+        self.imp()
+            .solve
+            .borrow()
+            .as_ref()
+            .expect(EXPECT_INITIALIZED)
+            .fewest_moves
+            .clone()
+    }
+
+    /// Gets the result this item is ranked by: the move count of its
+    /// Fewest Moves result, which is DNF if the solve has been marked DNF,
+    /// or else its time.
+    pub fn result(&self) -> SolveResult {
+        let time = self.time();
+        match self.fewest_moves() {
+            Some(_) if time.is_dnf() => SolveResult::Moves(MoveCount::DNF),
+            Some(result) => SolveResult::Moves(MoveCount::from(&result)),
+            None => SolveResult::Time(time),
+        }
+    }
+
     /// Gets the mean of 3 of this item.
-    pub fn mo3(&self) -> Option<SolveTime> {
+    pub fn mo3(&self) -> Option<SolveResult> {
         self.imp().mo3.get()
     }
 
@@ -132,7 +250,7 @@ impl SessionItem {
     }
 
     /// Sets the mean of 3 of this item.
-    pub(crate) fn set_mo3(&self, v: Option<SolveTime>) {
+    pub(crate) fn set_mo3(&self, v: Option<SolveResult>) {
         self.imp().mo3.set(v);
         self.notify_mo3_string();
     }
@@ -149,6 +267,84 @@ impl SessionItem {
         self.notify_ao12_string();
     }
 
+    /// Gets a copy of the solve of this item.
+    pub fn solve_data(&self) -> SolveData {
+        self.imp()
+            .solve
+            .borrow()
+            .as_ref()
+            .expect(EXPECT_INITIALIZED)
+            .clone()
+    }
+
+    /// Gets the position of this item in its session.
+    pub fn index(&self) -> usize {
+        self.imp().index.get()
+    }
+
+    /// Sets the position of this item in its session.
+    pub(crate) fn set_index(&self, v: usize) {
+        self.imp().index.set(v);
+    }
+
+    /// Sets the precision the times of this item are displayed with.
+    pub(crate) fn set_time_precision(&self, precision: TimePrecision) {
+        let imp = self.imp();
+        if imp.time_precision.replace(precision) == precision {
+            return;
+        }
+        self.notify_solve_time_string();
+        self.notify_memo_string();
+        self.notify_puzzle_times_string();
+        self.notify_mo3_string();
+        self.notify_ao5_string();
+        self.notify_ao12_string();
+    }
+
+    /// Gets the intermediate times of this item.
+    pub fn splits(&self) -> Vec<Duration> {
+        self.imp()
+            .solve
+            .borrow()
+            .as_ref()
+            .expect(EXPECT_INITIALIZED)
+            .splits
+            .clone()
+    }
+
+    /// Gets the tags of this item.
+    pub fn tags(&self) -> BTreeSet<String> {
+        self.imp()
+            .solve
+            .borrow()
+            .as_ref()
+            .expect(EXPECT_INITIALIZED)
+            .tags
+            .clone()
+    }
+
+    /// Sets the tags of this item.
+    pub fn set_tags(&self, tags: BTreeSet<String>) {
+        self.imp()
+            .solve
+            .borrow_mut()
+            .as_mut()
+            .expect(EXPECT_INITIALIZED)
+            .tags = tags;
+        self.notify_tags_string();
+    }
+
+    /// Sets the scramble of this item, which must not be a relay.
+    pub fn set_scramble(&self, scramble: String) {
+        self.imp()
+            .solve
+            .borrow_mut()
+            .as_mut()
+            .expect(EXPECT_INITIALIZED)
+            .scramble = scramble;
+        self.notify_scramble();
+    }
+
     /// Gets the timestamp of this item.
     pub(crate) fn timestamp(&self) -> SystemTime {
         self.imp()
@@ -165,3 +361,14 @@ impl From<&SessionItem> for SolveTime {
         value.time()
     }
 }
+
+impl From<&SessionItem> for ReportSolve {
+    fn from(value: &SessionItem) -> Self {
+        Self {
+            number: value.index() + 1,
+            time: value.time(),
+            scramble: value.scramble(),
+            comment: value.comment(),
+        }
+    }
+}
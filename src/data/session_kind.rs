@@ -0,0 +1,119 @@
+use gtk::glib;
+
+/// Kind of a session, which determines how solves are timed and ranked.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, glib::Enum)]
+#[enum_type(name = "PtSessionKind")]
+#[repr(u32)]
+pub enum SessionKind {
+    /// Regular speedsolving.
+    #[default]
+    #[enum_value(name = "Standard", nick = "standard")]
+    Standard = 0,
+    /// Blindfolded solving. The first press during a solve marks the end of
+    /// memorization.
+    #[enum_value(name = "Blindfolded", nick = "blindfolded")]
+    Blindfolded = 1,
+    /// Multi-Blind attempts, ranked by points.
+    #[enum_value(name = "Multi-Blind", nick = "multi-blind")]
+    MultiBlind = 2,
+    /// Fewest Moves attempts, ranked by move count.
+    #[enum_value(name = "Fewest Moves", nick = "fewest-moves")]
+    FewestMoves = 3,
+    /// 2x2x2, 3x3x3 and 4x4x4 solved in one attempt. Presses during a solve
+    /// mark the end of each puzzle.
+    #[enum_value(name = "2-3-4 Relay", nick = "relay-2-3-4")]
+    Relay234 = 4,
+    /// 2x2x2 to 7x7x7 solved in one attempt.
+    #[enum_value(name = "2-7 Relay", nick = "relay-2-7")]
+    Relay2To7 = 5,
+}
+
+impl SessionKind {
+    /// All session kinds.
+    pub const ALL: &'static [SessionKind] = &[
+        Self::Standard,
+        Self::Blindfolded,
+        Self::MultiBlind,
+        Self::FewestMoves,
+        Self::Relay234,
+        Self::Relay2To7,
+    ];
+
+    /// Gets the kind with this nick.
+    pub fn from_nick(nick: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|kind| kind.nick() == nick)
+    }
+
+    /// Gets the nick of this kind.
+    pub fn nick(&self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Blindfolded => "blindfolded",
+            Self::MultiBlind => "multi-blind",
+            Self::FewestMoves => "fewest-moves",
+            Self::Relay234 => "relay-2-3-4",
+            Self::Relay2To7 => "relay-2-7",
+        }
+    }
+
+    /// Gets the human readable name of this kind.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Standard => "Standard",
+            Self::Blindfolded => "Blindfolded",
+            Self::MultiBlind => "Multi-Blind",
+            Self::FewestMoves => "Fewest Moves",
+            Self::Relay234 => "2-3-4 Relay",
+            Self::Relay2To7 => "2-7 Relay",
+        }
+    }
+
+    /// Gets the number of presses during a solve that record a split instead
+    /// of stopping the timer.
+    pub fn n_splits(&self) -> u32 {
+        match self {
+            Self::Standard => 0,
+            Self::Blindfolded => 1,
+            Self::MultiBlind => 0,
+            Self::FewestMoves => 0,
+            Self::Relay234 | Self::Relay2To7 => self.relay_sizes().len() as u32 - 1,
+        }
+    }
+
+    /// Gets the sizes of the cubes solved in a relay, in order, or an empty
+    /// slice if this is not a relay.
+    pub fn relay_sizes(&self) -> &'static [u8] {
+        match self {
+            Self::Relay234 => &[2, 3, 4],
+            Self::Relay2To7 => &[2, 3, 4, 5, 6, 7],
+            _ => &[],
+        }
+    }
+
+    /// Returns `true` if solves in this kind of session span several
+    /// puzzles.
+    pub fn is_relay(&self) -> bool {
+        !self.relay_sizes().is_empty()
+    }
+
+    /// Returns `true` if means of 3 are calculated for solves in this kind
+    /// of session.
+    pub fn has_mean_of_3(&self) -> bool {
+        !matches!(self, Self::MultiBlind)
+    }
+
+    /// Returns `true` if solves in this kind of session may start with
+    /// inspection. Blindfolded events have none.
+    pub fn has_inspection(&self) -> bool {
+        !matches!(
+            self,
+            Self::Blindfolded | Self::MultiBlind | Self::FewestMoves
+        )
+    }
+
+    /// Returns `true` if averages of 5 and 12 are calculated for solves in
+    /// this kind of session.
+    pub fn has_averages(&self) -> bool {
+        !matches!(self, Self::MultiBlind | Self::FewestMoves)
+    }
+}
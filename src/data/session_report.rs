@@ -0,0 +1,444 @@
+use std::time::Duration;
+
+use crate::data::{
+    Penalty, PersonalBest, ReportSolve, SolveResult, SolveStatistic, SolveTime, TimePrecision,
+};
+
+/// Widths that histogram bins are rounded up to, in milliseconds.
+const BIN_WIDTHS: [u64; 13] = [
+    100, 200, 500, 1_000, 2_000, 5_000, 10_000, 15_000, 30_000, 60_000, 120_000, 300_000, 600_000,
+];
+
+/// Width of the charts in the HTML report, in pixels.
+const CHART_WIDTH: f64 = 640.0;
+/// Height of the charts in the HTML report, in pixels.
+const CHART_HEIGHT: f64 = 200.0;
+
+/// A printable overview of a session, with its statistics, personal bests,
+/// charts of its times and every solve.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionReport {
+    pub title: String,
+    /// Name and value of each statistic, in order.
+    pub statistics: Vec<(String, String)>,
+    pub personal_bests: Vec<PersonalBest>,
+    pub solves: Vec<ReportSolve>,
+    /// Precision the times are written with.
+    pub precision: TimePrecision,
+}
+
+impl SessionReport {
+    /// Gets the time of every solve, in order.
+    pub fn times(&self) -> Vec<SolveTime> {
+        self.solves.iter().map(|solve| solve.time).collect()
+    }
+
+    /// Describes a personal best of this report, e.g.
+    /// `Ao5 12.34 (from 12.80) · Solve 40`.
+    pub fn describe_personal_best(&self, pb: &PersonalBest) -> String {
+        let format = |result: &SolveResult| pb.statistic.format(result, self.precision);
+        match pb.old {
+            Some(old) => format!(
+                "{} {} (from {}) · Solve {}",
+                pb.statistic,
+                format(&pb.new),
+                format(&old),
+                pb.index + 1
+            ),
+            None => format!(
+                "{} {} · Solve {}",
+                pb.statistic,
+                format(&pb.new),
+                pb.index + 1
+            ),
+        }
+    }
+
+    /// Writes the report as a standalone HTML page, with the charts drawn
+    /// as inline SVG.
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str(&format!("<title>{}</title>\n", escape_html(&self.title)));
+        html.push_str(
+            "<style>\n\
+             body { font-family: sans-serif; max-width: 48em; margin: 2em auto; color: #241f31; }\n\
+             table { border-collapse: collapse; width: 100%; margin-bottom: 1.5em; }\n\
+             th, td { border-bottom: 1px solid #deddda; padding: 0.3em 0.6em; text-align: left; }\n\
+             td.time { font-variant-numeric: tabular-nums; white-space: nowrap; }\n\
+             td.scramble { font-family: monospace; }\n\
+             svg { display: block; margin-bottom: 1.5em; }\n\
+             @media print { h2 { break-after: avoid; } tr { break-inside: avoid; } }\n\
+             </style>\n</head>\n<body>\n",
+        );
+        html.push_str(&format!("<h1>{}</h1>\n", escape_html(&self.title)));
+
+        html.push_str("<h2>Statistics</h2>\n<table>\n");
+        for (name, value) in &self.statistics {
+            html.push_str(&format!(
+                "<tr><th>{}</th><td class=\"time\">{}</td></tr>\n",
+                escape_html(name),
+                escape_html(value)
+            ));
+        }
+        html.push_str("</table>\n");
+
+        if !self.personal_bests.is_empty() {
+            html.push_str("<h2>Personal Bests</h2>\n<table>\n");
+            html.push_str(
+                "<tr><th>Statistic</th><th>Time</th><th>Previous</th><th>Solve</th></tr>\n",
+            );
+            for pb in &self.personal_bests {
+                let format = |result: &SolveResult| pb.statistic.format(result, self.precision);
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td class=\"time\">{}</td><td class=\"time\">{}</td><td>{}</td></tr>\n",
+                    pb.statistic,
+                    format(&pb.new),
+                    pb.old.map_or(String::new(), |old| format(&old)),
+                    pb.index + 1
+                ));
+            }
+            html.push_str("</table>\n");
+        }
+
+        let times = self.times();
+        if let Some(graph) = TimeGraph::new(&times) {
+            html.push_str("<h2>Times</h2>\n");
+            html.push_str(&graph.to_svg());
+        }
+        if let Some(histogram) = Histogram::new(&times, 12) {
+            html.push_str("<h2>Distribution</h2>\n");
+            html.push_str(&histogram.to_svg());
+        }
+
+        html.push_str("<h2>Solves</h2>\n<table>\n");
+        html.push_str("<tr><th>#</th><th>Time</th><th>Scramble</th><th>Comment</th></tr>\n");
+        for solve in &self.solves {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td class=\"time\">{}</td><td class=\"scramble\">{}</td><td>{}</td></tr>\n",
+                solve.number,
+                solve.time.format(self.precision),
+                escape_html(&solve.scramble).replace('\n', "<br>"),
+                escape_html(&solve.comment)
+            ));
+        }
+        html.push_str("</table>\n</body>\n</html>\n");
+        html
+    }
+}
+
+/// The times of a session over the solves, with their average of 5.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeGraph {
+    /// Recorded time of each solve in seconds, or `None` for DNF solves.
+    pub singles: Vec<Option<f64>>,
+    /// Average of 5 ending at each solve in seconds, if any.
+    pub ao5: Vec<Option<f64>>,
+    /// Shortest time shown, in seconds.
+    pub min: f64,
+    /// Longest time shown, in seconds.
+    pub max: f64,
+}
+
+impl TimeGraph {
+    /// Creates the graph of these times, or `None` if no solve is finished.
+    pub fn new(times: &[SolveTime]) -> Option<Self> {
+        let seconds = |time: SolveTime| time.recorded_time().map(|t| t.as_secs_f64());
+        let singles: Vec<Option<f64>> = times.iter().map(|&time| seconds(time)).collect();
+        let ao5 = (0..times.len())
+            .map(|i| {
+                times[(i + 1).saturating_sub(5)..=i]
+                    .average_of_n()
+                    .filter(|_| i >= 4)
+                    .and_then(seconds)
+            })
+            .collect();
+
+        let finished = singles.iter().flatten();
+        let min = finished.clone().copied().reduce(f64::min)?;
+        let max = finished.copied().reduce(f64::max)?;
+        Some(Self {
+            singles,
+            ao5,
+            min,
+            max,
+        })
+    }
+
+    /// Gets the lines through the points of `values` in an area of this
+    /// size, with `y` growing downwards. Lines are broken at missing values.
+    pub fn lines(&self, values: &[Option<f64>], width: f64, height: f64) -> Vec<Vec<(f64, f64)>> {
+        let n = self.singles.len();
+        let range = (self.max - self.min).max(f64::EPSILON);
+        let mut lines = Vec::new();
+        let mut line = Vec::new();
+
+        for (i, value) in values.iter().enumerate() {
+            match value {
+                Some(value) => {
+                    let x = if n > 1 {
+                        width * i as f64 / (n - 1) as f64
+                    } else {
+                        width / 2.0
+                    };
+                    let y = height * (1.0 - (value - self.min) / range);
+                    line.push((x, y));
+                }
+                None if !line.is_empty() => lines.push(std::mem::take(&mut line)),
+                None => {}
+            }
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+        lines
+    }
+
+    fn to_svg(&self) -> String {
+        let mut svg = svg_start();
+        for (values, class) in [(&self.singles, "single"), (&self.ao5, "average")] {
+            for line in self.lines(values, CHART_WIDTH, CHART_HEIGHT) {
+                let points: Vec<String> = line
+                    .iter()
+                    .map(|(x, y)| format!("{:.1},{:.1}", x, y))
+                    .collect();
+                svg.push_str(&format!(
+                    "<polyline class=\"{}\" points=\"{}\"/>\n",
+                    class,
+                    points.join(" ")
+                ));
+            }
+        }
+        svg.push_str(&format!(
+            "<text x=\"2\" y=\"12\">{:.2}</text>\n<text x=\"2\" y=\"{}\">{:.2}</text>\n</svg>\n",
+            self.max,
+            CHART_HEIGHT - 4.0,
+            self.min
+        ));
+        svg
+    }
+}
+
+/// The number of finished solves within each range of times.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    /// Start of the first bin.
+    pub start: Duration,
+    pub bin_width: Duration,
+    /// Number of solves in each bin, from the fastest.
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Bins the finished times into about `n_bins` bins of a round width,
+    /// or returns `None` if no solve is finished.
+    pub fn new(times: &[SolveTime], n_bins: usize) -> Option<Self> {
+        let (start, width, len) = bin_range(times, n_bins)?;
+        Some(Self::with_bins(times, start, width, len))
+    }
+
+    /// Bins two sets of times into the same bins, so that their
+    /// distributions can be drawn over each other, or returns `None` if no
+    /// solve of either set is finished.
+    pub fn pair(first: &[SolveTime], second: &[SolveTime], n_bins: usize) -> Option<(Self, Self)> {
+        let times: Vec<SolveTime> = first.iter().chain(second).copied().collect();
+        let (start, width, len) = bin_range(&times, n_bins)?;
+        Some((
+            Self::with_bins(first, start, width, len),
+            Self::with_bins(second, start, width, len),
+        ))
+    }
+
+    /// Counts the finished times in `len` bins of `width` milliseconds from
+    /// `start`.
+    fn with_bins(times: &[SolveTime], start: u64, width: u64, len: usize) -> Self {
+        let mut counts = vec![0; len];
+        for time in times.iter().filter_map(|time| time.recorded_time()) {
+            counts[((time.as_millis() as u64 - start) / width) as usize] += 1;
+        }
+        Self {
+            start: Duration::from_millis(start),
+            bin_width: Duration::from_millis(width),
+            counts,
+        }
+    }
+
+    /// Gets the share of the finished solves in each bin, between 0 and 1.
+    pub fn fractions(&self) -> Vec<f64> {
+        let total = self.counts.iter().sum::<usize>().max(1);
+        self.counts
+            .iter()
+            .map(|&count| count as f64 / total as f64)
+            .collect()
+    }
+
+    /// Gets the range of times of the bin at this position, e.g.
+    /// `12.00–12.50`.
+    pub fn bin_label(&self, i: usize) -> String {
+        let time = |d: Duration| SolveTime::new(d, Penalty::Ok);
+        let start = self.start + self.bin_width * i as u32;
+        format!("{}–{}", time(start), time(start + self.bin_width))
+    }
+
+    /// Gets the position and size of the bar of each bin in an area of this
+    /// size, with `y` growing downwards.
+    pub fn bars(&self, width: f64, height: f64) -> Vec<(f64, f64, f64, f64)> {
+        let max = self.counts.iter().copied().max().unwrap_or(0).max(1);
+        let bar_width = width / self.counts.len() as f64;
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(i, &count)| {
+                let bar_height = height * count as f64 / max as f64;
+                (
+                    bar_width * i as f64,
+                    height - bar_height,
+                    bar_width,
+                    bar_height,
+                )
+            })
+            .collect()
+    }
+
+    fn to_svg(&self) -> String {
+        let mut svg = svg_start();
+        for (i, (x, y, width, height)) in
+            self.bars(CHART_WIDTH, CHART_HEIGHT).into_iter().enumerate()
+        {
+            svg.push_str(&format!(
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\"><title>{}: {}</title></rect>\n",
+                x + 1.0,
+                y,
+                (width - 2.0).max(1.0),
+                height,
+                self.bin_label(i),
+                self.counts[i]
+            ));
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// Gets the start and width in milliseconds of about `n_bins` bins of a
+/// round width covering the finished times, and the number of bins needed.
+fn bin_range(times: &[SolveTime], n_bins: usize) -> Option<(u64, u64, usize)> {
+    let millis = times
+        .iter()
+        .filter_map(|time| time.recorded_time())
+        .map(|time| time.as_millis() as u64);
+    let min = millis.clone().min()?;
+    let max = millis.max()?;
+
+    let target = (max - min) / n_bins.max(1) as u64;
+    let width = BIN_WIDTHS
+        .iter()
+        .copied()
+        .find(|&width| width >= target)
+        .unwrap_or(BIN_WIDTHS[BIN_WIDTHS.len() - 1]);
+    let start = min - min % width;
+    Some((start, width, ((max - start) / width + 1) as usize))
+}
+
+fn svg_start() -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n\
+         <style>polyline {{ fill: none; stroke-width: 1.5; }} .single {{ stroke: #99c1f1; }} \
+         .average {{ stroke: #1c71d8; stroke-width: 2; }} rect {{ fill: #62a0ea; }} \
+         text {{ font: 10px sans-serif; fill: #5e5c64; }}</style>\n",
+        w = CHART_WIDTH,
+        h = CHART_HEIGHT
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::PbStatistic;
+
+    fn ok(millis: u64) -> SolveTime {
+        SolveTime::new(Duration::from_millis(millis), Penalty::Ok)
+    }
+
+    #[test]
+    fn bin_times() {
+        let times = [
+            ok(10_100),
+            ok(10_400),
+            SolveTime::DNF,
+            ok(12_900),
+            ok(11_000),
+        ];
+        let histogram = Histogram::new(&times, 4).unwrap();
+        assert_eq!(histogram.start, Duration::from_millis(10_000));
+        assert_eq!(histogram.bin_width, Duration::from_millis(1_000));
+        assert_eq!(histogram.counts, vec![2, 1, 1]);
+        assert_eq!(histogram.bin_label(1), "11.00–12.00");
+        assert_eq!(histogram.bars(300.0, 100.0)[1], (100.0, 50.0, 100.0, 50.0));
+        assert_eq!(Histogram::new(&[SolveTime::DNF], 4), None);
+
+        let (first, second) =
+            Histogram::pair(&times[..2], &[ok(13_500), SolveTime::DNF], 4).unwrap();
+        assert_eq!(first.start, second.start);
+        assert_eq!(first.counts.len(), second.counts.len());
+        assert_eq!(second.fractions(), vec![0.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn graph_times() {
+        let times = [ok(10_000), SolveTime::DNF, ok(12_000), ok(14_000)];
+        let graph = TimeGraph::new(&times).unwrap();
+        assert_eq!(graph.min, 10.0);
+        assert_eq!(graph.max, 14.0);
+        assert!(graph.ao5.iter().all(Option::is_none));
+        assert_eq!(
+            graph.lines(&graph.singles, 30.0, 100.0),
+            vec![vec![(0.0, 100.0)], vec![(20.0, 50.0), (30.0, 0.0)]]
+        );
+    }
+
+    #[test]
+    fn write_html() {
+        let report = SessionReport {
+            title: "3x3 <OH>".to_string(),
+            statistics: vec![("Solves".to_string(), "2".to_string())],
+            personal_bests: vec![PersonalBest {
+                statistic: PbStatistic::Single,
+                index: 1,
+                old: Some(SolveResult::Time(ok(12_000))),
+                new: SolveResult::Time(ok(11_000)),
+            }],
+            solves: vec![
+                ReportSolve {
+                    number: 1,
+                    time: ok(12_000),
+                    scramble: "R U".to_string(),
+                    comment: String::new(),
+                },
+                ReportSolve {
+                    number: 2,
+                    time: ok(11_000),
+                    scramble: "F D".to_string(),
+                    comment: "Lucky & fast".to_string(),
+                },
+            ],
+            precision: TimePrecision::Centiseconds,
+        };
+        let html = report.to_html();
+        assert!(html.contains("<title>3x3 &lt;OH&gt;</title>"));
+        assert!(html.contains("<tr><td>Single</td><td class=\"time\">11.00</td><td class=\"time\">12.00</td><td>2</td></tr>"));
+        assert!(html.contains("<polyline class=\"single\""));
+        assert!(html.contains("<td>Lucky &amp; fast</td>"));
+        assert_eq!(
+            report.describe_personal_best(&report.personal_bests[0]),
+            "Single 11.00 (from 12.00) · Solve 2"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+/// A window action that can be bound to a configurable accelerator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shortcut {
+    /// Detailed name of the action, e.g. `last-solve.set-penalty::dnf`.
+    pub action: &'static str,
+    pub title: &'static str,
+    pub default_accel: &'static str,
+}
+
+/// Actions with configurable accelerators, in the order they are listed.
+pub const SHORTCUTS: &[Shortcut] = &[
+    Shortcut {
+        action: "last-solve.set-penalty::ok",
+        title: "Mark Last Solve as OK",
+        default_accel: "<primary>1",
+    },
+    Shortcut {
+        action: "last-solve.set-penalty::plus-two",
+        title: "Mark Last Solve as +2",
+        default_accel: "<primary>2",
+    },
+    Shortcut {
+        action: "last-solve.set-penalty::dnf",
+        title: "Mark Last Solve as DNF",
+        default_accel: "<primary>3",
+    },
+    Shortcut {
+        action: "last-solve.remove",
+        title: "Remove Last Solve",
+        default_accel: "<primary>Delete",
+    },
+    Shortcut {
+        action: "last-solve.show",
+        title: "Show Last Solve",
+        default_accel: "<primary>i",
+    },
+    Shortcut {
+        action: "last-solve.copy-ao5",
+        title: "Copy Last Ao5 Report",
+        default_accel: "<primary>5",
+    },
+    Shortcut {
+        action: "last-solve.copy-ao12",
+        title: "Copy Last Ao12 Report",
+        default_accel: "<primary><alt>5",
+    },
+    Shortcut {
+        action: "timer.new-scramble",
+        title: "New Scramble",
+        default_accel: "<primary>r",
+    },
+];
+
+/// Gets the accelerator of every shortcut, preferring the ones set in
+/// `custom` over the defaults. An empty custom accelerator disables the
+/// shortcut.
+pub fn shortcut_accels(custom: &HashMap<String, String>) -> Vec<(&'static str, Option<String>)> {
+    SHORTCUTS
+        .iter()
+        .map(|shortcut| {
+            let accel = custom
+                .get(shortcut.action)
+                .map_or(shortcut.default_accel, String::as_str);
+            let accel = Some(accel.to_string()).filter(|a| !a.is_empty());
+            (shortcut.action, accel)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_accels() {
+        let custom = HashMap::from([
+            ("last-solve.remove".to_string(), "<primary>z".to_string()),
+            ("timer.new-scramble".to_string(), String::new()),
+        ]);
+        let accels = shortcut_accels(&custom);
+        assert_eq!(accels.len(), SHORTCUTS.len());
+
+        let accel = |action: &str| {
+            accels
+                .iter()
+                .find(|(a, _)| *a == action)
+                .and_then(|(_, accel)| accel.clone())
+        };
+        assert_eq!(accel("last-solve.remove").as_deref(), Some("<primary>z"));
+        assert_eq!(
+            accel("last-solve.set-penalty::dnf").as_deref(),
+            Some("<primary>3")
+        );
+        assert_eq!(accel("timer.new-scramble"), None);
+    }
+
+    #[test]
+    fn unique_actions() {
+        for (i, shortcut) in SHORTCUTS.iter().enumerate() {
+            assert!(SHORTCUTS[i + 1..]
+                .iter()
+                .all(|s| s.action != shortcut.action && s.default_accel != shortcut.default_accel));
+        }
+    }
+}
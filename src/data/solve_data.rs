@@ -1,6 +1,7 @@
-use std::time::SystemTime;
+use std::collections::BTreeSet;
+use std::time::{Duration, SystemTime};
 
-use crate::data::SolveTime;
+use crate::data::{FewestMovesResult, MultiBlindResult, Penalty, RelayScramble, SolveTime};
 
 /// A solve.
 #[derive(Debug, Clone)]
@@ -11,6 +12,19 @@ pub struct SolveData {
     pub timestamp: SystemTime,
     /// The scramble used in the solve.
     pub scramble: String,
+    /// Intermediate times recorded during the solve, e.g. the end of
+    /// memorization in blindfolded solves.
+    pub splits: Vec<Duration>,
+    /// The result of a Multi-Blind attempt.
+    pub multi_blind: Option<MultiBlindResult>,
+    /// The result of a Fewest Moves attempt.
+    pub fewest_moves: Option<FewestMovesResult>,
+    /// The scrambles of each puzzle of a relay.
+    pub relay_scrambles: Vec<RelayScramble>,
+    /// A note written about the solve.
+    pub comment: String,
+    /// Labels of the solve, e.g. the method or the cube used.
+    pub tags: BTreeSet<String>,
 }
 
 impl SolveData {
@@ -19,8 +33,49 @@ impl SolveData {
             time,
             timestamp: SystemTime::now(),
             scramble,
+            splits: Vec::new(),
+            multi_blind: None,
+            fewest_moves: None,
+            relay_scrambles: Vec::new(),
+            comment: String::new(),
+            tags: BTreeSet::new(),
         }
     }
+
+    /// Creates a solve from a Multi-Blind attempt.
+    pub fn from_multi_blind(result: MultiBlindResult, scramble: String) -> Self {
+        Self {
+            multi_blind: Some(result),
+            ..Self::new(result.solve_time(), scramble)
+        }
+    }
+
+    /// Creates a solve from a Fewest Moves attempt that took `time`.
+    pub fn from_fewest_moves(result: FewestMovesResult, time: Duration, scramble: String) -> Self {
+        let penalty = if result.is_dnf() {
+            Penalty::Dnf
+        } else {
+            Penalty::Ok
+        };
+        Self {
+            fewest_moves: Some(result),
+            ..Self::new(SolveTime::new(time, penalty), scramble)
+        }
+    }
+
+    /// Creates a solve of a relay, whose splits mark the end of each puzzle.
+    pub fn from_relay(time: SolveTime, scrambles: Vec<RelayScramble>) -> Self {
+        Self {
+            relay_scrambles: scrambles,
+            ..Self::new(time, String::new())
+        }
+    }
+
+    /// Sets the intermediate times of the solve.
+    pub fn with_splits(mut self, splits: Vec<Duration>) -> Self {
+        self.splits = splits;
+        self
+    }
 }
 
 impl From<&SolveData> for SolveTime {
@@ -0,0 +1,309 @@
+use std::{cmp::Ordering, collections::BTreeSet, time::Duration, time::SystemTime};
+
+use chrono::NaiveDate;
+
+use crate::data::{Penalty, SolveStatistic, SolveTime, TimePrecision};
+
+/// Criteria that the solves of a session are filtered by. Criteria that are
+/// not set match every solve.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SolveFilter {
+    /// Text searched in the comment ignoring case, and in the scramble as
+    /// is, since case is meaningful in move notation.
+    pub text: String,
+    /// Tag the solves must have, ignoring case.
+    pub tag: Option<String>,
+    pub penalty: Option<Penalty>,
+    /// Shortest recorded time, inclusive. DNF solves are outside any range.
+    pub min_time: Option<Duration>,
+    /// Longest recorded time, inclusive.
+    pub max_time: Option<Duration>,
+    /// First day, inclusive.
+    pub from_date: Option<NaiveDate>,
+    /// Last day, inclusive.
+    pub to_date: Option<NaiveDate>,
+}
+
+impl SolveFilter {
+    /// Returns `true` if the filter matches every solve.
+    pub fn is_empty(&self) -> bool {
+        self.text.trim().is_empty()
+            && self.tag.is_none()
+            && self.penalty.is_none()
+            && self.min_time.is_none()
+            && self.max_time.is_none()
+            && self.from_date.is_none()
+            && self.to_date.is_none()
+    }
+
+    /// Returns `true` if a solve done on `date` matches the filter.
+    pub fn matches(
+        &self,
+        comment: &str,
+        scramble: &str,
+        tags: &BTreeSet<String>,
+        time: SolveTime,
+        date: NaiveDate,
+    ) -> bool {
+        let text = self.text.trim();
+        if !text.is_empty()
+            && !comment.to_lowercase().contains(&text.to_lowercase())
+            && !scramble.contains(text)
+        {
+            return false;
+        }
+
+        if let Some(tag) = &self.tag {
+            if !tags.iter().any(|t| t.to_lowercase() == tag.to_lowercase()) {
+                return false;
+            }
+        }
+
+        if self.penalty.is_some_and(|penalty| penalty != time.penalty) {
+            return false;
+        }
+
+        if self.min_time.is_some() || self.max_time.is_some() {
+            let Some(recorded) = time.recorded_time() else {
+                return false;
+            };
+            if self.min_time.is_some_and(|min| recorded < min)
+                || self.max_time.is_some_and(|max| recorded > max)
+            {
+                return false;
+            }
+        }
+
+        !(self.from_date.is_some_and(|from| date < from)
+            || self.to_date.is_some_and(|to| date > to))
+    }
+}
+
+/// Parses a day written as `YYYY-MM-DD`.
+pub fn parse_date(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d").ok()
+}
+
+/// What the solve list is sorted by.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SolveSortKey {
+    /// Position of the solve in the session.
+    Index,
+    /// Recorded time, with DNF solves last.
+    Time,
+    /// When the solve was done.
+    #[default]
+    Date,
+}
+
+/// Order of the solve list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolveSort {
+    pub key: SolveSortKey,
+    pub descending: bool,
+}
+
+impl Default for SolveSort {
+    /// Newest solves first.
+    fn default() -> Self {
+        Self {
+            key: SolveSortKey::Date,
+            descending: true,
+        }
+    }
+}
+
+impl SolveSort {
+    /// Gets the order with this nick, e.g. `time-ascending`.
+    pub fn from_nick(nick: &str) -> Option<Self> {
+        let (key, direction) = nick.split_once('-')?;
+        let key = match key {
+            "index" => SolveSortKey::Index,
+            "time" => SolveSortKey::Time,
+            "date" => SolveSortKey::Date,
+            _ => return None,
+        };
+        let descending = match direction {
+            "ascending" => false,
+            "descending" => true,
+            _ => return None,
+        };
+        Some(Self { key, descending })
+    }
+
+    /// Gets the nick of this order.
+    pub fn nick(&self) -> String {
+        let key = match self.key {
+            SolveSortKey::Index => "index",
+            SolveSortKey::Time => "time",
+            SolveSortKey::Date => "date",
+        };
+        let direction = if self.descending {
+            "descending"
+        } else {
+            "ascending"
+        };
+        format!("{}-{}", key, direction)
+    }
+
+    /// Compares two solves, given as their index, time and timestamp.
+    pub fn compare(
+        &self,
+        a: (usize, SolveTime, SystemTime),
+        b: (usize, SolveTime, SystemTime),
+    ) -> Ordering {
+        let ordering = match self.key {
+            SolveSortKey::Index => a.0.cmp(&b.0),
+            SolveSortKey::Time => a.1.cmp(&b.1).then(a.0.cmp(&b.0)),
+            SolveSortKey::Date => a.2.cmp(&b.2).then(a.0.cmp(&b.0)),
+        };
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+/// Summarizes the solves matching a filter, in the order they were done,
+/// with this precision, e.g.
+/// `12 of 40 solves · Mean 13.20 · Best 11.02 · Ao5 12.80`.
+///
+/// The averages are of the last solves matching the filter.
+pub fn describe_filtered_solves(
+    times: &[SolveTime],
+    n_total: usize,
+    precision: TimePrecision,
+) -> String {
+    let mut parts = vec![format!("{} of {} solves", times.len(), n_total)];
+    if let Some(mean) = times.mean_of_successes() {
+        parts.push(format!("Mean {}", mean.format_average(precision)));
+    }
+    if let Some(best) = times.best_of_n().filter(|t| !t.is_dnf()) {
+        parts.push(format!("Best {}", best.format(precision)));
+    }
+    for n in [5, 12] {
+        if times.len() >= n {
+            if let Some(average) = times[times.len() - n..].average_of_n() {
+                parts.push(format!("Ao{} {}", n, average.format_average(precision)));
+            }
+        }
+    }
+    parts.join(" · ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok(millis: u64) -> SolveTime {
+        SolveTime::new(Duration::from_millis(millis), Penalty::Ok)
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn match_text() {
+        let none = BTreeSet::new();
+        let filter = SolveFilter {
+            text: "lucky".to_string(),
+            ..Default::default()
+        };
+        let day = date(2024, 3, 4);
+        assert!(filter.matches("Lucky PLL skip", "R U", &none, ok(9_000), day));
+        assert!(!filter.matches("", "R U", &none, ok(9_000), day));
+
+        let filter = SolveFilter {
+            text: "R U2".to_string(),
+            ..Default::default()
+        };
+        assert!(filter.matches("", "F R U2 D", &none, ok(9_000), day));
+        assert!(!filter.matches("", "F r u2 D", &none, ok(9_000), day));
+    }
+
+    #[test]
+    fn match_penalty_and_ranges() {
+        let none = BTreeSet::new();
+        let filter = SolveFilter {
+            min_time: Some(Duration::from_secs(10)),
+            max_time: Some(Duration::from_secs(12)),
+            from_date: Some(date(2024, 3, 1)),
+            to_date: Some(date(2024, 3, 31)),
+            ..Default::default()
+        };
+        let day = date(2024, 3, 4);
+        assert!(filter.matches("", "", &none, ok(12_000), day));
+        assert!(filter.matches(
+            "",
+            "",
+            &none,
+            SolveTime::new(Duration::from_secs(9), Penalty::Plus2),
+            day
+        ));
+        assert!(!filter.matches("", "", &none, ok(12_010), day));
+        assert!(!filter.matches("", "", &none, SolveTime::DNF, day));
+        assert!(!filter.matches("", "", &none, ok(11_000), date(2024, 4, 1)));
+
+        let filter = SolveFilter {
+            penalty: Some(Penalty::Dnf),
+            ..Default::default()
+        };
+        assert!(filter.matches("", "", &none, SolveTime::DNF, day));
+        assert!(!filter.matches("", "", &none, ok(11_000), day));
+        assert!(SolveFilter::default().is_empty());
+        assert_eq!(parse_date(" 2024-03-04 "), Some(day));
+        assert_eq!(parse_date("04/03/2024"), None);
+    }
+
+    #[test]
+    fn match_tag() {
+        let filter = SolveFilter {
+            tag: Some("zbll".to_string()),
+            ..Default::default()
+        };
+        let day = date(2024, 3, 4);
+        let tags = BTreeSet::from(["ZBLL".to_string(), "warmup".to_string()]);
+        assert!(filter.matches("", "", &tags, ok(9_000), day));
+        assert!(!filter.matches("", "", &BTreeSet::new(), ok(9_000), day));
+        assert!(!filter.is_empty());
+    }
+
+    #[test]
+    fn sort_solves() {
+        let now = SystemTime::now();
+        let a = (0, ok(12_000), now);
+        let b = (1, SolveTime::DNF, now + Duration::from_secs(30));
+        let c = (2, ok(10_000), now + Duration::from_secs(60));
+
+        let mut solves = [a, b, c];
+        solves.sort_by(|x, y| {
+            SolveSort::from_nick("time-ascending")
+                .unwrap()
+                .compare(*x, *y)
+        });
+        assert_eq!(
+            solves.iter().map(|s| s.0).collect::<Vec<_>>(),
+            vec![2, 0, 1]
+        );
+
+        solves.sort_by(|x, y| SolveSort::default().compare(*x, *y));
+        assert_eq!(
+            solves.iter().map(|s| s.0).collect::<Vec<_>>(),
+            vec![2, 1, 0]
+        );
+
+        assert_eq!(SolveSort::default().nick(), "date-descending");
+        assert_eq!(SolveSort::from_nick("index-sideways"), None);
+    }
+
+    #[test]
+    fn describe_filtered() {
+        let times = [ok(12_000), ok(13_000), ok(14_000), ok(11_000), ok(15_000)];
+        assert_eq!(
+            describe_filtered_solves(&times, 40, TimePrecision::Centiseconds),
+            "5 of 40 solves · Mean 13.00 · Best 11.00 · Ao5 13.00"
+        );
+    }
+}
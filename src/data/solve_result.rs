@@ -0,0 +1,116 @@
+use crate::data::{MoveCount, SolveStatistic, SolveTime, TimePrecision};
+
+/// Result a solve is ranked by: its time, or its move count in Fewest
+/// Moves sessions.
+///
+/// Results of a session are all of the same kind, so they are only compared
+/// with each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SolveResult {
+    Time(SolveTime),
+    Moves(MoveCount),
+}
+
+impl SolveResult {
+    pub fn is_dnf(&self) -> bool {
+        match self {
+            Self::Time(time) => time.is_dnf(),
+            Self::Moves(count) => count.is_dnf(),
+        }
+    }
+
+    /// Formats a single result with this precision.
+    pub fn format(&self, precision: TimePrecision) -> String {
+        match self {
+            Self::Time(time) => time.format(precision),
+            Self::Moves(count) => count.to_string(),
+        }
+    }
+
+    /// Formats a mean or an average of results with this precision.
+    pub fn format_average(&self, precision: TimePrecision) -> String {
+        match self {
+            Self::Time(time) => time.format_average(precision),
+            Self::Moves(count) => count.format_mean(),
+        }
+    }
+
+    /// Calculates the mean of 3 results. Returns `None` if there are not
+    /// exactly 3 results of the same kind.
+    pub fn mean_of_3(results: &[SolveResult]) -> Option<Self> {
+        if results.len() != 3 {
+            return None;
+        }
+        if let Some(times) = Self::times(results) {
+            times.mean_of_n().map(Self::Time)
+        } else {
+            let counts: Option<Vec<MoveCount>> = results
+                .iter()
+                .map(|result| match result {
+                    Self::Moves(count) => Some(*count),
+                    Self::Time(_) => None,
+                })
+                .collect();
+            MoveCount::mean_of_3(&counts?).map(Self::Moves)
+        }
+    }
+
+    /// Calculates the average of the results, which only times have.
+    pub fn average_of_n(results: &[SolveResult]) -> Option<Self> {
+        Self::times(results)?.average_of_n().map(Self::Time)
+    }
+
+    /// Gets the times of the results, or `None` if some are move counts.
+    fn times(results: &[SolveResult]) -> Option<Vec<SolveTime>> {
+        results
+            .iter()
+            .map(|result| match result {
+                Self::Time(time) => Some(*time),
+                Self::Moves(_) => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::data::Penalty;
+
+    fn ok(millis: u64) -> SolveResult {
+        SolveResult::Time(SolveTime::new(Duration::from_millis(millis), Penalty::Ok))
+    }
+
+    fn moves(count: u32) -> SolveResult {
+        SolveResult::Moves(MoveCount::new(count))
+    }
+
+    #[test]
+    fn mean_of_results() {
+        let times = [ok(12_000), ok(13_000), ok(14_000)];
+        assert_eq!(SolveResult::mean_of_3(&times), Some(ok(13_000)));
+        assert_eq!(SolveResult::average_of_n(&times), Some(ok(13_000)));
+        assert_eq!(SolveResult::mean_of_3(&times[..2]), None);
+
+        let counts = [moves(31), moves(28), moves(30)];
+        let mean = SolveResult::mean_of_3(&counts).unwrap();
+        assert_eq!(mean.format_average(TimePrecision::default()), "29.67");
+        assert_eq!(counts[1].format(TimePrecision::default()), "28");
+        assert_eq!(SolveResult::average_of_n(&counts), None);
+        assert_eq!(
+            SolveResult::mean_of_3(&[moves(31), ok(12_000), moves(30)]),
+            None
+        );
+    }
+
+    #[test]
+    fn dnf_results() {
+        let dnf = SolveResult::Moves(MoveCount::DNF);
+        assert!(dnf.is_dnf());
+        assert!(moves(80) < dnf);
+        assert!(SolveResult::Time(SolveTime::DNF).is_dnf());
+        assert!(!ok(12_000).is_dnf());
+    }
+}
@@ -8,6 +8,87 @@ use std::{
 
 use crate::data::Penalty;
 
+/// Number of decimals times are displayed with.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TimePrecision {
+    /// Hundredths of a second, e.g. `12.34`, as shown by a stackmat.
+    #[default]
+    Centiseconds,
+    /// Thousandths of a second, e.g. `12.345`.
+    Milliseconds,
+}
+
+impl TimePrecision {
+    /// All time precisions.
+    pub const ALL: &'static [TimePrecision] = &[Self::Centiseconds, Self::Milliseconds];
+
+    /// Gets the precision with this nick.
+    pub fn from_nick(nick: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|precision| precision.nick() == nick)
+    }
+
+    /// Gets the nick of this precision.
+    pub fn nick(&self) -> &'static str {
+        match self {
+            Self::Centiseconds => "centiseconds",
+            Self::Milliseconds => "milliseconds",
+        }
+    }
+
+    /// Gets the human readable name of this precision.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Centiseconds => "Hundredths (0.01 s)",
+            Self::Milliseconds => "Thousandths (0.001 s)",
+        }
+    }
+
+    /// Gets the number of decimals of this precision.
+    pub fn decimals(&self) -> usize {
+        match self {
+            Self::Centiseconds => 2,
+            Self::Milliseconds => 3,
+        }
+    }
+
+    /// Gets the smallest time that can be displayed with this precision.
+    pub fn unit(&self) -> Duration {
+        match self {
+            Self::Centiseconds => Duration::from_millis(10),
+            Self::Milliseconds => Duration::from_millis(1),
+        }
+    }
+
+    /// Truncates a time to this precision, as a stackmat does with single
+    /// times.
+    pub fn truncate(&self, time: Duration) -> Duration {
+        let unit = self.unit().as_nanos();
+        duration_from_nanos(time.as_nanos() / unit * unit)
+    }
+
+    /// Rounds an average or mean to this precision, following the WCA rules:
+    /// results of 10 minutes or more are rounded to the nearest second, and
+    /// other results to the nearest unit of this precision.
+    pub fn round_average(&self, time: Duration) -> Duration {
+        let unit = if time >= Duration::from_secs(600) {
+            Duration::from_secs(1).as_nanos()
+        } else {
+            self.unit().as_nanos()
+        };
+        duration_from_nanos((time.as_nanos() + unit / 2) / unit * unit)
+    }
+}
+
+fn duration_from_nanos(nanos: u128) -> Duration {
+    Duration::new(
+        (nanos / 1_000_000_000) as u64,
+        (nanos % 1_000_000_000) as u32,
+    )
+}
+
 /// Time of a solve.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct SolveTime {
@@ -23,11 +104,12 @@ impl SolveTime {
         penalty: Penalty::Dnf,
     };
 
-    /// Creates a new `SolveTime`.
+    /// Creates a new `SolveTime`. The time is kept to the millisecond,
+    /// whatever precision it is displayed with.
     pub fn new(time: Duration, penalty: Penalty) -> Self {
         let millis = time.as_millis() as u64;
         Self {
-            time: Duration::from_millis(millis - millis % 10),
+            time: Duration::from_millis(millis),
             penalty,
         }
     }
@@ -69,6 +151,25 @@ impl SolveTime {
             eps.recorded_time().unwrap().as_millis() <= eps_millis
         }
     }
+
+    /// Formats the time with this precision, e.g. `12.34+` or `DNF`.
+    pub fn format(&self, precision: TimePrecision) -> String {
+        let rec_time = self.recorded_time().unwrap_or_default();
+        match self.penalty {
+            Penalty::Ok => display_time(&rec_time, precision),
+            Penalty::Plus2 => format!("{}+", display_time(&rec_time, precision)),
+            Penalty::Dnf => "DNF".to_string(),
+        }
+    }
+
+    /// Formats an average or mean with this precision. Unlike single times,
+    /// which are truncated, averages are rounded following the WCA rules.
+    pub fn format_average(&self, precision: TimePrecision) -> String {
+        match self.recorded_time() {
+            Some(time) => display_time(&precision.round_average(time), precision),
+            None => "DNF".to_string(),
+        }
+    }
 }
 
 impl PartialEq for SolveTime {
@@ -98,12 +199,7 @@ impl Ord for SolveTime {
 
 impl Display for SolveTime {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let rec_time = self.recorded_time().unwrap_or_default();
-        match self.penalty {
-            Penalty::Ok => write!(f, "{}", display_time(&rec_time)),
-            Penalty::Plus2 => write!(f, "{}+", display_time(&rec_time)),
-            Penalty::Dnf => write!(f, "DNF"),
-        }
+        write!(f, "{}", self.format(TimePrecision::default()))
     }
 }
 
@@ -184,15 +280,48 @@ impl From<&SolveTime> for SolveTime {
     }
 }
 
-fn display_time(time: &Duration) -> String {
-    let hundreths = ((time.as_millis() / 10) % 100) as u32;
+/// Parses a time written as `[[h:]m:]s[.fraction]`, e.g. `12.34`,
+/// `1:02.34` or `45:12`.
+pub fn parse_time(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    let mut parts = text.rsplit(':');
+    let seconds = parts.next()?;
+    let (whole, fraction) = seconds.split_once('.').unwrap_or((seconds, ""));
+    if whole.is_empty() || fraction.len() > 3 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let millis = format!("{:0<3}", fraction).parse::<u64>().ok()?;
+    let mut total = whole.parse::<u64>().ok()?;
+    if parts.clone().next().is_some() && total >= 60 {
+        return None;
+    }
+
+    for (i, part) in parts.enumerate() {
+        let value = part.parse::<u64>().ok()?;
+        total += match i {
+            0 => value * 60,
+            1 => value * 60 * 60,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_millis(total * 1000 + millis))
+}
+
+fn display_time(time: &Duration, precision: TimePrecision) -> String {
+    let time = precision.truncate(*time);
+    let fraction = format!("{:03}", time.subsec_millis());
+    let fraction = &fraction[..precision.decimals()];
     let seconds = (time.as_secs() % 60) as u32;
     let minutes = (time.as_secs() / 60) as u32;
 
     if minutes >= 1 {
-        format!("{}:{:02}.{:02}", minutes, seconds, hundreths)
+        format!("{}:{:02}.{}", minutes, seconds, fraction)
     } else {
-        format!("{}.{:02}", seconds, hundreths)
+        format!("{}.{}", seconds, fraction)
     }
 }
 
@@ -247,4 +376,68 @@ mod tests {
             "DNF".to_string(),
         );
     }
+
+    #[test]
+    fn keep_milliseconds() {
+        let time = SolveTime::new(Duration::from_millis(8_357), Penalty::Ok);
+        assert_eq!(time.recorded_time(), Some(Duration::from_millis(8_357)));
+        assert_eq!(time.format(TimePrecision::Centiseconds), "8.35");
+        assert_eq!(time.format(TimePrecision::Milliseconds), "8.357");
+
+        let time = SolveTime::new(Duration::from_millis(62_009), Penalty::Plus2);
+        assert_eq!(time.format(TimePrecision::Centiseconds), "1:04.00+");
+        assert_eq!(time.format(TimePrecision::Milliseconds), "1:04.009+");
+    }
+
+    #[test]
+    fn round_average() {
+        let round = |precision: TimePrecision, millis| {
+            precision
+                .round_average(Duration::from_millis(millis))
+                .as_millis()
+        };
+        assert_eq!(round(TimePrecision::Centiseconds, 12_344), 12_340);
+        assert_eq!(round(TimePrecision::Centiseconds, 12_345), 12_350);
+        assert_eq!(round(TimePrecision::Milliseconds, 12_345), 12_345);
+        assert_eq!(round(TimePrecision::Centiseconds, 600_499), 600_000);
+        assert_eq!(round(TimePrecision::Milliseconds, 612_500), 613_000);
+    }
+
+    #[test]
+    fn format_average() {
+        let average = SolveTime::new(Duration::from_millis(12_345), Penalty::Ok);
+        assert_eq!(average.format_average(TimePrecision::Centiseconds), "12.35");
+        assert_eq!(
+            average.format_average(TimePrecision::Milliseconds),
+            "12.345"
+        );
+        assert_eq!(average.format(TimePrecision::Centiseconds), "12.34");
+        assert_eq!(
+            SolveTime::DNF.format_average(TimePrecision::Centiseconds),
+            "DNF"
+        );
+    }
+
+    #[test]
+    fn parse_seconds() {
+        assert_eq!(parse_time("12.34"), Some(Duration::from_millis(12_340)));
+        assert_eq!(parse_time("8"), Some(Duration::from_millis(8_000)));
+        assert_eq!(parse_time(" 7.5 "), Some(Duration::from_millis(7_500)));
+    }
+
+    #[test]
+    fn parse_minutes() {
+        assert_eq!(parse_time("1:02.34"), Some(Duration::from_millis(62_340)));
+        assert_eq!(parse_time("45:12"), Some(Duration::from_secs(2_712)));
+        assert_eq!(parse_time("1:00:00"), Some(Duration::from_secs(3_600)));
+    }
+
+    #[test]
+    fn parse_invalid_time() {
+        assert_eq!(parse_time(""), None);
+        assert_eq!(parse_time("abc"), None);
+        assert_eq!(parse_time("1:75"), None);
+        assert_eq!(parse_time("1.2345"), None);
+        assert_eq!(parse_time("1:2:3:4"), None);
+    }
 }
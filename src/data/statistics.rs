@@ -1,4 +1,28 @@
-use crate::data::SolveTime;
+use std::time::Duration;
+
+use crate::data::{Penalty, SolveTime, TimePrecision};
+
+/// The time the next solve needs for an average to reach a target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeNeeded {
+    /// Any result of the next solve reaches the target, even a DNF.
+    Any,
+    /// The next solve must be faster than this time.
+    Below(SolveTime),
+    /// No result of the next solve reaches the target.
+    Impossible,
+}
+
+impl TimeNeeded {
+    /// Formats the time needed with this precision, e.g. `< 11.48`.
+    pub fn format(&self, precision: TimePrecision) -> String {
+        match self {
+            Self::Any => "Any".to_string(),
+            Self::Below(time) => format!("< {}", time.format(precision)),
+            Self::Impossible => "Not possible".to_string(),
+        }
+    }
+}
 
 /// A trait to retrieve statistics for a sequence of solves.
 pub trait SolveStatistic {
@@ -12,6 +36,30 @@ pub trait SolveStatistic {
     fn best_solve_index(&self) -> Option<usize>;
     /// Gets the index of the worst solve.
     fn worst_solve_index(&self) -> Option<usize>;
+    /// Gets the best time of the solves.
+    fn best_of_n(&self) -> Option<SolveTime>;
+    /// Calculates the ratio of solves that are not DNF, between 0 and 1.
+    fn success_rate(&self) -> Option<f64>;
+    /// Calculates the mean time of the solves that are not DNF.
+    fn mean_of_successes(&self) -> Option<SolveTime>;
+    /// Calculates the best average of `n` these solves can reach, if the
+    /// one solve left to complete the average is as fast as possible. With
+    /// more solves left, any average is still possible.
+    fn best_possible_average(&self, n: usize) -> Option<SolveTime>;
+    /// Calculates the worst average of `n` these solves can reach, if every
+    /// solve needed to complete the average is DNF.
+    fn worst_possible_average(&self, n: usize) -> Option<SolveTime>;
+    /// Calculates the time the next solve needs for the average of these
+    /// solves and the next solve to be faster than `target`.
+    fn time_needed_for_average(&self, target: SolveTime) -> Option<TimeNeeded>;
+    /// Calculates the median time of the solves. DNF solves count as the
+    /// slowest, so the median is DNF if the middle solves are.
+    fn median(&self) -> Option<SolveTime>;
+    /// Calculates the sample standard deviation of the solves that are not
+    /// DNF. At least two of them are needed.
+    fn standard_deviation(&self) -> Option<Duration>;
+    /// Calculates the best average of `n` consecutive solves.
+    fn best_average_of(&self, n: usize) -> Option<SolveTime>;
 }
 
 impl<T> SolveStatistic for [T]
@@ -25,7 +73,7 @@ where
         }
 
         let sum: SolveTime = self.iter().map(Into::<SolveTime>::into).sum();
-        Some(sum / len)
+        Some(round_average(sum / len))
     }
 
     fn average_of_n(&self) -> Option<SolveTime> {
@@ -44,7 +92,7 @@ where
                 acc
             }
         });
-        Some(sum / (len - 2))
+        Some(round_average(sum / (len - 2)))
     }
 
     fn best_solve_index(&self) -> Option<usize> {
@@ -62,6 +110,241 @@ where
             .max_by_key(|&(_, time)| time)
             .map(|(i, _)| i)
     }
+
+    fn best_of_n(&self) -> Option<SolveTime> {
+        self.iter().map(Into::<SolveTime>::into).min()
+    }
+
+    fn success_rate(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let n_success = self
+            .iter()
+            .map(Into::<SolveTime>::into)
+            .filter(|time| !time.is_dnf())
+            .count();
+        Some(n_success as f64 / self.len() as f64)
+    }
+
+    fn mean_of_successes(&self) -> Option<SolveTime> {
+        let successes: Vec<SolveTime> = self
+            .iter()
+            .map(Into::<SolveTime>::into)
+            .filter(|time| !time.is_dnf())
+            .collect();
+        successes.mean_of_n()
+    }
+
+    fn best_possible_average(&self, n: usize) -> Option<SolveTime> {
+        if self.is_empty() || self.len() + 1 != n {
+            return None;
+        }
+        let fastest = SolveTime::new(Duration::ZERO, Penalty::Ok);
+        average_with(self, &[fastest])
+    }
+
+    fn worst_possible_average(&self, n: usize) -> Option<SolveTime> {
+        if self.is_empty() || self.len() >= n {
+            return None;
+        }
+        average_with(self, &vec![SolveTime::DNF; n - self.len()])
+    }
+
+    fn time_needed_for_average(&self, target: SolveTime) -> Option<TimeNeeded> {
+        let target_millis = target.recorded_time()?.as_millis() as u64;
+        let average_with_next = |millis: u64| {
+            let next = SolveTime::new(Duration::from_millis(millis), Penalty::Ok);
+            average_with(self, &[next])
+        };
+
+        if average_with(self, &[SolveTime::DNF])? < target {
+            return Some(TimeNeeded::Any);
+        }
+        if average_with_next(0)? >= target {
+            return Some(TimeNeeded::Impossible);
+        }
+
+        // The average grows with the time of the next solve, and a time this
+        // slow is sure not to reach the target.
+        let mut fast = 0;
+        let mut slow = target_millis * (self.len() as u64 + 1);
+        while slow - fast > 1 {
+            let mid = fast + (slow - fast) / 2;
+            if average_with_next(mid)? < target {
+                fast = mid;
+            } else {
+                slow = mid;
+            }
+        }
+        Some(TimeNeeded::Below(SolveTime::new(
+            Duration::from_millis(slow),
+            Penalty::Ok,
+        )))
+    }
+
+    fn median(&self) -> Option<SolveTime> {
+        let mut times: Vec<SolveTime> = self.iter().map(Into::<SolveTime>::into).collect();
+        if times.is_empty() {
+            return None;
+        }
+        times.sort();
+
+        let middle = times.len() / 2;
+        if times.len() % 2 == 1 {
+            Some(times[middle])
+        } else {
+            Some(round_average((times[middle - 1] + times[middle]) / 2))
+        }
+    }
+
+    fn standard_deviation(&self) -> Option<Duration> {
+        let seconds: Vec<f64> = self
+            .iter()
+            .map(Into::<SolveTime>::into)
+            .filter_map(|time| time.recorded_time())
+            .map(|time| time.as_secs_f64())
+            .collect();
+        if seconds.len() < 2 {
+            return None;
+        }
+
+        let mean = seconds.iter().sum::<f64>() / seconds.len() as f64;
+        let variance =
+            seconds.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (seconds.len() - 1) as f64;
+        Some(Duration::from_secs_f64(variance.sqrt()))
+    }
+
+    fn best_average_of(&self, n: usize) -> Option<SolveTime> {
+        if n == 0 {
+            return None;
+        }
+        self.windows(n)
+            .filter_map(|solves| solves.average_of_n())
+            .min()
+    }
+}
+
+/// Significance level below which a difference between two sets of solves
+/// is considered real rather than chance.
+pub const SIGNIFICANCE_LEVEL: f64 = 0.05;
+
+/// Result of a Mann-Whitney U test comparing two sets of solves.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MannWhitneyU {
+    /// U statistic of the first set, the number of pairs of a solve of each
+    /// set where the first one is slower, ties counting as half.
+    pub u: f64,
+    /// Probability that a solve of the first set is faster than one of the
+    /// second, ties counting as half.
+    pub first_faster: f64,
+    /// Two-sided p-value, the probability of a difference at least this
+    /// large if both sets came from the same distribution.
+    pub p_value: f64,
+}
+
+impl MannWhitneyU {
+    /// Returns `true` if the difference is significant at
+    /// `SIGNIFICANCE_LEVEL`.
+    pub fn is_significant(&self) -> bool {
+        self.p_value < SIGNIFICANCE_LEVEL
+    }
+}
+
+/// Tests whether two sets of solves differ with a Mann-Whitney U test. It
+/// ranks the solves rather than averaging them, so DNF solves take part as
+/// the slowest rank, tied with each other, instead of being dropped.
+///
+/// The p-value uses the normal approximation with corrections for ties and
+/// continuity, which is reliable from about 8 solves in each set. Returns
+/// `None` if a set is empty or every solve is tied.
+pub fn mann_whitney_u(first: &[SolveTime], second: &[SolveTime]) -> Option<MannWhitneyU> {
+    let n1 = first.len() as f64;
+    let n2 = second.len() as f64;
+    if first.is_empty() || second.is_empty() {
+        return None;
+    }
+
+    let mut solves: Vec<(SolveTime, bool)> = first
+        .iter()
+        .map(|&time| (time, true))
+        .chain(second.iter().map(|&time| (time, false)))
+        .collect();
+    solves.sort_by_key(|&(time, _)| time);
+
+    // Tied solves share the mean of their ranks.
+    let mut first_rank_sum = 0.0;
+    let mut tie_sum = 0.0;
+    let mut start = 0;
+    while start < solves.len() {
+        let end = start
+            + solves[start..]
+                .iter()
+                .take_while(|(time, _)| *time == solves[start].0)
+                .count();
+        let n_tied = (end - start) as f64;
+        let rank = (start + end + 1) as f64 / 2.0;
+        let n_first = solves[start..end]
+            .iter()
+            .filter(|(_, is_first)| *is_first)
+            .count();
+        first_rank_sum += rank * n_first as f64;
+        tie_sum += n_tied.powi(3) - n_tied;
+        start = end;
+    }
+
+    let n = n1 + n2;
+    let u = first_rank_sum - n1 * (n1 + 1.0) / 2.0;
+    let mean = n1 * n2 / 2.0;
+    let variance = n1 * n2 / 12.0 * ((n + 1.0) - tie_sum / (n * (n - 1.0)));
+    if variance <= 0.0 || !variance.is_finite() {
+        return None;
+    }
+
+    let z = ((u - mean).abs() - 0.5).max(0.0) / variance.sqrt();
+    Some(MannWhitneyU {
+        u,
+        first_faster: 1.0 - u / (n1 * n2),
+        p_value: erfc(z / std::f64::consts::SQRT_2).min(1.0),
+    })
+}
+
+/// Approximates the complementary error function, with a relative error
+/// below 1.2e-7.
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let result = t * poly.exp();
+    if x >= 0.0 {
+        result
+    } else {
+        2.0 - result
+    }
+}
+
+/// Rounds an average or mean to the millisecond, as times are kept. Averages
+/// are only rounded to the display precision when formatted.
+fn round_average(average: SolveTime) -> SolveTime {
+    SolveTime::new(average.time + Duration::from_micros(500), average.penalty)
+}
+
+/// Calculates the average of `solves` followed by `next`.
+fn average_with<T>(solves: &[T], next: &[SolveTime]) -> Option<SolveTime>
+where
+    for<'a> &'a T: Into<SolveTime>,
+{
+    let mut times: Vec<SolveTime> = solves.iter().map(Into::<SolveTime>::into).collect();
+    times.extend_from_slice(next);
+    times.average_of_n()
 }
 
 #[cfg(test)]
@@ -186,4 +469,163 @@ mod tests {
             SolveTime::DNF,
         );
     }
+
+    #[test]
+    fn calculate_best_of_n() {
+        let solves = [
+            SolveTime::new(Duration::from_millis(95_300), Penalty::Dnf),
+            SolveTime::new(Duration::from_millis(102_470), Penalty::Ok),
+            SolveTime::new(Duration::from_millis(98_120), Penalty::Plus2),
+        ];
+        assert!(solves.best_of_n().unwrap().eq_aprrox(
+            &SolveTime::new(Duration::from_millis(100_120), Penalty::Ok),
+            10
+        ));
+    }
+
+    #[test]
+    fn calculate_best_of_n_all_dnf() {
+        let solves = [
+            SolveTime::new(Duration::from_millis(95_300), Penalty::Dnf),
+            SolveTime::new(Duration::from_millis(102_470), Penalty::Dnf),
+        ];
+        assert!(solves.best_of_n().unwrap().is_dnf());
+    }
+
+    #[test]
+    fn calculate_success_rate() {
+        let solves = [
+            SolveTime::new(Duration::from_millis(95_300), Penalty::Dnf),
+            SolveTime::new(Duration::from_millis(102_470), Penalty::Ok),
+            SolveTime::new(Duration::from_millis(98_120), Penalty::Plus2),
+            SolveTime::new(Duration::from_millis(91_870), Penalty::Ok),
+        ];
+        assert_eq!(solves.success_rate(), Some(0.75));
+        assert_eq!(Vec::<SolveTime>::new().success_rate(), None);
+    }
+
+    #[test]
+    fn calculate_mean_of_successes() {
+        let solves = [
+            SolveTime::new(Duration::from_millis(95_300), Penalty::Dnf),
+            SolveTime::new(Duration::from_millis(102_470), Penalty::Ok),
+            SolveTime::new(Duration::from_millis(98_120), Penalty::Plus2),
+        ];
+        assert!(solves.mean_of_successes().unwrap().eq_aprrox(
+            &SolveTime::new(Duration::from_millis(101_290), Penalty::Ok),
+            10
+        ));
+    }
+
+    #[test]
+    fn calculate_mean_of_successes_all_dnf() {
+        let solves = [SolveTime::new(Duration::from_millis(95_300), Penalty::Dnf)];
+        assert_eq!(solves.mean_of_successes(), None);
+    }
+
+    fn ok(millis: u64) -> SolveTime {
+        SolveTime::new(Duration::from_millis(millis), Penalty::Ok)
+    }
+
+    #[test]
+    fn calculate_possible_averages() {
+        let solves = [ok(12_000), ok(15_000), ok(11_000), ok(13_000)];
+        assert_eq!(solves.best_possible_average(5), Some(ok(12_000)));
+        assert!(solves
+            .worst_possible_average(5)
+            .unwrap()
+            .eq_aprrox(&ok(13_330), 10));
+        assert_eq!(solves.best_possible_average(4), None);
+        assert_eq!(solves[..3].best_possible_average(5), None);
+
+        let solves = [ok(12_000), SolveTime::DNF, ok(11_000), ok(13_000)];
+        assert_eq!(solves.worst_possible_average(5), Some(SolveTime::DNF));
+        assert_eq!(solves.best_possible_average(5), Some(ok(12_000)));
+    }
+
+    #[test]
+    fn calculate_time_needed_for_average() {
+        let solves = [ok(12_000), ok(15_000), ok(11_000), ok(13_000)];
+        // 12.00 and 13.00 are counted, so the next solve needs to be faster
+        // than 11.479 for an average below 12.160.
+        assert_eq!(
+            solves.time_needed_for_average(ok(12_160)),
+            Some(TimeNeeded::Below(ok(11_479)))
+        );
+        assert_eq!(
+            solves.time_needed_for_average(ok(14_000)),
+            Some(TimeNeeded::Any)
+        );
+        assert_eq!(
+            solves.time_needed_for_average(ok(11_000)),
+            Some(TimeNeeded::Impossible)
+        );
+        assert_eq!(solves.time_needed_for_average(SolveTime::DNF), None);
+    }
+
+    #[test]
+    fn calculate_median() {
+        assert_eq!(
+            [ok(13_000), ok(11_000), ok(12_000)].median(),
+            Some(ok(12_000))
+        );
+        // DNF is the slowest, so the middle solves are 12.00 and 13.00.
+        assert_eq!(
+            [ok(12_000), SolveTime::DNF, ok(11_000), ok(13_000)].median(),
+            Some(ok(12_500))
+        );
+        assert_eq!(
+            [ok(11_000), SolveTime::DNF, SolveTime::DNF].median(),
+            Some(SolveTime::DNF)
+        );
+        assert_eq!(Vec::<SolveTime>::new().median(), None);
+    }
+
+    #[test]
+    fn calculate_standard_deviation() {
+        let solves = [ok(10_000), SolveTime::DNF, ok(12_000), ok(14_000)];
+        let sd = solves.standard_deviation().unwrap();
+        assert!((sd.as_secs_f64() - 2.0).abs() < 1e-9);
+        assert_eq!([ok(10_000), SolveTime::DNF].standard_deviation(), None);
+    }
+
+    #[test]
+    fn calculate_best_average_of() {
+        let solves = [
+            ok(15_000),
+            ok(12_000),
+            SolveTime::DNF,
+            ok(11_000),
+            ok(13_000),
+            ok(10_000),
+        ];
+        assert_eq!(solves.best_average_of(5), Some(ok(12_000)));
+        assert_eq!(solves.best_average_of(12), None);
+    }
+
+    #[test]
+    fn compare_with_mann_whitney_u() {
+        let fast = [ok(10_000), ok(11_000), ok(12_000), ok(13_000), ok(14_000)];
+        let slow = [ok(15_000), ok(16_000), ok(17_000), ok(18_000), ok(19_000)];
+        let test = mann_whitney_u(&fast, &slow).unwrap();
+        assert_eq!(test.u, 0.0);
+        assert_eq!(test.first_faster, 1.0);
+        assert!((test.p_value - 0.0122).abs() < 1e-3);
+        assert!(test.is_significant());
+
+        // DNF solves are ranked as the slowest instead of being dropped.
+        let with_dnf = [
+            ok(10_500),
+            SolveTime::DNF,
+            ok(12_500),
+            SolveTime::DNF,
+            ok(11_500),
+        ];
+        let test = mann_whitney_u(&fast, &with_dnf).unwrap();
+        assert_eq!(test.u, 9.0);
+        assert!(!test.is_significant());
+
+        assert_eq!(mann_whitney_u(&fast, &[]), None);
+        assert_eq!(mann_whitney_u(&[SolveTime::DNF], &[SolveTime::DNF]), None);
+    }
 }
@@ -0,0 +1,137 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::data::{SolveStatistic, SolveTime, TimePrecision};
+
+/// Parses tags separated by commas, e.g. `ZBLL, new cube`. Spaces around
+/// each tag are removed, and empty or repeated tags are ignored.
+pub fn parse_tags(text: &str) -> BTreeSet<String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Formats tags the way `parse_tags` reads them.
+pub fn format_tags(tags: &BTreeSet<String>) -> String {
+    tags.iter().cloned().collect::<Vec<_>>().join(", ")
+}
+
+/// Statistics of the solves with a tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagStatistics {
+    pub tag: String,
+    pub count: usize,
+    /// Mean of the solves that are not DNF.
+    pub mean: Option<SolveTime>,
+    /// Average of the last 5 solves with the tag.
+    pub ao5: Option<SolveTime>,
+    /// Average of the last 12 solves with the tag.
+    pub ao12: Option<SolveTime>,
+}
+
+impl TagStatistics {
+    /// Calculates the statistics of the solves with a tag, given their
+    /// times in the order they were done.
+    pub fn new(tag: &str, times: &[SolveTime]) -> Self {
+        let last_average = |n: usize| {
+            times
+                .len()
+                .checked_sub(n)
+                .and_then(|start| times[start..].average_of_n())
+        };
+        Self {
+            tag: tag.to_string(),
+            count: times.len(),
+            mean: times.mean_of_successes(),
+            ao5: last_average(5),
+            ao12: last_average(12),
+        }
+    }
+
+    /// Describes the statistics with this precision, e.g.
+    /// `14 solves · Mean 13.20 · Ao5 12.80`.
+    pub fn describe(&self, precision: TimePrecision) -> String {
+        let solves = if self.count == 1 { "solve" } else { "solves" };
+        let mut parts = vec![format!("{} {}", self.count, solves)];
+        for (name, value) in [("Mean", self.mean), ("Ao5", self.ao5), ("Ao12", self.ao12)] {
+            if let Some(value) = value {
+                parts.push(format!("{} {}", name, value.format_average(precision)));
+            }
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Calculates the statistics of every tag, sorted by tag. `solves` are the
+/// tags and time of each solve, in the order they were done.
+pub fn tag_statistics(solves: &[(BTreeSet<String>, SolveTime)]) -> Vec<TagStatistics> {
+    let mut times: BTreeMap<&str, Vec<SolveTime>> = BTreeMap::new();
+    for (tags, time) in solves {
+        for tag in tags {
+            times.entry(tag).or_default().push(*time);
+        }
+    }
+    times
+        .into_iter()
+        .map(|(tag, times)| TagStatistics::new(tag, &times))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::data::Penalty;
+
+    fn ok(millis: u64) -> SolveTime {
+        SolveTime::new(Duration::from_millis(millis), Penalty::Ok)
+    }
+
+    #[test]
+    fn parse_and_format_tags() {
+        let tags = parse_tags(" warmup, ZBLL,,new cube , ZBLL ");
+        assert_eq!(
+            tags.iter().map(String::as_str).collect::<Vec<_>>(),
+            vec!["ZBLL", "new cube", "warmup"]
+        );
+        assert_eq!(format_tags(&tags), "ZBLL, new cube, warmup");
+        assert!(parse_tags(" , ").is_empty());
+    }
+
+    #[test]
+    fn calculate_tag_statistics() {
+        let zbll = parse_tags("ZBLL");
+        let both = parse_tags("ZBLL, warmup");
+        let solves = vec![
+            (both.clone(), ok(15_000)),
+            (zbll.clone(), ok(12_000)),
+            (BTreeSet::new(), ok(30_000)),
+            (zbll.clone(), SolveTime::DNF),
+            (zbll.clone(), ok(11_000)),
+            (zbll.clone(), ok(13_000)),
+        ];
+        let statistics = tag_statistics(&solves);
+        assert_eq!(statistics.len(), 2);
+
+        let zbll = &statistics[0];
+        assert_eq!(zbll.tag, "ZBLL");
+        assert_eq!(zbll.count, 5);
+        assert_eq!(zbll.mean, Some(ok(12_750)));
+        // 15.00, 12.00, DNF, 11.00, 13.00
+        assert_eq!(zbll.ao5, Some(ok(13_333)));
+        assert_eq!(zbll.ao12, None);
+        assert_eq!(
+            zbll.describe(TimePrecision::Centiseconds),
+            "5 solves · Mean 12.75 · Ao5 13.33"
+        );
+
+        let warmup = &statistics[1];
+        assert_eq!(warmup.count, 1);
+        assert_eq!(
+            warmup.describe(TimePrecision::Centiseconds),
+            "1 solve · Mean 15.00"
+        );
+    }
+}
@@ -2,7 +2,17 @@ use std::time::{Duration, Instant};
 
 use gtk::glib;
 
-use super::SolveTime;
+use super::{Penalty, SolveTime};
+
+/// The inspection before a solve, which goes on while the timer is waiting
+/// and ready.
+#[derive(Debug)]
+pub(crate) struct InspectionPriv {
+    pub started: Instant,
+    pub tick_cb_id: glib::SourceId,
+    /// Inspection time at the last tick.
+    pub elapsed: Duration,
+}
 
 /// The internal representation of the state of a timer.
 #[derive(Debug, Default)]
@@ -13,11 +23,13 @@ pub(crate) enum TimerStatePriv {
         timeout_id: glib::SourceId,
     },
     Ready,
+    Inspecting,
     Timing {
         last_tick: Instant,
         tick_cb_id: glib::SourceId,
         duration: Duration,
-        plus_2: bool,
+        penalty: Penalty,
+        splits: Vec<Duration>,
     },
     Finished {
         solve_time: SolveTime,
@@ -30,8 +42,12 @@ impl TimerStatePriv {
             Self::Idle => TimerState::Idle,
             Self::Wait { .. } => TimerState::Wait,
             Self::Ready => TimerState::Ready,
-            Self::Timing { duration, .. } => TimerState::Timing {
+            Self::Inspecting => TimerState::Inspecting,
+            Self::Timing {
+                duration, splits, ..
+            } => TimerState::Timing {
                 duration: *duration,
+                n_splits: splits.len(),
             },
             Self::Finished { solve_time } => TimerState::Finished {
                 solve_time: *solve_time,
@@ -51,8 +67,11 @@ pub enum TimerState {
     Wait,
     /// The timer is ready to start.
     Ready,
-    /// The timer is currently running.
-    Timing { duration: Duration },
+    /// The puzzle is being inspected before the timer is pressed.
+    Inspecting,
+    /// The timer is currently running. `n_splits` is the number of splits
+    /// recorded so far.
+    Timing { duration: Duration, n_splits: usize },
     /// The timer has finished timing.
     Finished { solve_time: SolveTime },
 }
@@ -2,18 +2,23 @@ use std::mem;
 use std::time::Duration;
 use std::time::Instant;
 
-use crate::data::{Penalty, SolveTime, TimerState, TimerStatePriv};
+use crate::data::{
+    inspection_cue, inspection_penalty, InspectionPriv, Penalty, SolveTime, TimerState,
+    TimerStatePriv,
+};
 use adw::subclass::prelude::*;
 use gtk::glib;
 use gtk::prelude::*;
 
 const WAIT_TIMEOUT: u64 = 500;
 const TICK_INTERVAL: u64 = 10;
+const INSPECTION_TICK_INTERVAL: u64 = 50;
 
 const EXPECT_RWLOCK: &str = "Error accessing timer state.";
 
 #[doc(hidden)]
 mod imp {
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
     use std::sync::RwLock;
 
     use gtk::glib::subclass::{Signal, SignalType};
@@ -21,10 +26,44 @@ mod imp {
 
     use super::*;
 
-    #[derive(Debug, Default)]
+    #[derive(Debug, Default, glib::Properties)]
+    #[properties(wrapper_type = super::TimerStateMachine)]
     pub struct TimerStateMachine {
         pub(super) state: RwLock<TimerStatePriv>,
         pub(super) last_solve: RwLock<SolveTime>,
+        pub(super) last_splits: RwLock<Vec<Duration>>,
+        pub(super) inspection: RwLock<Option<InspectionPriv>>,
+
+        /// Number of presses during timing that record a split instead of
+        /// stopping the timer.
+        #[property(get = Self::n_splits, set = Self::set_n_splits, type = u32)]
+        pub(super) n_splits: AtomicU32,
+
+        /// Whether the first press starts inspection instead of the timer.
+        #[property(
+            get = Self::inspection_enabled,
+            set = Self::set_inspection_enabled,
+            type = bool
+        )]
+        pub(super) inspection_enabled: AtomicBool,
+    }
+
+    impl TimerStateMachine {
+        fn n_splits(&self) -> u32 {
+            self.n_splits.load(Ordering::Relaxed)
+        }
+
+        fn set_n_splits(&self, n_splits: u32) {
+            self.n_splits.store(n_splits, Ordering::Relaxed);
+        }
+
+        fn inspection_enabled(&self) -> bool {
+            self.inspection_enabled.load(Ordering::Relaxed)
+        }
+
+        fn set_inspection_enabled(&self, enabled: bool) {
+            self.inspection_enabled.store(enabled, Ordering::Relaxed);
+        }
     }
 
     #[glib::object_subclass]
@@ -33,6 +72,7 @@ mod imp {
         type Type = super::TimerStateMachine;
     }
 
+    #[glib::derived_properties]
     impl ObjectImpl for TimerStateMachine {
         fn signals() -> &'static [Signal] {
             static SIGNALS: Lazy<Vec<Signal>> = Lazy::new(|| {
@@ -43,6 +83,9 @@ mod imp {
                     Signal::builder("tick")
                         .param_types(Vec::<SignalType>::new())
                         .build(),
+                    Signal::builder("inspection-cue")
+                        .param_types([String::static_type()])
+                        .build(),
                 ]
             });
             SIGNALS.as_ref()
@@ -73,6 +116,82 @@ impl TimerStateMachine {
         *imp.last_solve.read().expect(EXPECT_RWLOCK)
     }
 
+    /// Gets the splits recorded so far by the running timer.
+    pub fn current_splits(&self) -> Vec<Duration> {
+        let imp = self.imp();
+        match &*imp.state.read().expect(EXPECT_RWLOCK) {
+            TimerStatePriv::Timing { splits, .. } => splits.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Gets the splits of the last time recorded by the timer.
+    pub fn last_splits(&self) -> Vec<Duration> {
+        let imp = self.imp();
+        imp.last_splits.read().expect(EXPECT_RWLOCK).clone()
+    }
+
+    /// Gets the time spent inspecting so far, or `None` if the puzzle is not
+    /// being inspected.
+    pub fn inspection_elapsed(&self) -> Option<Duration> {
+        let imp = self.imp();
+        imp.inspection
+            .read()
+            .expect(EXPECT_RWLOCK)
+            .as_ref()
+            .map(|inspection| inspection.elapsed)
+    }
+
+    /// Starts inspection, ticking until the timer starts.
+    fn start_inspection(&self) {
+        let imp = self.imp();
+        let tick_cb = glib::timeout_add(
+            Duration::from_millis(INSPECTION_TICK_INTERVAL),
+            glib::clone!(@weak self as obj => @default-return glib::ControlFlow::Break, move || {
+                obj.inspection_tick();
+                glib::ControlFlow::Continue
+            }),
+        );
+        *imp.inspection.write().expect(EXPECT_RWLOCK) = Some(InspectionPriv {
+            started: Instant::now(),
+            tick_cb_id: tick_cb,
+            elapsed: Duration::ZERO,
+        });
+    }
+
+    /// Stops inspection and gets the penalty of a solve started now, or
+    /// `Penalty::Ok` if there was no inspection.
+    fn finish_inspection(&self) -> Penalty {
+        let imp = self.imp();
+        match imp.inspection.write().expect(EXPECT_RWLOCK).take() {
+            Some(inspection) => {
+                inspection.tick_cb_id.remove();
+                inspection_penalty(inspection.started.elapsed())
+            }
+            None => Penalty::Ok,
+        }
+    }
+
+    /// Stops inspection without starting the timer.
+    pub(crate) fn cancel_inspection(&self) {
+        let imp = self.imp();
+        let mut state_changed = false;
+
+        {
+            let mut state = imp.state.write().expect(EXPECT_RWLOCK);
+            if matches!(*state, TimerStatePriv::Inspecting) {
+                self.finish_inspection();
+                *state = TimerStatePriv::Idle;
+                state_changed = true;
+                log::debug!("--cancel--> {:?}", &*state)
+            }
+        }
+
+        if state_changed {
+            self.emit_by_name::<()>("state-changed", &[])
+        }
+    }
+
     /// Called when timer trigger is pressed.
     pub(crate) fn press(&self) {
         let imp = self.imp();
@@ -83,7 +202,11 @@ impl TimerStateMachine {
             let o_state = mem::take(&mut *state);
 
             let n_state = match o_state {
-                TimerStatePriv::Idle => {
+                TimerStatePriv::Idle if self.inspection_enabled() => {
+                    self.start_inspection();
+                    TimerStatePriv::Inspecting
+                }
+                TimerStatePriv::Idle | TimerStatePriv::Inspecting => {
                     let timeout = glib::timeout_add_once(
                         Duration::from_millis(WAIT_TIMEOUT),
                         glib::clone!(@weak self as obj => move || {
@@ -98,14 +221,30 @@ impl TimerStateMachine {
                     last_tick,
                     tick_cb_id,
                     duration,
-                    plus_2,
+                    penalty,
+                    mut splits,
+                } if splits.len() < self.n_splits() as usize => {
+                    splits.push(duration + (Instant::now() - last_tick));
+                    TimerStatePriv::Timing {
+                        last_tick,
+                        tick_cb_id,
+                        duration,
+                        penalty,
+                        splits,
+                    }
+                }
+                TimerStatePriv::Timing {
+                    last_tick,
+                    tick_cb_id,
+                    duration,
+                    penalty,
+                    splits,
                 } => {
                     tick_cb_id.remove();
-                    let solve_time = SolveTime::new(
-                        duration + (Instant::now() - last_tick),
-                        if plus_2 { Penalty::Plus2 } else { Penalty::Ok },
-                    );
+                    let solve_time =
+                        SolveTime::new(duration + (Instant::now() - last_tick), penalty);
                     *imp.last_solve.write().expect(EXPECT_RWLOCK) = solve_time;
+                    *imp.last_splits.write().expect(EXPECT_RWLOCK) = splits;
                     TimerStatePriv::Finished { solve_time }
                 }
                 s => {
@@ -137,9 +276,14 @@ impl TimerStateMachine {
             let n_state = match o_state {
                 TimerStatePriv::Wait { timeout_id } => {
                     timeout_id.remove();
-                    TimerStatePriv::Idle
+                    if self.inspection_elapsed().is_some() {
+                        TimerStatePriv::Inspecting
+                    } else {
+                        TimerStatePriv::Idle
+                    }
                 }
                 TimerStatePriv::Ready => {
+                    let penalty = self.finish_inspection();
                     let tick_cb = glib::timeout_add(
                         Duration::from_millis(TICK_INTERVAL),
                         glib::clone!(@strong self as obj => move || {
@@ -151,7 +295,8 @@ impl TimerStateMachine {
                         last_tick: Instant::now(),
                         tick_cb_id: tick_cb,
                         duration: Duration::ZERO,
-                        plus_2: false,
+                        penalty,
+                        splits: Vec::new(),
                     }
                 }
                 TimerStatePriv::Finished { .. } => TimerStatePriv::Idle,
@@ -224,6 +369,28 @@ impl TimerStateMachine {
 
         self.emit_by_name::<()>("tick", &[])
     }
+
+    /// Called on every tick during inspection, which calls the cues of the
+    /// inspection time reached since the last tick.
+    pub(crate) fn inspection_tick(&self) {
+        let imp = self.imp();
+
+        let cue = {
+            let mut inspection = imp.inspection.write().expect(EXPECT_RWLOCK);
+            let Some(inspection) = &mut *inspection else {
+                return;
+            };
+            let elapsed = inspection.started.elapsed();
+            let cue = inspection_cue(inspection.elapsed, elapsed);
+            inspection.elapsed = elapsed;
+            cue
+        };
+
+        if let Some(cue) = cue {
+            self.emit_by_name::<()>("inspection-cue", &[&cue.nick()]);
+        }
+        self.emit_by_name::<()>("tick", &[])
+    }
 }
 
 impl Default for TimerStateMachine {
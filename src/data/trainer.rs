@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Number of latest times of a case its average is taken over.
+pub const CASE_AVERAGE_LENGTH: usize = 12;
+
+/// Weight of cases that were never timed, so they come up early.
+const UNTIMED_WEIGHT: f64 = 3.0;
+/// Bounds of the weight of timed cases, so fast cases still come up and
+/// slow ones do not crowd out the others.
+const MIN_WEIGHT: f64 = 0.25;
+const MAX_WEIGHT: f64 = 4.0;
+
+/// Latest times of the cases of the last layer trainer, by case
+/// identifier.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CaseTimes {
+    times: HashMap<String, Vec<Duration>>,
+}
+
+impl CaseTimes {
+    /// Creates case times from the times of each case, oldest first.
+    pub fn new(times: HashMap<String, Vec<Duration>>) -> Self {
+        let mut case_times = Self::default();
+        for (id, times) in times {
+            for time in times {
+                case_times.record(&id, time);
+            }
+        }
+        case_times
+    }
+
+    /// Gets the latest times of each case, oldest first.
+    pub fn times(&self) -> &HashMap<String, Vec<Duration>> {
+        &self.times
+    }
+
+    /// Records a time of a case, forgetting times older than the ones the
+    /// average is taken over.
+    pub fn record(&mut self, id: &str, time: Duration) {
+        let times = self.times.entry(id.to_string()).or_default();
+        times.push(time);
+        if times.len() > CASE_AVERAGE_LENGTH {
+            times.remove(0);
+        }
+    }
+
+    /// Forgets the times of every case.
+    pub fn clear(&mut self) {
+        self.times.clear();
+    }
+
+    /// Gets the mean of the latest times of a case, or `None` if it was
+    /// never timed.
+    pub fn average(&self, id: &str) -> Option<Duration> {
+        let times = self.times.get(id).filter(|times| !times.is_empty())?;
+        Some(times.iter().sum::<Duration>() / times.len() as u32)
+    }
+
+    /// Gets how likely each case is to come up next. Cases slower than the
+    /// others weigh more, by the square of how much slower they are.
+    pub fn weights<S: AsRef<str>>(&self, ids: &[S]) -> Vec<f64> {
+        let averages: Vec<Option<f64>> = ids
+            .iter()
+            .map(|id| self.average(id.as_ref()).map(|a| a.as_secs_f64()))
+            .collect();
+        let timed: Vec<f64> = averages.iter().flatten().copied().collect();
+        let overall = timed.iter().sum::<f64>() / timed.len().max(1) as f64;
+
+        averages
+            .iter()
+            .map(|average| match average {
+                Some(average) if overall > 0.0 => {
+                    (average / overall).powi(2).clamp(MIN_WEIGHT, MAX_WEIGHT)
+                }
+                Some(_) => 1.0,
+                None if timed.is_empty() => 1.0,
+                None => UNTIMED_WEIGHT,
+            })
+            .collect()
+    }
+
+    /// Writes the times as text to save them, one case per line with its
+    /// identifier and its times in milliseconds separated by tabs.
+    pub fn to_text(&self) -> String {
+        let mut ids: Vec<&String> = self.times.keys().collect();
+        ids.sort();
+        ids.into_iter()
+            .map(|id| {
+                let mut line = id.clone();
+                for time in &self.times[id] {
+                    line.push_str(&format!("\t{}", time.as_millis()));
+                }
+                line.push('\n');
+                line
+            })
+            .collect()
+    }
+
+    /// Reads the times saved by `to_text`, skipping times that are not
+    /// numbers.
+    pub fn from_text(text: &str) -> Self {
+        let times = text
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split('\t');
+                let id = fields.next().filter(|id| !id.is_empty())?;
+                let times = fields
+                    .filter_map(|time| time.parse().ok())
+                    .map(Duration::from_millis)
+                    .collect();
+                Some((id.to_string(), times))
+            })
+            .collect();
+        Self::new(times)
+    }
+
+    /// Chooses the index of the next case among `ids` by their weights,
+    /// never choosing the `previous` case twice in a row if there are
+    /// others. Returns `None` if there are no cases.
+    pub fn choose<S: AsRef<str>>(
+        &self,
+        ids: &[S],
+        previous: Option<&str>,
+        mut random: impl FnMut(u32) -> u32,
+    ) -> Option<usize> {
+        let mut weights = self.weights(ids);
+        if ids.len() > 1 {
+            for (weight, id) in weights.iter_mut().zip(ids) {
+                if Some(id.as_ref()) == previous {
+                    *weight = 0.0;
+                }
+            }
+        }
+
+        let total: f64 = weights.iter().sum();
+        let mut target = random(1_000_000) as f64 / 1_000_000.0 * total;
+        for (i, weight) in weights.iter().enumerate() {
+            if target < *weight {
+                return Some(i);
+            }
+            target -= weight;
+        }
+        weights.iter().rposition(|&weight| weight > 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(secs: f64) -> Duration {
+        Duration::from_secs_f64(secs)
+    }
+
+    #[test]
+    fn average_latest_times() {
+        let mut times = CaseTimes::default();
+        assert_eq!(times.average("PLL T"), None);
+        times.record("PLL T", secs(3.0));
+        for _ in 0..CASE_AVERAGE_LENGTH {
+            times.record("PLL T", secs(1.0));
+        }
+        assert_eq!(times.times()["PLL T"].len(), CASE_AVERAGE_LENGTH);
+        assert_eq!(times.average("PLL T"), Some(secs(1.0)));
+        assert_eq!(CaseTimes::new(times.times().clone()), times);
+    }
+
+    #[test]
+    fn read_saved_times() {
+        let mut times = CaseTimes::default();
+        times.record("PLL T", secs(1.5));
+        times.record("PLL T", secs(2.25));
+        times.record("OLL 21", secs(0.8));
+        let text = times.to_text();
+        assert_eq!(text, "OLL 21\t800\nPLL T\t1500\t2250\n");
+        assert_eq!(CaseTimes::from_text(&text), times);
+
+        let times = CaseTimes::from_text("PLL Y\t1000\tabc\n\nPLL E\n");
+        assert_eq!(times.times()["PLL Y"], vec![secs(1.0)]);
+        assert!(!times.times().contains_key("PLL E"));
+    }
+
+    #[test]
+    fn weigh_slow_cases() {
+        let mut times = CaseTimes::default();
+        let ids = ["PLL T", "PLL Y", "PLL Na", "PLL E"];
+        assert_eq!(times.weights(&ids), vec![1.0; 4]);
+
+        times.record("PLL T", secs(3.0));
+        times.record("PLL Y", secs(3.0));
+        times.record("PLL Na", secs(6.0));
+        assert_eq!(
+            times.weights(&ids),
+            vec![0.5625, 0.5625, 2.25, UNTIMED_WEIGHT]
+        );
+
+        // A random number of 0.5 lands in the weight of the third case.
+        let middle = |n| n / 2;
+        assert_eq!(times.choose(&ids, None, middle), Some(2));
+        assert_eq!(times.choose(&ids, Some("PLL Na"), middle), Some(3));
+        assert_eq!(times.choose(&ids[..1], Some("PLL T"), middle), Some(0));
+        assert_eq!(times.choose::<&str>(&[], None, middle), None);
+    }
+}
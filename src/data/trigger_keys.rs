@@ -0,0 +1,261 @@
+use gtk::gdk;
+
+/// An event produced by `TriggerKeys` that should be forwarded to the
+/// timer state machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerEvent {
+    /// The trigger has been pressed.
+    Press,
+    /// The trigger has been released.
+    Release,
+}
+
+/// Tracks a set of keys that act as the timer trigger.
+#[derive(Debug, Clone, Default)]
+pub struct TriggerKeys {
+    keys: Vec<gdk::Key>,
+    require_all: bool,
+    held: Vec<gdk::Key>,
+    /// Whether a `Press` has been emitted and not yet followed by a `Release`.
+    active: bool,
+    /// Whether the current activation required all keys to be held.
+    active_all: bool,
+}
+
+impl TriggerKeys {
+    /// Creates a new set of trigger keys.
+    ///
+    /// If `require_all` is `true`, the trigger is only pressed when every
+    /// key in the set is held at the same time.
+    pub fn new(keys: &[gdk::Key], require_all: bool) -> Self {
+        let mut normalized: Vec<gdk::Key> = Vec::with_capacity(keys.len());
+        for key in keys.iter().map(|k| k.to_lower()) {
+            if !normalized.contains(&key) {
+                normalized.push(key);
+            }
+        }
+        if normalized.is_empty() {
+            normalized.push(gdk::Key::space);
+        }
+
+        Self {
+            keys: normalized,
+            require_all,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new set of trigger keys from key names, as stored in the
+    /// settings. Unknown key names are ignored.
+    pub fn from_names<S: AsRef<str>>(names: &[S], require_all: bool) -> Self {
+        let keys: Vec<gdk::Key> = names
+            .iter()
+            .filter_map(|name| gdk::Key::from_name(name.as_ref()))
+            .collect();
+        Self::new(&keys, require_all)
+    }
+
+    /// Returns `true` if `key` is one of the trigger keys.
+    pub fn contains(&self, key: gdk::Key) -> bool {
+        self.keys.contains(&key.to_lower())
+    }
+
+    /// Gets the modifiers that are set by holding the trigger keys
+    /// themselves. These should be ignored when checking the modifier state
+    /// of a key event.
+    pub fn modifier_mask(&self) -> gdk::ModifierType {
+        self.keys
+            .iter()
+            .fold(gdk::ModifierType::empty(), |acc, &key| {
+                acc | modifier_of_key(key)
+            })
+    }
+
+    /// Called when a key is pressed. `timing` should be `true` if the timer
+    /// is currently running, in which case any single trigger key is enough
+    /// to press the trigger.
+    pub fn key_pressed(&mut self, key: gdk::Key, timing: bool) -> Option<TriggerEvent> {
+        let key = key.to_lower();
+        if !self.keys.contains(&key) {
+            return None;
+        }
+        if !self.held.contains(&key) {
+            self.held.push(key);
+        }
+
+        if self.active {
+            return None;
+        }
+
+        let require_all = self.require_all && !timing;
+        if (require_all && self.all_held()) || (!require_all && !self.held.is_empty()) {
+            self.active = true;
+            self.active_all = require_all;
+            Some(TriggerEvent::Press)
+        } else {
+            None
+        }
+    }
+
+    /// Called when a key is released.
+    pub fn key_released(&mut self, key: gdk::Key) -> Option<TriggerEvent> {
+        let key = key.to_lower();
+        if !self.keys.contains(&key) {
+            return None;
+        }
+        self.held.retain(|&k| k != key);
+
+        if !self.active {
+            return None;
+        }
+
+        if (self.active_all && !self.all_held()) || self.held.is_empty() {
+            self.active = false;
+            self.active_all = false;
+            Some(TriggerEvent::Release)
+        } else {
+            None
+        }
+    }
+
+    /// Releases every held key, e.g. when the timer loses focus.
+    pub fn reset(&mut self) -> Option<TriggerEvent> {
+        self.held.clear();
+        self.active_all = false;
+        if std::mem::take(&mut self.active) {
+            Some(TriggerEvent::Release)
+        } else {
+            None
+        }
+    }
+
+    fn all_held(&self) -> bool {
+        self.keys.iter().all(|key| self.held.contains(key))
+    }
+}
+
+/// Gets the modifier that is set while `key` is held, if `key` is a
+/// modifier key.
+fn modifier_of_key(key: gdk::Key) -> gdk::ModifierType {
+    match key {
+        gdk::Key::Control_L | gdk::Key::Control_R => gdk::ModifierType::CONTROL_MASK,
+        gdk::Key::Shift_L | gdk::Key::Shift_R => gdk::ModifierType::SHIFT_MASK,
+        gdk::Key::Alt_L | gdk::Key::Alt_R => gdk::ModifierType::ALT_MASK,
+        gdk::Key::Super_L | gdk::Key::Super_R => gdk::ModifierType::SUPER_MASK,
+        gdk::Key::Meta_L | gdk::Key::Meta_R => gdk::ModifierType::META_MASK,
+        gdk::Key::Hyper_L | gdk::Key::Hyper_R => gdk::ModifierType::HYPER_MASK,
+        _ => gdk::ModifierType::empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_key() {
+        let mut trigger = TriggerKeys::new(&[gdk::Key::space], false);
+
+        assert_eq!(
+            trigger.key_pressed(gdk::Key::space, false),
+            Some(TriggerEvent::Press)
+        );
+        assert_eq!(trigger.key_pressed(gdk::Key::space, false), None);
+        assert_eq!(
+            trigger.key_released(gdk::Key::space),
+            Some(TriggerEvent::Release)
+        );
+    }
+
+    #[test]
+    fn ignore_other_keys() {
+        let mut trigger = TriggerKeys::new(&[gdk::Key::space], false);
+
+        assert_eq!(trigger.key_pressed(gdk::Key::a, false), None);
+        assert_eq!(trigger.key_released(gdk::Key::a), None);
+        assert_eq!(trigger.reset(), None);
+    }
+
+    #[test]
+    fn any_key_mode() {
+        let mut trigger = TriggerKeys::new(&[gdk::Key::F13, gdk::Key::F14], false);
+
+        assert_eq!(
+            trigger.key_pressed(gdk::Key::F13, false),
+            Some(TriggerEvent::Press)
+        );
+        assert_eq!(trigger.key_pressed(gdk::Key::F14, false), None);
+        assert_eq!(trigger.key_released(gdk::Key::F13), None);
+        assert_eq!(
+            trigger.key_released(gdk::Key::F14),
+            Some(TriggerEvent::Release)
+        );
+    }
+
+    #[test]
+    fn all_keys_mode() {
+        let mut trigger = TriggerKeys::new(&[gdk::Key::Control_L, gdk::Key::Control_R], true);
+
+        assert_eq!(trigger.key_pressed(gdk::Key::Control_L, false), None);
+        assert_eq!(
+            trigger.key_pressed(gdk::Key::Control_R, false),
+            Some(TriggerEvent::Press)
+        );
+        assert_eq!(
+            trigger.key_released(gdk::Key::Control_L),
+            Some(TriggerEvent::Release)
+        );
+        assert_eq!(trigger.key_released(gdk::Key::Control_R), None);
+    }
+
+    #[test]
+    fn all_keys_mode_stops_with_any_key() {
+        let mut trigger = TriggerKeys::new(&[gdk::Key::Control_L, gdk::Key::Control_R], true);
+
+        assert_eq!(
+            trigger.key_pressed(gdk::Key::Control_R, true),
+            Some(TriggerEvent::Press)
+        );
+        assert_eq!(trigger.key_pressed(gdk::Key::Control_L, false), None);
+        assert_eq!(trigger.key_released(gdk::Key::Control_R), None);
+        assert_eq!(
+            trigger.key_released(gdk::Key::Control_L),
+            Some(TriggerEvent::Release)
+        );
+    }
+
+    #[test]
+    fn reset_releases_trigger() {
+        let mut trigger = TriggerKeys::new(&[gdk::Key::space], false);
+
+        trigger.key_pressed(gdk::Key::space, false);
+        assert_eq!(trigger.reset(), Some(TriggerEvent::Release));
+        assert_eq!(trigger.reset(), None);
+    }
+
+    #[test]
+    fn modifier_mask() {
+        let trigger = TriggerKeys::new(&[gdk::Key::Control_L, gdk::Key::space], true);
+
+        assert_eq!(trigger.modifier_mask(), gdk::ModifierType::CONTROL_MASK);
+    }
+
+    #[test]
+    fn empty_keys_default_to_space() {
+        let mut trigger = TriggerKeys::new(&[], false);
+
+        assert!(trigger.contains(gdk::Key::space));
+        assert_eq!(
+            trigger.key_pressed(gdk::Key::space, false),
+            Some(TriggerEvent::Press)
+        );
+    }
+
+    #[test]
+    fn from_names_ignores_unknown_keys() {
+        let trigger = TriggerKeys::from_names(&["Control_L", "not-a-key"], true);
+
+        assert!(trigger.contains(gdk::Key::Control_L));
+        assert!(!trigger.contains(gdk::Key::space));
+    }
+}
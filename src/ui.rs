@@ -1,15 +1,53 @@
+pub use accel_capture_row::AccelCaptureRow;
+pub use calendar_heatmap::CalendarHeatmap;
+pub use cube_view::CubeView;
+pub use date_statistics_window::DateStatisticsWindow;
+pub use distribution_chart::DistributionChart;
+pub use fewest_moves_view::FewestMovesView;
+pub use goals_window::GoalsWindow;
+pub use key_capture_row::KeyCaptureRow;
+pub use mock_competition_dialog::MockCompetitionDialog;
+pub use multi_blind_dialog::MultiBlindDialog;
+pub use pb_history_window::PbHistoryWindow;
 pub use penalty_selector::PenaltySelector;
-pub use preferences_window::PreferencesWindow;
+pub use practice_window::{practice_summary, PracticeWindow};
+pub use preferences_window::{running_display, time_precision, PreferencesWindow};
+pub use recognition_window::RecognitionWindow;
+pub use report_dialog::{report_format, ReportDialog};
+pub use session_comparison_window::SessionComparisonWindow;
 pub use session_item_row::SessionItemRow;
+pub use session_report_printer::export_session_report_pdf;
 pub use solve_dialog::SolveDialog;
+pub use solve_filter_bar::SolveFilterBar;
+pub use tag_statistics_window::TagStatisticsWindow;
 pub use text_area_row::TextAreaRow;
 pub use time_label::TimeLabel;
 pub use timer_face::TimerFace;
+pub use trainer_window::TrainerWindow;
 
+mod accel_capture_row;
+mod calendar_heatmap;
+mod cube_view;
+mod date_statistics_window;
+mod distribution_chart;
+mod fewest_moves_view;
+mod goals_window;
+mod key_capture_row;
+mod mock_competition_dialog;
+mod multi_blind_dialog;
+mod pb_history_window;
 mod penalty_selector;
+mod practice_window;
 mod preferences_window;
+mod recognition_window;
+mod report_dialog;
+mod session_comparison_window;
 mod session_item_row;
+mod session_report_printer;
 mod solve_dialog;
+mod solve_filter_bar;
+mod tag_statistics_window;
 mod text_area_row;
 mod time_label;
 mod timer_face;
+mod trainer_window;
@@ -0,0 +1,158 @@
+use adw::prelude::*;
+use adw::subclass::prelude::*;
+use gtk::{gdk, glib};
+
+#[doc(hidden)]
+mod imp {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[derive(Debug, Default, gtk::CompositeTemplate, glib::Properties)]
+    #[template(resource = "/io/github/manenfu/PrismaTimer/ui/accel_capture_row.ui")]
+    #[properties(wrapper_type = super::AccelCaptureRow)]
+    pub struct AccelCaptureRow {
+        #[template_child]
+        pub accel_label: TemplateChild<gtk::ShortcutLabel>,
+        #[template_child]
+        pub record_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub reset_button: TemplateChild<gtk::Button>,
+
+        /// The captured accelerator, or an empty string if there is none.
+        #[property(get, set = Self::set_accelerator)]
+        pub accelerator: RefCell<String>,
+        /// The accelerator that the reset button restores.
+        #[property(get, set = Self::set_default_accelerator)]
+        pub default_accelerator: RefCell<String>,
+    }
+
+    impl AccelCaptureRow {
+        fn set_accelerator(&self, v: String) {
+            self.accelerator.replace(v);
+            self.obj().update_accel_label();
+        }
+
+        fn set_default_accelerator(&self, v: String) {
+            self.default_accelerator.replace(v);
+            self.obj().update_accel_label();
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for AccelCaptureRow {
+        const NAME: &'static str = "PtAccelCaptureRow";
+        type Type = super::AccelCaptureRow;
+        type ParentType = adw::ActionRow;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_instance_callbacks();
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for AccelCaptureRow {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            obj.setup_event_controllers();
+            obj.update_accel_label();
+        }
+    }
+
+    impl WidgetImpl for AccelCaptureRow {}
+    impl ListBoxRowImpl for AccelCaptureRow {}
+    impl PreferencesRowImpl for AccelCaptureRow {}
+    impl ActionRowImpl for AccelCaptureRow {}
+}
+
+glib::wrapper! {
+    /// A row that records a key combination used as an accelerator.
+    pub struct AccelCaptureRow(ObjectSubclass<imp::AccelCaptureRow>)
+        @extends gtk::Widget, gtk::ListBoxRow, adw::PreferencesRow, adw::ActionRow,
+        @implements gtk::Accessible, gtk::Actionable, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+#[gtk::template_callbacks]
+impl AccelCaptureRow {
+    pub fn new() -> Self {
+        glib::Object::builder().build()
+    }
+
+    fn setup_event_controllers(&self) {
+        // Capture the keys before they activate accelerators of the window.
+        let key_events = gtk::EventControllerKey::new();
+        key_events.set_propagation_phase(gtk::PropagationPhase::Capture);
+        key_events.connect_key_pressed(glib::clone!(@weak self as obj => @default-return glib::Propagation::Proceed, move |_, key, _, modifier| {
+            obj.key_pressed_cb(key, modifier)
+        }));
+        self.add_controller(key_events);
+    }
+
+    fn is_recording(&self) -> bool {
+        self.imp().record_button.is_active()
+    }
+
+    fn key_pressed_cb(&self, key: gdk::Key, modifier: gdk::ModifierType) -> glib::Propagation {
+        let imp = self.imp();
+        if !self.is_recording() {
+            return glib::Propagation::Proceed;
+        }
+
+        let modifier = modifier & gtk::accelerator_get_default_mod_mask();
+        match key {
+            gdk::Key::Escape if modifier.is_empty() => {
+                imp.record_button.set_active(false);
+            }
+            gdk::Key::BackSpace if modifier.is_empty() => {
+                imp.record_button.set_active(false);
+                self.set_accelerator("");
+            }
+            // Wait for a key other than a modifier.
+            _ if !gtk::accelerator_valid(key.to_lower(), modifier) => {}
+            _ => {
+                imp.record_button.set_active(false);
+                self.set_accelerator(gtk::accelerator_name(key.to_lower(), modifier));
+            }
+        }
+        glib::Propagation::Stop
+    }
+
+    #[template_callback]
+    fn record_button_toggled_cb(&self, button: &gtk::ToggleButton) {
+        let imp = self.imp();
+        if button.is_active() {
+            imp.accel_label.set_disabled_text("Press keys…");
+            imp.accel_label.set_accelerator("");
+        } else {
+            self.update_accel_label();
+        }
+    }
+
+    #[template_callback]
+    fn reset_button_clicked_cb(&self, _button: &gtk::Button) {
+        self.set_accelerator(self.default_accelerator());
+    }
+
+    fn update_accel_label(&self) {
+        let imp = self.imp();
+        let accelerator = self.accelerator();
+        // An empty accelerator shows the disabled text.
+        imp.accel_label.set_disabled_text("Disabled");
+        imp.accel_label.set_accelerator(&accelerator);
+        imp.reset_button
+            .set_visible(accelerator != *imp.default_accelerator.borrow());
+    }
+}
+
+impl Default for AccelCaptureRow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+
+use adw::prelude::*;
+use adw::subclass::prelude::*;
+use chrono::{Days, NaiveDate};
+use gtk::{gdk, glib, graphene};
+
+use crate::data::DatePeriod;
+
+/// Number of weeks shown, ending with the current week.
+const N_WEEKS: u32 = 26;
+/// Size of the cell of a day, in pixels.
+const CELL_SIZE: f32 = 12.0;
+/// Space between cells, in pixels.
+const CELL_SPACING: f32 = 3.0;
+
+#[doc(hidden)]
+mod imp {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct CalendarHeatmap {
+        pub(super) solves_per_day: RefCell<BTreeMap<NaiveDate, usize>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for CalendarHeatmap {
+        const NAME: &'static str = "PtCalendarHeatmap";
+        type Type = super::CalendarHeatmap;
+        type ParentType = gtk::Widget;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.set_css_name("calendar-heatmap");
+        }
+    }
+
+    impl ObjectImpl for CalendarHeatmap {
+        fn constructed(&self) {
+            self.parent_constructed();
+
+            let obj = self.obj();
+            obj.set_has_tooltip(true);
+            obj.connect_query_tooltip(|obj, x, y, _keyboard, tooltip| {
+                let Some(date) = obj.date_at(x as f32, y as f32) else {
+                    return false;
+                };
+                let n_solves = obj.imp().n_solves(date);
+                let solves = if n_solves == 1 { "solve" } else { "solves" };
+                tooltip.set_text(Some(&format!(
+                    "{}: {} {}",
+                    DatePeriod::Day.format(date),
+                    n_solves,
+                    solves
+                )));
+                true
+            });
+        }
+    }
+
+    impl WidgetImpl for CalendarHeatmap {
+        fn measure(&self, orientation: gtk::Orientation, _for_size: i32) -> (i32, i32, i32, i32) {
+            let n_cells = match orientation {
+                gtk::Orientation::Horizontal => N_WEEKS,
+                _ => 7,
+            };
+            let size = (n_cells as f32 * (CELL_SIZE + CELL_SPACING) - CELL_SPACING) as i32;
+            (size, size, -1, -1)
+        }
+
+        fn snapshot(&self, snapshot: &gtk::Snapshot) {
+            let obj = self.obj();
+            let color = obj.color();
+            let max = self
+                .solves_per_day
+                .borrow()
+                .values()
+                .copied()
+                .max()
+                .unwrap_or(0)
+                .max(1);
+
+            for (week, weekday, date) in obj.days() {
+                let n_solves = self.n_solves(date);
+                // Empty days are faint, the busiest day is fully colored.
+                let alpha = if n_solves == 0 {
+                    0.1
+                } else {
+                    0.3 + 0.7 * n_solves as f32 / max as f32
+                };
+                let cell_color = gdk::RGBA::new(color.red(), color.green(), color.blue(), alpha);
+                snapshot.append_color(&cell_color, &cell_rect(week, weekday));
+            }
+        }
+    }
+
+    impl CalendarHeatmap {
+        pub(super) fn n_solves(&self, date: NaiveDate) -> usize {
+            self.solves_per_day
+                .borrow()
+                .get(&date)
+                .copied()
+                .unwrap_or(0)
+        }
+    }
+}
+
+glib::wrapper! {
+    /// A calendar of the last weeks, coloring each day by the number of
+    /// solves done on it.
+    pub struct CalendarHeatmap(ObjectSubclass<imp::CalendarHeatmap>)
+        @extends gtk::Widget,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl CalendarHeatmap {
+    pub fn new() -> Self {
+        glib::Object::builder().build()
+    }
+
+    /// Sets the number of solves done on each day.
+    pub fn set_solves_per_day(&self, solves_per_day: BTreeMap<NaiveDate, usize>) {
+        self.imp().solves_per_day.replace(solves_per_day);
+        self.queue_draw();
+    }
+
+    /// Gets the column, row and date of each day shown, up to today.
+    fn days(&self) -> Vec<(u32, u32, NaiveDate)> {
+        let today = chrono::Local::now().date_naive();
+        let first = DatePeriod::Week.start_of(today) - Days::new(7 * (N_WEEKS as u64 - 1));
+
+        (0..N_WEEKS)
+            .flat_map(|week| (0..7).map(move |weekday| (week, weekday)))
+            .map(|(week, weekday)| {
+                let date = first + Days::new((week * 7 + weekday) as u64);
+                (week, weekday, date)
+            })
+            .take_while(|&(_, _, date)| date <= today)
+            .collect()
+    }
+
+    /// Gets the day of the cell at this position, if any.
+    fn date_at(&self, x: f32, y: f32) -> Option<NaiveDate> {
+        self.days()
+            .into_iter()
+            .find(|&(week, weekday, _)| {
+                cell_rect(week, weekday).contains_point(&graphene::Point::new(x, y))
+            })
+            .map(|(_, _, date)| date)
+    }
+}
+
+impl Default for CalendarHeatmap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Gets the area of the cell of a day.
+fn cell_rect(week: u32, weekday: u32) -> graphene::Rect {
+    graphene::Rect::new(
+        week as f32 * (CELL_SIZE + CELL_SPACING),
+        weekday as f32 * (CELL_SIZE + CELL_SPACING),
+        CELL_SIZE,
+        CELL_SIZE,
+    )
+}
@@ -0,0 +1,143 @@
+use adw::prelude::*;
+use adw::subclass::prelude::*;
+use gtk::{gdk, glib, graphene};
+
+use crate::data::{Cube, Face};
+
+/// Smallest size of the view, in pixels.
+const MIN_SIZE: i32 = 160;
+/// Share of the view the cube takes, leaving a margin around it.
+const CUBE_SCALE: f32 = 0.9;
+/// Space between stickers, as a share of a sticker.
+const STICKER_GAP: f32 = 0.06;
+
+/// Color of the body of the cube between stickers.
+const BODY_COLOR: [f32; 4] = [0.118, 0.118, 0.118, 1.0];
+/// Colors of the stickers, in the usual color scheme with white on U and
+/// green on F.
+const U_COLOR: [f32; 4] = [0.98, 0.98, 0.98, 1.0];
+const D_COLOR: [f32; 4] = [0.965, 0.827, 0.176, 1.0];
+const L_COLOR: [f32; 4] = [1.0, 0.471, 0.0, 1.0];
+const R_COLOR: [f32; 4] = [0.878, 0.106, 0.141, 1.0];
+const F_COLOR: [f32; 4] = [0.18, 0.761, 0.494, 1.0];
+const B_COLOR: [f32; 4] = [0.208, 0.518, 0.894, 1.0];
+
+/// Cosine of 30°, how much of a sticker edge along the x or z axis shows
+/// horizontally in an isometric view.
+const COS_30: f32 = 0.866_025_4;
+
+fn sticker_color(face: Face) -> gdk::RGBA {
+    let [red, green, blue, alpha] = match face {
+        Face::U => U_COLOR,
+        Face::D => D_COLOR,
+        Face::L => L_COLOR,
+        Face::R => R_COLOR,
+        Face::F => F_COLOR,
+        Face::B => B_COLOR,
+    };
+    gdk::RGBA::new(red, green, blue, alpha)
+}
+
+#[doc(hidden)]
+mod imp {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct CubeView {
+        pub(super) cube: RefCell<Option<Cube>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for CubeView {
+        const NAME: &'static str = "PtCubeView";
+        type Type = super::CubeView;
+        type ParentType = gtk::Widget;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.set_css_name("cube-view");
+        }
+    }
+
+    impl ObjectImpl for CubeView {}
+
+    impl WidgetImpl for CubeView {
+        fn measure(&self, _orientation: gtk::Orientation, _for_size: i32) -> (i32, i32, i32, i32) {
+            (MIN_SIZE, MIN_SIZE, -1, -1)
+        }
+
+        fn snapshot(&self, snapshot: &gtk::Snapshot) {
+            let cube = self.cube.borrow();
+            let Some(cube) = cube.as_ref() else {
+                return;
+            };
+
+            // The cube is 3 stickers wide along each axis, so its outline is
+            // 6 × cos 30° stickers wide and 6 stickers high.
+            let obj = self.obj();
+            let width = obj.width() as f32;
+            let height = obj.height() as f32;
+            let s = (width / (6.0 * COS_30)).min(height / 6.0) * CUBE_SCALE;
+            let (c, h) = (COS_30 * s, 0.5 * s);
+
+            // Each face is drawn as a 3 × 3 square of stickers, mapped onto
+            // the view by the directions of its rows and columns, and the
+            // corner of its first sticker from the center of the cube.
+            let faces = [
+                (Face::U, [c, h, -c, h, 0.0, -3.0 * h - 1.5 * s]),
+                (Face::F, [c, h, 0.0, s, -3.0 * c, -1.5 * s]),
+                (Face::R, [c, -h, 0.0, s, 0.0, 3.0 * h - 1.5 * s]),
+            ];
+            let [red, green, blue, alpha] = BODY_COLOR;
+            let body_color = gdk::RGBA::new(red, green, blue, alpha);
+            for (face, [xx, yx, xy, yy, x0, y0]) in faces {
+                snapshot.save();
+                snapshot.translate(&graphene::Point::new(width / 2.0, height / 2.0));
+                snapshot.transform_matrix(&graphene::Matrix::from_2d(
+                    xx as f64, yx as f64, xy as f64, yy as f64, x0 as f64, y0 as f64,
+                ));
+                snapshot.append_color(&body_color, &graphene::Rect::new(0.0, 0.0, 3.0, 3.0));
+                for (i, color) in cube.face_colors(face).into_iter().enumerate() {
+                    let (row, column) = ((i / 3) as f32, (i % 3) as f32);
+                    snapshot.append_color(
+                        &sticker_color(color),
+                        &graphene::Rect::new(
+                            column + STICKER_GAP,
+                            row + STICKER_GAP,
+                            1.0 - 2.0 * STICKER_GAP,
+                            1.0 - 2.0 * STICKER_GAP,
+                        ),
+                    );
+                }
+                snapshot.restore();
+            }
+        }
+    }
+}
+
+glib::wrapper! {
+    /// A drawing of a cube from above its front right corner, showing the
+    /// U, F and R faces.
+    pub struct CubeView(ObjectSubclass<imp::CubeView>)
+        @extends gtk::Widget,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl CubeView {
+    pub fn new() -> Self {
+        glib::Object::builder().build()
+    }
+
+    /// Sets the cube to draw, or nothing.
+    pub fn set_cube(&self, cube: Option<Cube>) {
+        self.imp().cube.replace(cube);
+        self.queue_draw();
+    }
+}
+
+impl Default for CubeView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,161 @@
+use crate::data;
+use crate::ui;
+use adw::prelude::*;
+use adw::subclass::prelude::*;
+use gtk::{gdk, glib};
+
+#[doc(hidden)]
+mod imp {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[derive(Debug, Default, gtk::CompositeTemplate, glib::Properties)]
+    #[template(resource = "/io/github/manenfu/PrismaTimer/ui/date_statistics_window.ui")]
+    #[properties(wrapper_type = super::DateStatisticsWindow)]
+    pub struct DateStatisticsWindow {
+        #[template_child]
+        pub stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub heatmap: TemplateChild<ui::CalendarHeatmap>,
+        #[template_child]
+        pub today_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub week_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub month_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub days_group: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        pub weeks_group: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        pub months_group: TemplateChild<adw::PreferencesGroup>,
+
+        #[property(get, set = Self::set_session, nullable)]
+        pub session: RefCell<Option<data::Session>>,
+        session_handler: RefCell<Option<glib::SignalHandlerId>>,
+
+        /// Rows of the days, weeks and months, with the group they are in.
+        pub(super) rows: RefCell<Vec<(adw::PreferencesGroup, adw::ActionRow)>>,
+    }
+
+    impl DateStatisticsWindow {
+        fn set_session(&self, v: Option<data::Session>) {
+            let obj = self.obj();
+
+            if let Some(session) = self.session.take() {
+                if let Some(id) = self.session_handler.take() {
+                    session.disconnect(id);
+                }
+            }
+
+            if let Some(session) = &v {
+                self.session_handler
+                    .replace(Some(session.connect_items_changed(
+                        glib::clone!(@weak obj => move |_, _, _, _| {
+                            obj.update_statistics();
+                        }),
+                    )));
+            }
+            self.session.replace(v);
+            obj.update_statistics();
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for DateStatisticsWindow {
+        const NAME: &'static str = "PtDateStatisticsWindow";
+        type Type = super::DateStatisticsWindow;
+        type ParentType = adw::Window;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+
+            klass.add_binding_action(
+                gdk::Key::Escape,
+                gdk::ModifierType::empty(),
+                "window.close",
+                None,
+            );
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for DateStatisticsWindow {
+        fn dispose(&self) {
+            if let Some(session) = self.session.take() {
+                if let Some(id) = self.session_handler.take() {
+                    session.disconnect(id);
+                }
+            }
+        }
+    }
+
+    impl WidgetImpl for DateStatisticsWindow {}
+    impl WindowImpl for DateStatisticsWindow {}
+    impl AdwWindowImpl for DateStatisticsWindow {}
+}
+
+glib::wrapper! {
+    /// A window showing statistics of a session for each day, week and
+    /// month, and a calendar of the days solves were done on.
+    pub struct DateStatisticsWindow(ObjectSubclass<imp::DateStatisticsWindow>)
+        @extends gtk::Widget, gtk::Window, adw::Window;
+}
+
+impl DateStatisticsWindow {
+    pub fn new(session: &data::Session) -> Self {
+        glib::Object::builder().property("session", session).build()
+    }
+
+    /// Rebuilds the statistics of every period, newest first.
+    fn update_statistics(&self) {
+        let imp = self.imp();
+        for (group, row) in imp.rows.take() {
+            group.remove(&row);
+        }
+
+        let Some(session) = self.session().filter(|s| s.n_items() > 0) else {
+            imp.stack.set_visible_child_name("empty");
+            return;
+        };
+        imp.stack.set_visible_child_name("statistics");
+
+        imp.heatmap.set_solves_per_day(session.solves_per_day());
+
+        let today = chrono::Local::now().date_naive();
+        let precision = session.time_precision();
+        let periods = [
+            (data::DatePeriod::Day, &imp.today_row, &imp.days_group),
+            (data::DatePeriod::Week, &imp.week_row, &imp.weeks_group),
+            (data::DatePeriod::Month, &imp.month_row, &imp.months_group),
+        ];
+
+        let mut rows = Vec::new();
+        for (period, current_row, group) in periods {
+            let summaries = session.period_summaries(period);
+
+            let current = summaries
+                .last()
+                .filter(|summary| summary.start == period.start_of(today));
+            current_row.set_subtitle(&current.map_or("No solves".to_string(), |summary| {
+                summary.description(precision)
+            }));
+
+            for summary in summaries.iter().rev() {
+                let row = adw::ActionRow::builder()
+                    .title(period.format(summary.start))
+                    .subtitle(summary.description(precision))
+                    .subtitle_selectable(true)
+                    .build();
+                group.add(&row);
+                rows.push((group.get(), row));
+            }
+        }
+        imp.rows.replace(rows);
+    }
+}
@@ -0,0 +1,142 @@
+use adw::prelude::*;
+use adw::subclass::prelude::*;
+use gtk::{gdk, glib, graphene};
+
+use crate::data::Histogram;
+
+/// Smallest width of the chart, in pixels.
+const MIN_WIDTH: i32 = 240;
+/// Height of the chart, in pixels.
+const HEIGHT: i32 = 160;
+/// Space between bars, in pixels.
+const BAR_SPACING: f32 = 2.0;
+
+/// Color of the distribution of the first set of solves, a translucent blue.
+const FIRST_COLOR: [f32; 4] = [0.208, 0.518, 0.894, 0.6];
+/// Color of the distribution of the second set of solves, a translucent
+/// orange.
+const SECOND_COLOR: [f32; 4] = [1.0, 0.471, 0.0, 0.6];
+
+#[doc(hidden)]
+mod imp {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct DistributionChart {
+        pub(super) histograms: RefCell<Option<(Histogram, Histogram)>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for DistributionChart {
+        const NAME: &'static str = "PtDistributionChart";
+        type Type = super::DistributionChart;
+        type ParentType = gtk::Widget;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.set_css_name("distribution-chart");
+        }
+    }
+
+    impl ObjectImpl for DistributionChart {
+        fn constructed(&self) {
+            self.parent_constructed();
+
+            let obj = self.obj();
+            obj.set_has_tooltip(true);
+            obj.connect_query_tooltip(|obj, x, _y, _keyboard, tooltip| {
+                let histograms = obj.imp().histograms.borrow();
+                let Some((first, second)) = histograms.as_ref() else {
+                    return false;
+                };
+                let bar_width = obj.width() as f64 / first.counts.len() as f64;
+                let i = (x as f64 / bar_width) as usize;
+                if i >= first.counts.len() {
+                    return false;
+                }
+                tooltip.set_text(Some(&format!(
+                    "{}: {} first, {} second",
+                    first.bin_label(i),
+                    first.counts[i],
+                    second.counts[i]
+                )));
+                true
+            });
+        }
+    }
+
+    impl WidgetImpl for DistributionChart {
+        fn measure(&self, orientation: gtk::Orientation, _for_size: i32) -> (i32, i32, i32, i32) {
+            match orientation {
+                gtk::Orientation::Horizontal => (MIN_WIDTH, MIN_WIDTH, -1, -1),
+                _ => (HEIGHT, HEIGHT, -1, -1),
+            }
+        }
+
+        fn snapshot(&self, snapshot: &gtk::Snapshot) {
+            let histograms = self.histograms.borrow();
+            let Some((first, second)) = histograms.as_ref() else {
+                return;
+            };
+
+            // Sets can have any number of solves, so bars show the share of
+            // each set in a bin rather than the count.
+            let fractions = [
+                (first.fractions(), FIRST_COLOR),
+                (second.fractions(), SECOND_COLOR),
+            ];
+            let max = fractions
+                .iter()
+                .flat_map(|(fractions, _)| fractions.iter().copied())
+                .fold(0.0, f64::max)
+                .max(f64::EPSILON);
+
+            let obj = self.obj();
+            let width = obj.width() as f32;
+            let height = obj.height() as f32;
+            let bar_width = width / first.counts.len() as f32;
+            for (fractions, [red, green, blue, alpha]) in fractions {
+                let color = gdk::RGBA::new(red, green, blue, alpha);
+                for (i, fraction) in fractions.iter().enumerate() {
+                    let bar_height = height * (fraction / max) as f32;
+                    snapshot.append_color(
+                        &color,
+                        &graphene::Rect::new(
+                            bar_width * i as f32 + BAR_SPACING / 2.0,
+                            height - bar_height,
+                            (bar_width - BAR_SPACING).max(1.0),
+                            bar_height,
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
+
+glib::wrapper! {
+    /// A histogram of two sets of solves drawn over each other, to compare
+    /// their distributions.
+    pub struct DistributionChart(ObjectSubclass<imp::DistributionChart>)
+        @extends gtk::Widget,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl DistributionChart {
+    pub fn new() -> Self {
+        glib::Object::builder().build()
+    }
+
+    /// Sets the histograms of both sets, which must have the same bins.
+    pub fn set_histograms(&self, histograms: Option<(Histogram, Histogram)>) {
+        self.imp().histograms.replace(histograms);
+        self.queue_draw();
+    }
+}
+
+impl Default for DistributionChart {
+    fn default() -> Self {
+        Self::new()
+    }
+}
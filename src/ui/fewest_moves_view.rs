@@ -0,0 +1,260 @@
+use std::time::{Duration, Instant};
+
+use crate::{data, ui};
+use adw::subclass::prelude::*;
+use gtk::glib;
+use gtk::prelude::*;
+
+/// Interval between updates of the countdown.
+const COUNTDOWN_INTERVAL: Duration = Duration::from_millis(200);
+
+#[doc(hidden)]
+mod imp {
+    use std::cell::{Cell, RefCell};
+
+    use super::*;
+
+    #[derive(Debug, Default, gtk::CompositeTemplate, glib::Properties)]
+    #[template(resource = "/io/github/manenfu/PrismaTimer/ui/fewest_moves_view.ui")]
+    #[properties(wrapper_type = super::FewestMovesView)]
+    pub struct FewestMovesView {
+        #[template_child]
+        pub countdown_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub scramble_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub solution_row: TemplateChild<ui::TextAreaRow>,
+        #[template_child]
+        pub move_count_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub error_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub start_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub submit_button: TemplateChild<gtk::Button>,
+
+        #[property(get, set = Self::set_session)]
+        pub session: RefCell<Option<data::Session>>,
+
+        /// The scramble of the current attempt.
+        pub(super) scramble: RefCell<Vec<data::Move>>,
+        /// When the current attempt has started.
+        pub(super) started: Cell<Option<Instant>>,
+        pub(super) countdown_source: RefCell<Option<glib::SourceId>>,
+    }
+
+    impl FewestMovesView {
+        fn set_session(&self, v: Option<data::Session>) {
+            let obj = self.obj();
+            obj.stop_attempt();
+            self.session.replace(v);
+            obj.reset();
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for FewestMovesView {
+        const NAME: &'static str = "PtFewestMovesView";
+        type Type = super::FewestMovesView;
+        type ParentType = adw::Bin;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_instance_callbacks();
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for FewestMovesView {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            obj.add_css_class("fewest-moves-view");
+            obj.setup_callbacks();
+            obj.reset();
+        }
+
+        fn dispose(&self) {
+            if let Some(source) = self.countdown_source.take() {
+                source.remove();
+            }
+        }
+    }
+
+    impl WidgetImpl for FewestMovesView {}
+    impl BinImpl for FewestMovesView {}
+}
+
+glib::wrapper! {
+    /// A view to practice the Fewest Moves Challenge: it reveals a scramble,
+    /// counts down the time limit and checks the written solution.
+    pub struct FewestMovesView(ObjectSubclass<imp::FewestMovesView>)
+        @extends gtk::Widget, adw::Bin,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+#[gtk::template_callbacks]
+impl FewestMovesView {
+    pub fn new() -> Self {
+        glib::Object::builder().build()
+    }
+
+    fn setup_callbacks(&self) {
+        let imp = self.imp();
+        imp.solution_row
+            .buffer()
+            .connect_changed(glib::clone!(@weak self as obj => move |_| {
+                obj.solution_changed_cb();
+            }));
+    }
+
+    fn solution(&self) -> String {
+        let buffer = self.imp().solution_row.buffer();
+        buffer
+            .text(&buffer.start_iter(), &buffer.end_iter(), false)
+            .to_string()
+    }
+
+    fn is_running(&self) -> bool {
+        self.imp().started.get().is_some()
+    }
+
+    /// Shows the view as it is before an attempt.
+    fn reset(&self) {
+        let imp = self.imp();
+        imp.scramble.replace(Vec::new());
+        imp.scramble_label
+            .set_label("Start an attempt to reveal the scramble");
+        imp.solution_row.buffer().set_text("");
+        imp.error_label.set_visible(false);
+        imp.move_count_label.set_label("");
+        self.update_countdown(data::FEWEST_MOVES_TIME_LIMIT);
+        self.update_buttons();
+    }
+
+    fn update_buttons(&self) {
+        let imp = self.imp();
+        let running = self.is_running();
+        imp.start_button.set_visible(!running);
+        imp.start_button.set_sensitive(self.session().is_some());
+        imp.submit_button.set_visible(running);
+        imp.solution_row.set_editable(running);
+    }
+
+    fn update_countdown(&self, remaining: Duration) {
+        let seconds = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+        self.imp()
+            .countdown_label
+            .set_label(&format!("{}:{:02}", seconds / 60, seconds % 60));
+    }
+
+    fn start_attempt(&self) {
+        let imp = self.imp();
+        self.reset();
+
+        let scramble = data::generate_fewest_moves_scramble();
+        imp.scramble_label.set_label(&data::format_moves(&scramble));
+        imp.scramble.replace(scramble);
+        imp.started.set(Some(Instant::now()));
+
+        let source = glib::timeout_add_local(
+            COUNTDOWN_INTERVAL,
+            glib::clone!(@weak self as obj => @default-return glib::ControlFlow::Break, move || {
+                obj.countdown_cb()
+            }),
+        );
+        imp.countdown_source.replace(Some(source));
+        self.update_buttons();
+        imp.solution_row.grab_focus();
+    }
+
+    /// Stops the current attempt and returns its duration.
+    fn stop_attempt(&self) -> Option<Duration> {
+        let imp = self.imp();
+        if let Some(source) = imp.countdown_source.take() {
+            source.remove();
+        }
+        let elapsed = imp.started.take().map(|started| started.elapsed());
+        self.update_buttons();
+        elapsed
+    }
+
+    fn countdown_cb(&self) -> glib::ControlFlow {
+        let Some(started) = self.imp().started.get() else {
+            return glib::ControlFlow::Break;
+        };
+
+        let remaining = data::FEWEST_MOVES_TIME_LIMIT.saturating_sub(started.elapsed());
+        self.update_countdown(remaining);
+        if remaining.is_zero() {
+            // The source is removed by returning `Break`.
+            self.imp().countdown_source.take();
+            self.submit_solution();
+            return glib::ControlFlow::Break;
+        }
+        glib::ControlFlow::Continue
+    }
+
+    /// Checks the solution and adds the attempt to the session.
+    fn submit_solution(&self) {
+        let imp = self.imp();
+        let Some(elapsed) = self.stop_attempt() else {
+            return;
+        };
+
+        let scramble = imp.scramble.borrow().clone();
+        let solution = self.solution();
+        let result = data::FewestMovesResult::new(&scramble, &solution);
+        if let Some(session) = self.session() {
+            session.add_solve(data::SolveData::from_fewest_moves(
+                result,
+                elapsed.min(data::FEWEST_MOVES_TIME_LIMIT),
+                data::format_moves(&scramble),
+            ));
+        }
+        self.solution_changed_cb();
+    }
+
+    fn solution_changed_cb(&self) {
+        let imp = self.imp();
+        if imp.scramble.borrow().is_empty() {
+            return;
+        }
+
+        let solution = self.solution();
+        let count = data::parse_moves(&solution).map(|moves| data::htm_count(&moves));
+        imp.move_count_label
+            .set_label(&count.map_or(String::default(), |c| c.to_string()));
+
+        match data::check_solution(&imp.scramble.borrow(), &solution) {
+            Ok(_) => {
+                imp.error_label.set_visible(false);
+            }
+            Err(e) => {
+                imp.error_label.set_label(&e.to_string());
+                imp.error_label.set_visible(!solution.trim().is_empty());
+            }
+        }
+    }
+
+    #[template_callback]
+    fn start_button_clicked_cb(&self, _button: &gtk::Button) {
+        self.start_attempt();
+    }
+
+    #[template_callback]
+    fn submit_button_clicked_cb(&self, _button: &gtk::Button) {
+        self.submit_solution();
+    }
+}
+
+impl Default for FewestMovesView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,186 @@
+use crate::data;
+use adw::prelude::*;
+use adw::subclass::prelude::*;
+use gtk::{gdk, glib};
+
+#[doc(hidden)]
+mod imp {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[derive(Debug, Default, gtk::CompositeTemplate, glib::Properties)]
+    #[template(resource = "/io/github/manenfu/PrismaTimer/ui/goals_window.ui")]
+    #[properties(wrapper_type = super::GoalsWindow)]
+    pub struct GoalsWindow {
+        #[template_child]
+        pub goal_row: TemplateChild<adw::EntryRow>,
+        #[template_child]
+        pub goals_group: TemplateChild<adw::PreferencesGroup>,
+
+        #[property(get, set = Self::set_session, nullable)]
+        pub session: RefCell<Option<data::Session>>,
+        session_handlers: RefCell<Vec<glib::SignalHandlerId>>,
+
+        /// Rows of the list, one for each goal.
+        pub(super) rows: RefCell<Vec<adw::ActionRow>>,
+    }
+
+    impl GoalsWindow {
+        fn set_session(&self, v: Option<data::Session>) {
+            let obj = self.obj();
+
+            self.disconnect_session();
+
+            if let Some(session) = &v {
+                let items_handler =
+                    session.connect_items_changed(glib::clone!(@weak obj => move |_, _, _, _| {
+                        obj.update_goals();
+                    }));
+                let goals_handler = session.connect_closure(
+                    "goals-changed",
+                    false,
+                    glib::closure_local!(@watch obj => move |_: &data::Session| {
+                        obj.update_goals();
+                    }),
+                );
+                self.session_handlers
+                    .replace(vec![items_handler, goals_handler]);
+            }
+            self.session.replace(v);
+            obj.update_goals();
+        }
+
+        fn disconnect_session(&self) {
+            if let Some(session) = self.session.take() {
+                for id in self.session_handlers.take() {
+                    session.disconnect(id);
+                }
+            }
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for GoalsWindow {
+        const NAME: &'static str = "PtGoalsWindow";
+        type Type = super::GoalsWindow;
+        type ParentType = adw::Window;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_instance_callbacks();
+
+            klass.add_binding_action(
+                gdk::Key::Escape,
+                gdk::ModifierType::empty(),
+                "window.close",
+                None,
+            );
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for GoalsWindow {
+        fn dispose(&self) {
+            self.disconnect_session();
+        }
+    }
+
+    impl WidgetImpl for GoalsWindow {}
+    impl WindowImpl for GoalsWindow {}
+    impl AdwWindowImpl for GoalsWindow {}
+}
+
+glib::wrapper! {
+    /// A window listing the goals of a session with the progress towards
+    /// each, where goals are added and removed.
+    pub struct GoalsWindow(ObjectSubclass<imp::GoalsWindow>)
+        @extends gtk::Widget, gtk::Window, adw::Window;
+}
+
+#[gtk::template_callbacks]
+impl GoalsWindow {
+    pub fn new(session: &data::Session) -> Self {
+        glib::Object::builder().property("session", session).build()
+    }
+
+    /// Rebuilds the list of goals with their progress.
+    fn update_goals(&self) {
+        let imp = self.imp();
+        for row in imp.rows.take() {
+            imp.goals_group.remove(&row);
+        }
+
+        let progress = self
+            .session()
+            .map(|session| session.goal_progress())
+            .unwrap_or_default();
+        imp.goals_group.set_description(if progress.is_empty() {
+            Some("No goals yet")
+        } else {
+            None
+        });
+
+        let mut rows = Vec::new();
+        for (index, (goal, progress)) in progress.into_iter().enumerate() {
+            let row = adw::ActionRow::builder()
+                .title(goal.label())
+                .subtitle(&progress.description)
+                .build();
+
+            if progress.met {
+                let icon = gtk::Image::from_icon_name("emblem-ok-symbolic");
+                icon.add_css_class("success");
+                row.add_prefix(&icon);
+            }
+
+            let bar = gtk::ProgressBar::builder()
+                .fraction(progress.fraction)
+                .valign(gtk::Align::Center)
+                .width_request(96)
+                .build();
+            row.add_suffix(&bar);
+
+            let remove_button = gtk::Button::builder()
+                .icon_name("user-trash-symbolic")
+                .tooltip_text("Remove Goal")
+                .valign(gtk::Align::Center)
+                .build();
+            remove_button.add_css_class("flat");
+            remove_button.connect_clicked(glib::clone!(@weak self as obj => move |_| {
+                if let Some(session) = obj.session() {
+                    session.remove_goal(index);
+                }
+            }));
+            row.add_suffix(&remove_button);
+
+            imp.goals_group.add(&row);
+            rows.push(row);
+        }
+        imp.rows.replace(rows);
+    }
+
+    /// Adds the goal entered in the row, if it can be parsed.
+    #[template_callback]
+    fn goal_applied_cb(&self, row: &adw::EntryRow) {
+        let Some(session) = self.session() else {
+            return;
+        };
+        match data::Goal::parse(&row.text()) {
+            Some(goal) => {
+                session.add_goal(goal);
+                row.set_text("");
+            }
+            None => row.add_css_class("error"),
+        }
+    }
+
+    #[template_callback]
+    fn goal_changed_cb(&self, row: &adw::EntryRow) {
+        row.remove_css_class("error");
+    }
+}
@@ -0,0 +1,189 @@
+use adw::prelude::*;
+use adw::subclass::prelude::*;
+use gettextrs::gettext;
+use gtk::{gdk, glib};
+
+#[doc(hidden)]
+mod imp {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[derive(Debug, Default, gtk::CompositeTemplate, glib::Properties)]
+    #[template(resource = "/io/github/manenfu/PrismaTimer/ui/key_capture_row.ui")]
+    #[properties(wrapper_type = super::KeyCaptureRow)]
+    pub struct KeyCaptureRow {
+        #[template_child]
+        pub keys_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub record_button: TemplateChild<gtk::ToggleButton>,
+
+        /// Names of the captured keys.
+        #[property(get, set = Self::set_keys)]
+        pub keys: RefCell<Vec<String>>,
+
+        /// Keys pressed during the current recording.
+        pub(super) pending_keys: RefCell<Vec<gdk::Key>>,
+        /// Keys currently held during the current recording.
+        pub(super) held_keys: RefCell<Vec<gdk::Key>>,
+    }
+
+    impl KeyCaptureRow {
+        fn set_keys(&self, v: Vec<String>) {
+            self.keys.replace(v);
+            self.obj().update_keys_label();
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for KeyCaptureRow {
+        const NAME: &'static str = "PtKeyCaptureRow";
+        type Type = super::KeyCaptureRow;
+        type ParentType = adw::ActionRow;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_instance_callbacks();
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for KeyCaptureRow {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            obj.setup_event_controllers();
+            obj.update_keys_label();
+        }
+    }
+
+    impl WidgetImpl for KeyCaptureRow {}
+    impl ListBoxRowImpl for KeyCaptureRow {}
+    impl PreferencesRowImpl for KeyCaptureRow {}
+    impl ActionRowImpl for KeyCaptureRow {}
+}
+
+glib::wrapper! {
+    /// A row that records a set of keys pressed together.
+    pub struct KeyCaptureRow(ObjectSubclass<imp::KeyCaptureRow>)
+        @extends gtk::Widget, gtk::ListBoxRow, adw::PreferencesRow, adw::ActionRow,
+        @implements gtk::Accessible, gtk::Actionable, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+#[gtk::template_callbacks]
+impl KeyCaptureRow {
+    pub fn new() -> Self {
+        glib::Object::builder().build()
+    }
+
+    fn setup_event_controllers(&self) {
+        let key_events = gtk::EventControllerKey::new();
+        key_events.connect_key_pressed(glib::clone!(@weak self as obj => @default-return glib::Propagation::Proceed, move |_, key, _, _| {
+            obj.key_pressed_cb(key)
+        }));
+        key_events.connect_key_released(glib::clone!(@weak self as obj => move |_, key, _, _| {
+            obj.key_released_cb(key);
+        }));
+        self.add_controller(key_events);
+    }
+
+    fn is_recording(&self) -> bool {
+        self.imp().record_button.is_active()
+    }
+
+    fn key_pressed_cb(&self, key: gdk::Key) -> glib::Propagation {
+        let imp = self.imp();
+        if !self.is_recording() {
+            return glib::Propagation::Proceed;
+        }
+
+        if key == gdk::Key::Escape {
+            imp.pending_keys.borrow_mut().clear();
+            imp.record_button.set_active(false);
+            return glib::Propagation::Stop;
+        }
+
+        let key = key.to_lower();
+        {
+            let mut pending_keys = imp.pending_keys.borrow_mut();
+            if !pending_keys.contains(&key) {
+                pending_keys.push(key);
+            }
+            let mut held_keys = imp.held_keys.borrow_mut();
+            if !held_keys.contains(&key) {
+                held_keys.push(key);
+            }
+        }
+        imp.keys_label
+            .set_label(&Self::keys_label_text(&imp.pending_keys.borrow()));
+        glib::Propagation::Stop
+    }
+
+    fn key_released_cb(&self, key: gdk::Key) {
+        let imp = self.imp();
+        if !self.is_recording() {
+            return;
+        }
+
+        let key = key.to_lower();
+        let all_released = {
+            let mut held_keys = imp.held_keys.borrow_mut();
+            held_keys.retain(|&k| k != key);
+            held_keys.is_empty()
+        };
+
+        if all_released && !imp.pending_keys.borrow().is_empty() {
+            let keys: Vec<String> = imp
+                .pending_keys
+                .take()
+                .into_iter()
+                .filter_map(|k| k.name())
+                .map(|name| name.to_string())
+                .collect();
+            imp.record_button.set_active(false);
+            self.set_keys(keys);
+        }
+    }
+
+    #[template_callback]
+    fn record_button_toggled_cb(&self, button: &gtk::ToggleButton) {
+        let imp = self.imp();
+        imp.pending_keys.borrow_mut().clear();
+        imp.held_keys.borrow_mut().clear();
+
+        if button.is_active() {
+            imp.keys_label.set_label(&gettext("Press keys…"));
+        } else {
+            self.update_keys_label();
+        }
+    }
+
+    fn update_keys_label(&self) {
+        let imp = self.imp();
+        let keys: Vec<gdk::Key> = imp
+            .keys
+            .borrow()
+            .iter()
+            .filter_map(|name| gdk::Key::from_name(name.as_str()))
+            .collect();
+        imp.keys_label.set_label(&Self::keys_label_text(&keys));
+    }
+
+    fn keys_label_text(keys: &[gdk::Key]) -> String {
+        keys.iter()
+            .map(|&key| gtk::accelerator_get_label(key, gdk::ModifierType::empty()).to_string())
+            .collect::<Vec<_>>()
+            .join(" + ")
+    }
+}
+
+impl Default for KeyCaptureRow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,177 @@
+use crate::data;
+use adw::prelude::*;
+use adw::subclass::prelude::*;
+use gtk::{gdk, glib};
+
+#[doc(hidden)]
+mod imp {
+    use gtk::glib::subclass::Signal;
+    use once_cell::sync::Lazy;
+
+    use super::*;
+
+    #[derive(Debug, Default, gtk::CompositeTemplate)]
+    #[template(resource = "/io/github/manenfu/PrismaTimer/ui/mock_competition_dialog.ui")]
+    pub struct MockCompetitionDialog {
+        #[template_child]
+        pub start_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub format_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub cutoff_row: TemplateChild<adw::ExpanderRow>,
+        #[template_child]
+        pub cutoff_attempts_row: TemplateChild<adw::SpinRow>,
+        #[template_child]
+        pub cutoff_time_row: TemplateChild<adw::EntryRow>,
+        #[template_child]
+        pub time_limit_row: TemplateChild<adw::ExpanderRow>,
+        #[template_child]
+        pub time_limit_time_row: TemplateChild<adw::EntryRow>,
+        #[template_child]
+        pub cumulative_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub error_label: TemplateChild<gtk::Label>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for MockCompetitionDialog {
+        const NAME: &'static str = "PtMockCompetitionDialog";
+        type Type = super::MockCompetitionDialog;
+        type ParentType = adw::Window;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_instance_callbacks();
+
+            klass.add_binding_action(
+                gdk::Key::Escape,
+                gdk::ModifierType::empty(),
+                "window.close",
+                None,
+            );
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for MockCompetitionDialog {
+        fn signals() -> &'static [Signal] {
+            static SIGNALS: Lazy<Vec<Signal>> = Lazy::new(|| {
+                vec![Signal::builder("started")
+                    .param_types([data::MockCompetition::static_type()])
+                    .build()]
+            });
+            SIGNALS.as_ref()
+        }
+
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            let labels: Vec<&str> = data::RoundFormat::ALL.iter().map(|f| f.label()).collect();
+            self.format_row
+                .set_model(Some(&gtk::StringList::new(&labels)));
+            obj.round_changed_cb();
+        }
+    }
+
+    impl WidgetImpl for MockCompetitionDialog {}
+    impl WindowImpl for MockCompetitionDialog {}
+    impl AdwWindowImpl for MockCompetitionDialog {}
+}
+
+glib::wrapper! {
+    /// A dialog to set up the round of a mock competition.
+    pub struct MockCompetitionDialog(ObjectSubclass<imp::MockCompetitionDialog>)
+        @extends gtk::Widget, gtk::Window, adw::Window;
+}
+
+#[gtk::template_callbacks]
+impl MockCompetitionDialog {
+    pub fn new() -> Self {
+        glib::Object::builder().build()
+    }
+
+    fn format(&self) -> data::RoundFormat {
+        let selected = self.imp().format_row.selected() as usize;
+        data::RoundFormat::ALL
+            .get(selected)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Gets the round entered in the dialog, without scrambles.
+    fn round(&self) -> Result<data::CompetitionRound, String> {
+        let imp = self.imp();
+        let format = self.format();
+
+        let cutoff = if imp.cutoff_row.enables_expansion() {
+            let time = data::parse_time(&imp.cutoff_time_row.text())
+                .ok_or_else(|| "Enter the cutoff time, e.g. 20.00".to_string())?;
+            let attempts = imp.cutoff_attempts_row.value() as usize;
+            if attempts >= format.n_attempts() {
+                return Err(format!(
+                    "The cutoff must be within the first {} attempts",
+                    format.n_attempts() - 1
+                ));
+            }
+            Some(data::Cutoff { attempts, time })
+        } else {
+            None
+        };
+
+        let time_limit = if imp.time_limit_row.enables_expansion() {
+            let time = data::parse_time(&imp.time_limit_time_row.text())
+                .ok_or_else(|| "Enter the time limit, e.g. 1:00".to_string())?;
+            if imp.cumulative_row.is_active() {
+                Some(data::TimeLimit::Cumulative(time))
+            } else {
+                Some(data::TimeLimit::PerSolve(time))
+            }
+        } else {
+            None
+        };
+
+        Ok(data::CompetitionRound::with_scrambles(
+            format,
+            cutoff,
+            time_limit,
+            Vec::new(),
+        ))
+    }
+
+    #[template_callback]
+    fn round_changed_cb(&self) {
+        let imp = self.imp();
+        match self.round() {
+            Ok(_) => {
+                imp.error_label.set_visible(false);
+                imp.start_button.set_sensitive(true);
+            }
+            Err(e) => {
+                imp.error_label.set_label(&e);
+                imp.error_label.set_visible(true);
+                imp.start_button.set_sensitive(false);
+            }
+        }
+    }
+
+    #[template_callback]
+    fn start_button_clicked_cb(&self, _button: &gtk::Button) {
+        if let Ok(round) = self.round() {
+            // The scrambles of the group are generated when the round starts.
+            let round = data::CompetitionRound::new(round.format, round.cutoff, round.time_limit);
+            let competition = data::MockCompetition::new(round);
+            self.emit_by_name::<()>("started", &[&competition]);
+            self.close();
+        }
+    }
+}
+
+impl Default for MockCompetitionDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use crate::data;
+use adw::prelude::*;
+use adw::subclass::prelude::*;
+use gtk::{gdk, glib};
+
+#[doc(hidden)]
+mod imp {
+    use gtk::glib::subclass::Signal;
+    use once_cell::sync::{Lazy, OnceCell};
+
+    use super::*;
+
+    #[derive(Debug, Default, gtk::CompositeTemplate, glib::Properties)]
+    #[template(resource = "/io/github/manenfu/PrismaTimer/ui/multi_blind_dialog.ui")]
+    #[properties(wrapper_type = super::MultiBlindDialog)]
+    pub struct MultiBlindDialog {
+        #[template_child]
+        pub save_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub attempted_row: TemplateChild<adw::SpinRow>,
+        #[template_child]
+        pub solved_row: TemplateChild<adw::SpinRow>,
+        #[template_child]
+        pub time_row: TemplateChild<adw::EntryRow>,
+        #[template_child]
+        pub points_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub time_limit_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub error_label: TemplateChild<gtk::Label>,
+
+        #[property(get, construct_only)]
+        pub session: OnceCell<data::Session>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for MultiBlindDialog {
+        const NAME: &'static str = "PtMultiBlindDialog";
+        type Type = super::MultiBlindDialog;
+        type ParentType = adw::Window;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_instance_callbacks();
+
+            klass.add_binding_action(
+                gdk::Key::Escape,
+                gdk::ModifierType::empty(),
+                "window.close",
+                None,
+            );
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for MultiBlindDialog {
+        fn signals() -> &'static [Signal] {
+            static SIGNALS: Lazy<Vec<Signal>> = Lazy::new(|| {
+                vec![Signal::builder("solve-added")
+                    .param_types([data::SessionItem::static_type()])
+                    .build()]
+            });
+            SIGNALS.as_ref()
+        }
+
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            obj.result_changed_cb();
+        }
+    }
+
+    impl WidgetImpl for MultiBlindDialog {}
+    impl WindowImpl for MultiBlindDialog {}
+    impl AdwWindowImpl for MultiBlindDialog {}
+}
+
+glib::wrapper! {
+    /// A dialog to enter the result of a Multi-Blind attempt.
+    pub struct MultiBlindDialog(ObjectSubclass<imp::MultiBlindDialog>)
+        @extends gtk::Widget, gtk::Window, adw::Window;
+}
+
+#[gtk::template_callbacks]
+impl MultiBlindDialog {
+    pub fn new(session: &data::Session) -> Self {
+        glib::Object::builder().property("session", session).build()
+    }
+
+    /// Fills in the time of the attempt, e.g. from the timer.
+    pub fn set_time(&self, time: Duration) {
+        let seconds = time.as_secs();
+        self.imp()
+            .time_row
+            .set_text(&format!("{}:{:02}", seconds / 60, seconds % 60));
+    }
+
+    /// Gets the result entered in the dialog.
+    fn result(&self) -> Result<data::MultiBlindResult, String> {
+        let imp = self.imp();
+        let attempted = imp.attempted_row.value() as u32;
+        let solved = imp.solved_row.value() as u32;
+        let time = data::parse_time(&imp.time_row.text())
+            .ok_or_else(|| "Enter the time as minutes:seconds".to_string())?;
+
+        data::MultiBlindResult::new(attempted, solved, time).map_err(|e| e.to_string())
+    }
+
+    #[template_callback]
+    fn result_changed_cb(&self) {
+        let imp = self.imp();
+        let attempted = imp.attempted_row.value() as u32;
+        let limit = data::MultiBlindResult::time_limit_for(attempted).as_secs();
+        imp.time_limit_label
+            .set_label(&format!("{}:{:02}", limit / 60, limit % 60));
+
+        match self.result() {
+            Ok(result) => {
+                imp.points_label.set_label(&result.points().to_string());
+                imp.error_label.set_visible(false);
+                imp.save_button.set_sensitive(true);
+            }
+            Err(e) => {
+                imp.points_label.set_label("");
+                imp.error_label.set_label(&e);
+                imp.error_label.set_visible(true);
+                imp.save_button.set_sensitive(false);
+            }
+        }
+    }
+
+    #[template_callback]
+    fn save_button_clicked_cb(&self, _button: &gtk::Button) {
+        if let Ok(result) = self.result() {
+            let item = self
+                .session()
+                .add_solve(data::SolveData::from_multi_blind(result, String::new()));
+            self.emit_by_name::<()>("solve-added", &[&item]);
+            self.close();
+        }
+    }
+}
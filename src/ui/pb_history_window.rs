@@ -0,0 +1,206 @@
+use crate::data;
+use adw::prelude::*;
+use adw::subclass::prelude::*;
+use gtk::{gdk, gio, glib};
+
+#[doc(hidden)]
+mod imp {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[derive(Debug, Default, gtk::CompositeTemplate, glib::Properties)]
+    #[template(resource = "/io/github/manenfu/PrismaTimer/ui/pb_history_window.ui")]
+    #[properties(wrapper_type = super::PbHistoryWindow)]
+    pub struct PbHistoryWindow {
+        #[template_child]
+        pub toast_overlay: TemplateChild<adw::ToastOverlay>,
+        #[template_child]
+        pub stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub history_page: TemplateChild<adw::PreferencesPage>,
+        #[template_child]
+        pub export_button: TemplateChild<gtk::Button>,
+
+        #[property(get, set = Self::set_session, nullable)]
+        pub session: RefCell<Option<data::Session>>,
+        session_handler: RefCell<Option<glib::SignalHandlerId>>,
+
+        /// Groups of the history, one for each statistic.
+        pub(super) groups: RefCell<Vec<adw::PreferencesGroup>>,
+    }
+
+    impl PbHistoryWindow {
+        fn set_session(&self, v: Option<data::Session>) {
+            let obj = self.obj();
+
+            if let Some(session) = self.session.take() {
+                if let Some(id) = self.session_handler.take() {
+                    session.disconnect(id);
+                }
+            }
+
+            if let Some(session) = &v {
+                self.session_handler.replace(Some(session.connect_closure(
+                    "pb-history-changed",
+                    false,
+                    glib::closure_local!(@watch obj => move |_: &data::Session| {
+                        obj.update_history();
+                    }),
+                )));
+            }
+            self.session.replace(v);
+            obj.update_history();
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for PbHistoryWindow {
+        const NAME: &'static str = "PtPbHistoryWindow";
+        type Type = super::PbHistoryWindow;
+        type ParentType = adw::Window;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_instance_callbacks();
+
+            klass.add_binding_action(
+                gdk::Key::Escape,
+                gdk::ModifierType::empty(),
+                "window.close",
+                None,
+            );
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for PbHistoryWindow {
+        fn dispose(&self) {
+            if let Some(session) = self.session.take() {
+                if let Some(id) = self.session_handler.take() {
+                    session.disconnect(id);
+                }
+            }
+        }
+    }
+
+    impl WidgetImpl for PbHistoryWindow {}
+    impl WindowImpl for PbHistoryWindow {}
+    impl AdwWindowImpl for PbHistoryWindow {}
+}
+
+glib::wrapper! {
+    /// A window listing every improvement of a personal best in a session.
+    pub struct PbHistoryWindow(ObjectSubclass<imp::PbHistoryWindow>)
+        @extends gtk::Widget, gtk::Window, adw::Window;
+}
+
+#[gtk::template_callbacks]
+impl PbHistoryWindow {
+    pub fn new(session: &data::Session) -> Self {
+        glib::Object::builder().property("session", session).build()
+    }
+
+    /// Rebuilds the list of personal bests, newest first for each statistic.
+    fn update_history(&self) {
+        let imp = self.imp();
+        for group in imp.groups.take() {
+            imp.history_page.remove(&group);
+        }
+
+        let Some(session) = self.session() else {
+            imp.stack.set_visible_child_name("empty");
+            imp.export_button.set_sensitive(false);
+            return;
+        };
+
+        let history = session.pb_history();
+        let mut groups = Vec::new();
+        for &statistic in data::PbStatistic::for_kind(session.kind()) {
+            let group = adw::PreferencesGroup::builder()
+                .title(statistic.to_string())
+                .build();
+            let mut is_empty = true;
+
+            for pb in history.iter().rev().filter(|pb| pb.statistic == statistic) {
+                let date = session
+                    .get_solve(pb.index)
+                    .map_or(String::default(), |item| item.timestamp_string());
+                let subtitle = match pb.old {
+                    Some(old) => format!(
+                        "Improved from {} · Solve {} · {}",
+                        session.format_personal_best(statistic, &old),
+                        pb.index + 1,
+                        date
+                    ),
+                    None => format!("First {} · Solve {} · {}", statistic, pb.index + 1, date),
+                };
+                let row = adw::ActionRow::builder()
+                    .title(session.format_personal_best(statistic, &pb.new))
+                    .subtitle(subtitle)
+                    .build();
+                group.add(&row);
+                is_empty = false;
+            }
+
+            if !is_empty {
+                imp.history_page.add(&group);
+                groups.push(group);
+            }
+        }
+
+        let has_history = !groups.is_empty();
+        imp.groups.replace(groups);
+        imp.stack
+            .set_visible_child_name(if has_history { "history" } else { "empty" });
+        imp.export_button.set_sensitive(has_history);
+    }
+
+    #[template_callback]
+    fn export_button_clicked_cb(&self, _button: &gtk::Button) {
+        let Some(session) = self.session() else {
+            return;
+        };
+
+        let filter = gtk::FileFilter::new();
+        filter.set_name(Some("CSV"));
+        filter.add_mime_type("text/csv");
+        let filters = gio::ListStore::new::<gtk::FileFilter>();
+        filters.append(&filter);
+
+        let dialog = gtk::FileDialog::builder()
+            .title("Export Personal Bests")
+            .initial_name(format!("{} personal bests.csv", session.name()))
+            .filters(&filters)
+            .modal(true)
+            .build();
+        let csv = session.pb_history_csv();
+        dialog.save(
+            Some(self),
+            gio::Cancellable::NONE,
+            glib::clone!(@weak self as obj => move |result| {
+                let Ok(file) = result else {
+                    return;
+                };
+                let toast = match file.replace_contents(
+                    csv.as_bytes(),
+                    None,
+                    false,
+                    gio::FileCreateFlags::REPLACE_DESTINATION,
+                    gio::Cancellable::NONE,
+                ) {
+                    Ok(_) => adw::Toast::new("Personal Bests Exported"),
+                    Err(e) => {
+                        log::error!("Failed to export personal bests. cause: {}", e);
+                        adw::Toast::new("Failed to Export Personal Bests")
+                    }
+                };
+                obj.imp().toast_overlay.add_toast(toast);
+            }),
+        );
+    }
+}
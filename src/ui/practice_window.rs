@@ -0,0 +1,221 @@
+use std::time::Duration;
+
+use crate::{config, data};
+use adw::prelude::*;
+use adw::subclass::prelude::*;
+use gtk::{gdk, gio, glib};
+
+#[doc(hidden)]
+mod imp {
+    use std::cell::RefCell;
+
+    use once_cell::sync::OnceCell;
+
+    use super::*;
+
+    #[derive(Debug, Default, gtk::CompositeTemplate, glib::Properties)]
+    #[template(resource = "/io/github/manenfu/PrismaTimer/ui/practice_window.ui")]
+    #[properties(wrapper_type = super::PracticeWindow)]
+    pub struct PracticeWindow {
+        #[template_child]
+        pub stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub current_streak_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub longest_streak_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub today_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub total_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub days_group: TemplateChild<adw::PreferencesGroup>,
+
+        #[property(get, construct_only)]
+        pub sessions: OnceCell<gio::ListStore>,
+        pub(super) settings: OnceCell<gio::Settings>,
+
+        /// Handler following sessions being added and removed.
+        pub(super) sessions_handler: RefCell<Option<glib::SignalHandlerId>>,
+        /// Handlers following the solves of each session.
+        pub(super) session_handlers: RefCell<Vec<(data::Session, glib::SignalHandlerId)>>,
+        /// Rows of the days, newest first.
+        pub(super) rows: RefCell<Vec<adw::ActionRow>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for PracticeWindow {
+        const NAME: &'static str = "PtPracticeWindow";
+        type Type = super::PracticeWindow;
+        type ParentType = adw::Window;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+
+            klass.add_binding_action(
+                gdk::Key::Escape,
+                gdk::ModifierType::empty(),
+                "window.close",
+                None,
+            );
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for PracticeWindow {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            let settings = gio::Settings::new(config::APP_ID);
+            settings.connect_changed(
+                Some("idle-cutoff"),
+                glib::clone!(@weak obj => move |_, _| {
+                    obj.update_practice();
+                }),
+            );
+            self.settings
+                .set(settings)
+                .expect("`settings` should not be set before `constructed`");
+
+            let sessions = obj.sessions();
+            self.sessions_handler
+                .replace(Some(sessions.connect_items_changed(
+                    glib::clone!(@weak obj => move |_, _, _, _| {
+                        obj.connect_sessions();
+                        obj.update_practice();
+                    }),
+                )));
+            obj.connect_sessions();
+            obj.update_practice();
+        }
+
+        fn dispose(&self) {
+            if let (Some(sessions), Some(id)) = (self.sessions.get(), self.sessions_handler.take())
+            {
+                sessions.disconnect(id);
+            }
+            for (session, id) in self.session_handlers.take() {
+                session.disconnect(id);
+            }
+        }
+    }
+
+    impl WidgetImpl for PracticeWindow {}
+    impl WindowImpl for PracticeWindow {}
+    impl AdwWindowImpl for PracticeWindow {}
+}
+
+glib::wrapper! {
+    /// A window showing the streaks of days practiced and the time spent
+    /// practicing each day, over every session.
+    pub struct PracticeWindow(ObjectSubclass<imp::PracticeWindow>)
+        @extends gtk::Widget, gtk::Window, adw::Window;
+}
+
+impl PracticeWindow {
+    pub fn new(sessions: &gio::ListStore) -> Self {
+        glib::Object::builder()
+            .property("sessions", sessions)
+            .build()
+    }
+
+    /// Follows the solves of every session, to update the summary when they
+    /// change.
+    fn connect_sessions(&self) {
+        let imp = self.imp();
+        for (session, id) in imp.session_handlers.take() {
+            session.disconnect(id);
+        }
+
+        let handlers = self
+            .sessions()
+            .iter::<data::Session>()
+            .filter_map(Result::ok)
+            .map(|session| {
+                let id = session.connect_items_changed(
+                    glib::clone!(@weak self as obj => move |_, _, _, _| {
+                        obj.update_practice();
+                    }),
+                );
+                (session, id)
+            })
+            .collect();
+        imp.session_handlers.replace(handlers);
+    }
+
+    /// Rebuilds the streaks and the time spent on each day.
+    fn update_practice(&self) {
+        let imp = self.imp();
+        for row in imp.rows.take() {
+            imp.days_group.remove(&row);
+        }
+
+        let Some(settings) = imp.settings.get() else {
+            return;
+        };
+        let summary = practice_summary(&self.sessions(), settings);
+        if summary.time_per_day.is_empty() {
+            imp.stack.set_visible_child_name("empty");
+            return;
+        }
+        imp.stack.set_visible_child_name("practice");
+
+        imp.current_streak_row
+            .set_subtitle(&format_days(summary.current_streak));
+        imp.longest_streak_row
+            .set_subtitle(&format_days(summary.longest_streak));
+        imp.today_row
+            .set_subtitle(&data::format_time_spent(summary.time_today()));
+        imp.total_row
+            .set_subtitle(&data::format_time_spent(summary.total_time()));
+
+        let mut rows = Vec::new();
+        for (&day, &time) in summary.time_per_day.iter().rev() {
+            let row = adw::ActionRow::builder()
+                .title(data::DatePeriod::Day.format(day))
+                .subtitle(data::format_time_spent(time))
+                .build();
+            imp.days_group.add(&row);
+            rows.push(row);
+        }
+        imp.rows.replace(rows);
+    }
+}
+
+/// Summarizes the practice in every session, with the idle cutoff chosen in
+/// the settings.
+pub fn practice_summary(
+    sessions: &gio::ListStore,
+    settings: &gio::Settings,
+) -> data::PracticeSummary {
+    let solves: Vec<_> = sessions
+        .iter::<data::Session>()
+        .filter_map(Result::ok)
+        .flat_map(|session| session.practice_solves())
+        .collect();
+    data::PracticeSummary::new(
+        &solves,
+        idle_cutoff(settings),
+        chrono::Local::now().date_naive(),
+    )
+}
+
+/// Gets the longest break between solves counted as practice, as chosen in
+/// the settings.
+pub fn idle_cutoff(settings: &gio::Settings) -> Duration {
+    Duration::try_from_secs_f64(settings.double("idle-cutoff") * 60.0)
+        .unwrap_or(data::DEFAULT_IDLE_CUTOFF)
+}
+
+/// Formats a number of days, e.g. `3 days`.
+fn format_days(n: usize) -> String {
+    if n == 1 {
+        "1 day".to_string()
+    } else {
+        format!("{} days", n)
+    }
+}
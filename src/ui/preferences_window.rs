@@ -1,8 +1,10 @@
+use std::collections::HashMap;
+
 use adw::prelude::*;
 use adw::subclass::prelude::*;
 use gtk::{gdk, gio, glib};
 
-use crate::config;
+use crate::{config, data, ui};
 
 #[doc(hidden)]
 mod imp {
@@ -13,6 +15,28 @@ mod imp {
     #[derive(Debug, Default, gtk::CompositeTemplate)]
     #[template(resource = "/io/github/manenfu/PrismaTimer/ui/preferences_window.ui")]
     pub struct PreferencesWindow {
+        #[template_child]
+        pub(super) trigger_keys_row: TemplateChild<ui::KeyCaptureRow>,
+        #[template_child]
+        pub(super) trigger_require_all_switch: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub(super) relay_splits_switch: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub(super) time_precision_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub(super) running_display_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub(super) minimal_focus_switch: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub(super) inspection_switch: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub(super) cue_sound_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub(super) cue_volume_scale: TemplateChild<gtk::Scale>,
+        #[template_child]
+        pub(super) idle_cutoff_row: TemplateChild<adw::SpinRow>,
+        #[template_child]
+        pub(super) shortcuts_group: TemplateChild<adw::PreferencesGroup>,
         #[template_child]
         pub(super) use_system_color_scheme_switch: TemplateChild<adw::SwitchRow>,
         #[template_child]
@@ -50,6 +74,10 @@ mod imp {
             let obj = self.obj();
 
             obj.setup_settings();
+            obj.setup_timer_group();
+            obj.setup_inspection_group();
+            obj.setup_practice_group();
+            obj.setup_shortcuts_group();
             obj.setup_appearance_group();
         }
     }
@@ -82,6 +110,160 @@ impl PreferencesWindow {
             .expect("`settings` should not be set before `setup_settings` is called");
     }
 
+    fn setup_timer_group(&self) {
+        let imp = self.imp();
+        let settings = self.settings();
+
+        settings
+            .bind("trigger-keys", &*imp.trigger_keys_row, "keys")
+            .build();
+        settings
+            .bind(
+                "trigger-require-all",
+                &*imp.trigger_require_all_switch,
+                "active",
+            )
+            .build();
+        settings
+            .bind("relay-splits", &*imp.relay_splits_switch, "active")
+            .build();
+
+        let labels: Vec<&str> = data::TimePrecision::ALL
+            .iter()
+            .map(|precision| precision.label())
+            .collect();
+        imp.time_precision_row
+            .set_model(Some(&gtk::StringList::new(&labels)));
+        let position = data::TimePrecision::ALL
+            .iter()
+            .position(|&p| p == time_precision(settings))
+            .unwrap_or(0);
+        imp.time_precision_row.set_selected(position as u32);
+        imp.time_precision_row.connect_selected_notify(
+            glib::clone!(@weak self as obj => move |row| {
+                let Some(precision) = data::TimePrecision::ALL.get(row.selected() as usize) else {
+                    return;
+                };
+                if let Err(e) = obj.settings().set_string("time-precision", precision.nick()) {
+                    log::error!("Failed to save time precision. cause: {}", e);
+                }
+            }),
+        );
+
+        let labels: Vec<&str> = data::RunningDisplay::ALL
+            .iter()
+            .map(|display| display.label())
+            .collect();
+        imp.running_display_row
+            .set_model(Some(&gtk::StringList::new(&labels)));
+        let position = data::RunningDisplay::ALL
+            .iter()
+            .position(|&d| d == running_display(settings))
+            .unwrap_or(0);
+        imp.running_display_row.set_selected(position as u32);
+        imp.running_display_row.connect_selected_notify(
+            glib::clone!(@weak self as obj => move |row| {
+                let Some(display) = data::RunningDisplay::ALL.get(row.selected() as usize) else {
+                    return;
+                };
+                if let Err(e) = obj.settings().set_string("running-display", display.nick()) {
+                    log::error!("Failed to save running display. cause: {}", e);
+                }
+            }),
+        );
+
+        settings
+            .bind("minimal-focus", &*imp.minimal_focus_switch, "active")
+            .build();
+    }
+
+    fn setup_inspection_group(&self) {
+        let imp = self.imp();
+        let settings = self.settings();
+
+        settings
+            .bind("inspection", &*imp.inspection_switch, "active")
+            .build();
+        settings
+            .bind(
+                "inspection-cue-volume",
+                &imp.cue_volume_scale.adjustment(),
+                "value",
+            )
+            .build();
+
+        let labels: Vec<&str> = data::CueSound::ALL
+            .iter()
+            .map(|sound| sound.label())
+            .collect();
+        imp.cue_sound_row
+            .set_model(Some(&gtk::StringList::new(&labels)));
+        let sound =
+            data::CueSound::from_nick(&settings.string("inspection-cue-sound")).unwrap_or_default();
+        let position = data::CueSound::ALL
+            .iter()
+            .position(|&s| s == sound)
+            .unwrap_or(0);
+        imp.cue_sound_row.set_selected(position as u32);
+        imp.cue_sound_row
+            .connect_selected_notify(glib::clone!(@weak self as obj => move |row| {
+                let Some(sound) = data::CueSound::ALL.get(row.selected() as usize) else {
+                    return;
+                };
+                if let Err(e) = obj.settings().set_string("inspection-cue-sound", sound.nick()) {
+                    log::error!("Failed to save inspection cue sound. cause: {}", e);
+                }
+            }));
+        imp.cue_sound_row
+            .bind_property("selected", &*imp.cue_volume_scale, "sensitive")
+            .transform_to(|_, selected: u32| {
+                Some(data::CueSound::ALL.get(selected as usize) != Some(&data::CueSound::Silent))
+            })
+            .sync_create()
+            .build();
+    }
+
+    /// Adds a row for each action with a configurable accelerator. Only
+    /// accelerators that differ from the default are saved.
+    fn setup_practice_group(&self) {
+        let imp = self.imp();
+        self.settings()
+            .bind("idle-cutoff", &*imp.idle_cutoff_row, "value")
+            .build();
+    }
+
+    fn setup_shortcuts_group(&self) {
+        let imp = self.imp();
+        let custom = self.custom_shortcuts();
+        let accels = data::shortcut_accels(&custom);
+
+        for (shortcut, (_, accel)) in data::SHORTCUTS.iter().zip(accels) {
+            let row = ui::AccelCaptureRow::new();
+            row.set_title(shortcut.title);
+            row.set_default_accelerator(shortcut.default_accel);
+            row.set_accelerator(accel.unwrap_or_default());
+            row.connect_accelerator_notify(glib::clone!(@weak self as obj => move |row| {
+                let mut custom = obj.custom_shortcuts();
+                if row.accelerator() == shortcut.default_accel {
+                    custom.remove(shortcut.action);
+                } else {
+                    custom.insert(shortcut.action.to_string(), row.accelerator());
+                }
+                if let Err(e) = obj.settings().set_value("shortcuts", &custom.to_variant()) {
+                    log::error!("Failed to save shortcuts. cause: {}", e);
+                }
+            }));
+            imp.shortcuts_group.add(&row);
+        }
+    }
+
+    fn custom_shortcuts(&self) -> HashMap<String, String> {
+        self.settings()
+            .value("shortcuts")
+            .get::<HashMap<String, String>>()
+            .unwrap_or_default()
+    }
+
     fn setup_appearance_group(&self) {
         let imp = self.imp();
         let manager = adw::StyleManager::default();
@@ -118,3 +300,14 @@ impl PreferencesWindow {
             .expect("`settings` should be set by `setup_settings` first")
     }
 }
+
+/// Gets the precision times are displayed with, as chosen in the settings.
+pub fn time_precision(settings: &gio::Settings) -> data::TimePrecision {
+    data::TimePrecision::from_nick(&settings.string("time-precision")).unwrap_or_default()
+}
+
+/// Gets how the time is shown while the timer is running, as chosen in the
+/// settings.
+pub fn running_display(settings: &gio::Settings) -> data::RunningDisplay {
+    data::RunningDisplay::from_nick(&settings.string("running-display")).unwrap_or_default()
+}
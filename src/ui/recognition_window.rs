@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use crate::data::{self, NameMatch};
+use crate::{config, ui, util};
+use adw::prelude::*;
+use adw::subclass::prelude::*;
+use gtk::{gdk, gio, glib};
+
+/// Number of confusions listed.
+const CONFUSIONS_SHOWN: usize = 5;
+/// Name of the data file the latest answers are saved in.
+const ANSWERS_FILE: &str = "recognition-answers";
+
+#[doc(hidden)]
+mod imp {
+    use std::cell::{Cell, RefCell};
+
+    use once_cell::sync::OnceCell;
+
+    use super::*;
+
+    #[derive(Debug, Default, gtk::CompositeTemplate)]
+    #[template(resource = "/io/github/manenfu/PrismaTimer/ui/recognition_window.ui")]
+    pub struct RecognitionWindow {
+        #[template_child]
+        pub cube_view: TemplateChild<ui::CubeView>,
+        #[template_child]
+        pub answer_entry: TemplateChild<gtk::Entry>,
+        #[template_child]
+        pub names_box: TemplateChild<gtk::FlowBox>,
+        #[template_child]
+        pub result_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub confusions_group: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        pub cases_group: TemplateChild<adw::PreferencesGroup>,
+
+        pub(super) settings: OnceCell<gio::Settings>,
+
+        pub(super) cases: RefCell<Vec<data::LastLayerCase>>,
+        /// Answers given, oldest first.
+        pub(super) answers: RefCell<Vec<data::RecognitionAnswer>>,
+        /// Index of the case shown.
+        pub(super) case: Cell<Option<usize>>,
+        /// When the case shown appeared.
+        pub(super) shown: Cell<Option<Instant>>,
+
+        /// Rows of the cases, by case name.
+        pub(super) case_rows: RefCell<HashMap<String, adw::ActionRow>>,
+        pub(super) confusion_rows: RefCell<Vec<adw::ActionRow>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for RecognitionWindow {
+        const NAME: &'static str = "PtRecognitionWindow";
+        type Type = super::RecognitionWindow;
+        type ParentType = adw::Window;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_instance_callbacks();
+
+            klass.add_binding_action(
+                gdk::Key::Escape,
+                gdk::ModifierType::empty(),
+                "window.close",
+                None,
+            );
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for RecognitionWindow {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            obj.setup_settings();
+            obj.setup_cases();
+            obj.update_confusions();
+            obj.next_case();
+            obj.set_focus(Some(&*self.answer_entry));
+        }
+    }
+
+    impl WidgetImpl for RecognitionWindow {}
+    impl WindowImpl for RecognitionWindow {}
+    impl AdwWindowImpl for RecognitionWindow {}
+}
+
+glib::wrapper! {
+    /// A window drilling the recognition of PLL cases, which shows a random
+    /// case on a cube and times how long it takes to name it.
+    pub struct RecognitionWindow(ObjectSubclass<imp::RecognitionWindow>)
+        @extends gtk::Widget, gtk::Window, adw::Window;
+}
+
+#[gtk::template_callbacks]
+impl RecognitionWindow {
+    pub fn new() -> Self {
+        glib::Object::builder().build()
+    }
+
+    fn settings(&self) -> &gio::Settings {
+        self.imp()
+            .settings
+            .get()
+            .expect("`settings` should be set by `setup_settings` first")
+    }
+
+    fn setup_settings(&self) {
+        let imp = self.imp();
+
+        imp.settings
+            .set(gio::Settings::new(config::APP_ID))
+            .expect("`settings` should not be set before `setup_settings` is called");
+
+        match util::read_data_file(ANSWERS_FILE) {
+            Ok(text) => {
+                let answers = data::parse_recognition_answers(&text.unwrap_or_default());
+                imp.answers.replace(answers);
+            }
+            Err(e) => log::error!("Failed to load recognition answers. cause: {}", e),
+        }
+    }
+
+    fn save_answers(&self) {
+        let text = data::format_recognition_answers(&self.imp().answers.borrow());
+        if let Err(e) = util::write_data_file(ANSWERS_FILE, &text) {
+            log::error!("Failed to save recognition answers. cause: {}", e);
+        }
+    }
+
+    /// Adds a button and a row of statistics for each case.
+    fn setup_cases(&self) {
+        let imp = self.imp();
+        let cases = data::last_layer_cases(data::CaseSet::Pll);
+
+        let mut case_rows = HashMap::new();
+        for (i, case) in cases.iter().enumerate() {
+            let button = gtk::Button::with_label(&case.name);
+            button.set_focus_on_click(false);
+            button.connect_clicked(glib::clone!(@weak self as obj => move |_| {
+                obj.answer(i);
+            }));
+            imp.names_box.append(&button);
+
+            let row = adw::ActionRow::builder().title(&case.name).build();
+            imp.cases_group.add(&row);
+            case_rows.insert(case.name.clone(), row);
+        }
+
+        imp.case_rows.replace(case_rows);
+        imp.cases.replace(cases);
+        for name in imp.case_rows.borrow().keys() {
+            self.update_case_row(name);
+        }
+    }
+
+    fn update_case_row(&self, name: &str) {
+        let imp = self.imp();
+        if let Some(row) = imp.case_rows.borrow().get(name) {
+            let recognition = data::CaseRecognition::new(&imp.answers.borrow(), name);
+            row.set_subtitle(&recognition.description(ui::time_precision(self.settings())));
+        }
+    }
+
+    /// Lists the cases most often taken for another.
+    fn update_confusions(&self) {
+        let imp = self.imp();
+        for row in imp.confusion_rows.take() {
+            imp.confusions_group.remove(&row);
+        }
+
+        let pairs = data::confusion_pairs(&imp.answers.borrow());
+        let mut rows: Vec<adw::ActionRow> = pairs
+            .iter()
+            .take(CONFUSIONS_SHOWN)
+            .map(|(case, answer, count)| {
+                let times = if *count == 1 { "time" } else { "times" };
+                adw::ActionRow::builder()
+                    .title(format!("{} taken for {}", case, answer))
+                    .subtitle(format!("{} {}", count, times))
+                    .build()
+            })
+            .collect();
+        if rows.is_empty() {
+            rows.push(adw::ActionRow::builder().title("No confusions yet").build());
+        }
+        for row in &rows {
+            imp.confusions_group.add(row);
+        }
+        imp.confusion_rows.replace(rows);
+    }
+
+    /// Shows a random state of a case other than the one shown before.
+    fn next_case(&self) {
+        let imp = self.imp();
+        let cases = imp.cases.borrow();
+        if cases.is_empty() {
+            return;
+        }
+
+        let random = |n: u32| glib::random_int_range(0, n as i32) as u32;
+        let i = match imp.case.get() {
+            Some(previous) if cases.len() > 1 => {
+                (previous + 1 + random(cases.len() as u32 - 1) as usize) % cases.len()
+            }
+            _ => random(cases.len() as u32) as usize,
+        };
+
+        let mut cube = data::Cube::new();
+        cube.apply_moves(&cases[i].random_setup(random));
+        imp.cube_view.set_cube(Some(cube));
+        imp.case.set(Some(i));
+        imp.shown.set(Some(Instant::now()));
+    }
+
+    #[template_callback]
+    fn answer_changed_cb(&self) {
+        self.match_answer(false);
+    }
+
+    #[template_callback]
+    fn answer_activated_cb(&self) {
+        self.match_answer(true);
+    }
+
+    /// Answers with the case matching the typed name, once only one case
+    /// matches it or the name is complete.
+    fn match_answer(&self, complete: bool) {
+        let imp = self.imp();
+        let entry = &*imp.answer_entry;
+        let matched = {
+            let cases = imp.cases.borrow();
+            let names: Vec<&str> = cases.iter().map(|case| case.name.as_str()).collect();
+            data::match_case_name(&names, &entry.text(), complete)
+        };
+
+        match matched {
+            NameMatch::Unique(i) => self.answer(i),
+            NameMatch::Ambiguous => entry.remove_css_class("error"),
+            NameMatch::NoMatch => entry.add_css_class("error"),
+        }
+    }
+
+    /// Records the case at index `i` as the answer for the case shown, and
+    /// shows the next case.
+    fn answer(&self, i: usize) {
+        let imp = self.imp();
+        let (Some(shown), Some(started)) = (imp.case.get(), imp.shown.take()) else {
+            return;
+        };
+        let answer = {
+            let cases = imp.cases.borrow();
+            data::RecognitionAnswer {
+                case: cases[shown].name.clone(),
+                answer: cases[i].name.clone(),
+                time: started.elapsed(),
+            }
+        };
+
+        let result = if answer.is_correct() {
+            format!(
+                "{} in {}",
+                answer.case,
+                data::SolveTime::new(answer.time, data::Penalty::Ok)
+                    .format(ui::time_precision(self.settings()))
+            )
+        } else {
+            format!("It was {}, not {}", answer.case, answer.answer)
+        };
+        imp.result_label.set_label(&result);
+        if answer.is_correct() {
+            imp.result_label.remove_css_class("error");
+        } else {
+            imp.result_label.add_css_class("error");
+        }
+
+        {
+            let mut answers = imp.answers.borrow_mut();
+            answers.push(answer.clone());
+            let excess = answers.len().saturating_sub(data::RECOGNITION_HISTORY);
+            answers.drain(..excess);
+        }
+        self.save_answers();
+        self.update_case_row(&answer.case);
+        if !answer.is_correct() {
+            self.update_confusions();
+        }
+
+        // Clearing the entry matches nothing, so it answers nothing more.
+        imp.answer_entry.set_text("");
+        imp.answer_entry.remove_css_class("error");
+        self.next_case();
+    }
+
+    #[template_callback]
+    fn clear_answers_cb(&self) {
+        let imp = self.imp();
+        imp.answers.borrow_mut().clear();
+        self.save_answers();
+        for name in imp.case_rows.borrow().keys() {
+            self.update_case_row(name);
+        }
+        self.update_confusions();
+    }
+}
+
+impl Default for RecognitionWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
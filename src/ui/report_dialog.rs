@@ -0,0 +1,202 @@
+use adw::prelude::*;
+use adw::subclass::prelude::*;
+use gtk::{gdk, gio, glib};
+
+use crate::{config, data};
+
+#[doc(hidden)]
+mod imp {
+    use std::cell::RefCell;
+
+    use once_cell::sync::OnceCell;
+
+    use super::*;
+
+    #[derive(Debug, Default, gtk::CompositeTemplate)]
+    #[template(resource = "/io/github/manenfu/PrismaTimer/ui/report_dialog.ui")]
+    pub struct ReportDialog {
+        #[template_child]
+        pub toast_overlay: TemplateChild<adw::ToastOverlay>,
+        #[template_child]
+        pub format_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub text_view: TemplateChild<gtk::TextView>,
+
+        pub(super) report: RefCell<data::Report>,
+        pub(super) settings: OnceCell<gio::Settings>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ReportDialog {
+        const NAME: &'static str = "PtReportDialog";
+        type Type = super::ReportDialog;
+        type ParentType = adw::Window;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_instance_callbacks();
+
+            klass.add_binding_action(
+                gdk::Key::Escape,
+                gdk::ModifierType::empty(),
+                "window.close",
+                None,
+            );
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for ReportDialog {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            obj.setup_settings();
+            obj.setup_format_row();
+        }
+    }
+
+    impl WidgetImpl for ReportDialog {}
+    impl WindowImpl for ReportDialog {}
+    impl AdwWindowImpl for ReportDialog {}
+}
+
+glib::wrapper! {
+    /// A window previewing a report in each format, which can be copied or
+    /// saved to a file.
+    pub struct ReportDialog(ObjectSubclass<imp::ReportDialog>)
+        @extends gtk::Widget, gtk::Window, adw::Window;
+}
+
+#[gtk::template_callbacks]
+impl ReportDialog {
+    pub fn new(report: data::Report) -> Self {
+        let obj: Self = glib::Object::builder().build();
+        obj.set_title(Some(&report.title));
+        obj.imp().report.replace(report);
+        obj.update_text();
+        obj
+    }
+
+    fn setup_settings(&self) {
+        let settings = gio::Settings::new(config::APP_ID);
+        self.imp()
+            .settings
+            .set(settings)
+            .expect("`settings` should not be set before `setup_settings` is called");
+    }
+
+    fn settings(&self) -> &gio::Settings {
+        self.imp()
+            .settings
+            .get()
+            .expect("`settings` should be set by `setup_settings` first")
+    }
+
+    /// Lists the formats, starting with the last one used.
+    fn setup_format_row(&self) {
+        let imp = self.imp();
+        let labels: Vec<&str> = data::ReportFormat::ALL
+            .iter()
+            .map(|format| format.label())
+            .collect();
+        imp.format_row
+            .set_model(Some(&gtk::StringList::new(&labels)));
+
+        let format = report_format(self.settings());
+        let position = data::ReportFormat::ALL
+            .iter()
+            .position(|&f| f == format)
+            .unwrap_or(0);
+        imp.format_row.set_selected(position as u32);
+    }
+
+    /// Gets the format selected in the format row.
+    fn format(&self) -> data::ReportFormat {
+        data::ReportFormat::ALL
+            .get(self.imp().format_row.selected() as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn text(&self) -> String {
+        self.imp().report.borrow().to_text(self.format())
+    }
+
+    fn update_text(&self) {
+        self.imp().text_view.buffer().set_text(&self.text());
+    }
+
+    #[template_callback]
+    fn format_selected_cb(&self, _pspec: &glib::ParamSpec, _row: &adw::ComboRow) {
+        if let Err(e) = self
+            .settings()
+            .set_string("report-format", self.format().nick())
+        {
+            log::error!("Failed to save report format. cause: {}", e);
+        }
+        self.update_text();
+    }
+
+    #[template_callback]
+    fn copy_button_clicked_cb(&self, _button: &gtk::Button) {
+        self.clipboard().set_text(&self.text());
+        self.imp()
+            .toast_overlay
+            .add_toast(adw::Toast::new("Report Copied"));
+    }
+
+    #[template_callback]
+    fn save_button_clicked_cb(&self, _button: &gtk::Button) {
+        let format = self.format();
+
+        let filter = gtk::FileFilter::new();
+        filter.set_name(Some(format.label()));
+        filter.add_mime_type(format.mime_type());
+        let filters = gio::ListStore::new::<gtk::FileFilter>();
+        filters.append(&filter);
+
+        let dialog = gtk::FileDialog::builder()
+            .title("Save Report")
+            .initial_name(format!(
+                "{}.{}",
+                self.imp().report.borrow().title,
+                format.file_extension()
+            ))
+            .filters(&filters)
+            .modal(true)
+            .build();
+        let text = self.text();
+        dialog.save(
+            Some(self),
+            gio::Cancellable::NONE,
+            glib::clone!(@weak self as obj => move |result| {
+                let Ok(file) = result else {
+                    return;
+                };
+                let toast = match file.replace_contents(
+                    text.as_bytes(),
+                    None,
+                    false,
+                    gio::FileCreateFlags::REPLACE_DESTINATION,
+                    gio::Cancellable::NONE,
+                ) {
+                    Ok(_) => adw::Toast::new("Report Saved"),
+                    Err(e) => {
+                        log::error!("Failed to save report. cause: {}", e);
+                        adw::Toast::new("Failed to Save Report")
+                    }
+                };
+                obj.imp().toast_overlay.add_toast(toast);
+            }),
+        );
+    }
+}
+
+/// Gets the report format last chosen, which quick copy actions also use.
+pub fn report_format(settings: &gio::Settings) -> data::ReportFormat {
+    data::ReportFormat::from_nick(&settings.string("report-format")).unwrap_or_default()
+}
@@ -0,0 +1,243 @@
+use crate::data;
+use crate::ui;
+use adw::prelude::*;
+use adw::subclass::prelude::*;
+use gtk::{gdk, gio, glib};
+
+/// Number of bins the distributions are split into.
+const N_BINS: usize = 12;
+
+#[doc(hidden)]
+mod imp {
+    use std::cell::RefCell;
+
+    use once_cell::sync::OnceCell;
+
+    use super::*;
+
+    #[derive(Debug, Default, gtk::CompositeTemplate, glib::Properties)]
+    #[template(resource = "/io/github/manenfu/PrismaTimer/ui/session_comparison_window.ui")]
+    #[properties(wrapper_type = super::SessionComparisonWindow)]
+    pub struct SessionComparisonWindow {
+        #[template_child]
+        pub first_session_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub first_from_row: TemplateChild<adw::EntryRow>,
+        #[template_child]
+        pub first_to_row: TemplateChild<adw::EntryRow>,
+        #[template_child]
+        pub second_session_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub second_from_row: TemplateChild<adw::EntryRow>,
+        #[template_child]
+        pub second_to_row: TemplateChild<adw::EntryRow>,
+        #[template_child]
+        pub statistics_grid: TemplateChild<gtk::Grid>,
+        #[template_child]
+        pub distribution_chart: TemplateChild<ui::DistributionChart>,
+        #[template_child]
+        pub significance_row: TemplateChild<adw::ActionRow>,
+
+        #[property(get, construct_only)]
+        pub sessions: OnceCell<gio::ListStore>,
+
+        /// Sessions currently compared, with the handler updating the
+        /// comparison when their solves change.
+        pub(super) session_handlers: RefCell<Vec<(data::Session, glib::SignalHandlerId)>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for SessionComparisonWindow {
+        const NAME: &'static str = "PtSessionComparisonWindow";
+        type Type = super::SessionComparisonWindow;
+        type ParentType = adw::Window;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_instance_callbacks();
+
+            klass.add_binding_action(
+                gdk::Key::Escape,
+                gdk::ModifierType::empty(),
+                "window.close",
+                None,
+            );
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for SessionComparisonWindow {
+        fn constructed(&self) {
+            self.parent_constructed();
+
+            let sessions = self.obj().sessions();
+            self.first_session_row.set_model(Some(&sessions));
+            self.second_session_row.set_model(Some(&sessions));
+        }
+
+        fn dispose(&self) {
+            for (session, id) in self.session_handlers.take() {
+                session.disconnect(id);
+            }
+        }
+    }
+
+    impl WidgetImpl for SessionComparisonWindow {}
+    impl WindowImpl for SessionComparisonWindow {}
+    impl AdwWindowImpl for SessionComparisonWindow {}
+}
+
+glib::wrapper! {
+    /// A window comparing the solves of two sessions, or of two date ranges
+    /// of sessions, side by side.
+    pub struct SessionComparisonWindow(ObjectSubclass<imp::SessionComparisonWindow>)
+        @extends gtk::Widget, gtk::Window, adw::Window;
+}
+
+#[gtk::template_callbacks]
+impl SessionComparisonWindow {
+    /// Creates a window comparing the session at `position` in `sessions`
+    /// with the next one, or the previous one if it is the last.
+    pub fn new(sessions: &gio::ListStore, position: u32) -> Self {
+        let obj: Self = glib::Object::builder()
+            .property("sessions", sessions)
+            .build();
+
+        let imp = obj.imp();
+        let other = if position + 1 < sessions.n_items() {
+            position + 1
+        } else {
+            position.saturating_sub(1)
+        };
+        imp.first_session_row.set_selected(position);
+        imp.second_session_row.set_selected(other);
+        obj.connect_sessions();
+        obj.update_comparison();
+        obj
+    }
+
+    /// Gets the times of one side of the comparison, or `None` if no
+    /// session is selected.
+    fn side_times(
+        session_row: &adw::ComboRow,
+        from_row: &adw::EntryRow,
+        to_row: &adw::EntryRow,
+    ) -> Option<Vec<data::SolveTime>> {
+        let session = session_row
+            .selected_item()
+            .and_downcast::<data::Session>()?;
+        Some(session.times_between(parse_row(from_row), parse_row(to_row)))
+    }
+
+    /// Follows the solves of the selected sessions, to update the comparison
+    /// when they change.
+    fn connect_sessions(&self) {
+        let imp = self.imp();
+        for (session, id) in imp.session_handlers.take() {
+            session.disconnect(id);
+        }
+
+        let mut handlers = Vec::new();
+        for row in [&imp.first_session_row, &imp.second_session_row] {
+            let Some(session) = row.selected_item().and_downcast::<data::Session>() else {
+                continue;
+            };
+            if handlers.iter().any(|(s, _)| *s == session) {
+                continue;
+            }
+            let id = session.connect_items_changed(
+                glib::clone!(@weak self as obj => move |_, _, _, _| {
+                    obj.update_comparison();
+                }),
+            );
+            handlers.push((session, id));
+        }
+        imp.session_handlers.replace(handlers);
+    }
+
+    /// Recalculates the statistics, distributions and significance of both
+    /// sides.
+    fn update_comparison(&self) {
+        let imp = self.imp();
+        let first = Self::side_times(
+            &imp.first_session_row,
+            &imp.first_from_row,
+            &imp.first_to_row,
+        )
+        .unwrap_or_default();
+        let second = Self::side_times(
+            &imp.second_session_row,
+            &imp.second_from_row,
+            &imp.second_to_row,
+        )
+        .unwrap_or_default();
+        let comparison = data::SessionComparison::new(&first, &second);
+        let precision = imp
+            .first_session_row
+            .selected_item()
+            .and_downcast::<data::Session>()
+            .map(|session| session.time_precision())
+            .unwrap_or_default();
+
+        let grid = &imp.statistics_grid;
+        while let Some(child) = grid.first_child() {
+            grid.remove(&child);
+        }
+        let header = [("", 0), ("First", 1), ("Second", 2)];
+        for (text, column) in header {
+            let label = gtk::Label::builder().label(text).xalign(1.0).build();
+            label.add_css_class("heading");
+            grid.attach(&label, column, 0, 1, 1);
+        }
+        for (row, (name, first, second)) in comparison.rows(precision).into_iter().enumerate() {
+            let row = row as i32 + 1;
+            let name = gtk::Label::builder().label(name).xalign(0.0).build();
+            name.add_css_class("dim-label");
+            grid.attach(&name, 0, row, 1, 1);
+            for (column, value) in [(1, first), (2, second)] {
+                let label = gtk::Label::builder()
+                    .label(value)
+                    .xalign(1.0)
+                    .selectable(true)
+                    .build();
+                label.add_css_class("numeric");
+                grid.attach(&label, column, row, 1, 1);
+            }
+        }
+
+        imp.distribution_chart
+            .set_histograms(data::Histogram::pair(&first, &second, N_BINS));
+
+        imp.significance_row.set_title(&comparison.describe_test());
+        imp.significance_row
+            .set_subtitle(&comparison.describe_odds().unwrap_or_default());
+    }
+
+    #[template_callback]
+    fn session_changed_cb(&self, _pspec: &glib::ParamSpec, _row: &adw::ComboRow) {
+        self.connect_sessions();
+        self.update_comparison();
+    }
+
+    #[template_callback]
+    fn date_changed_cb(&self, _row: &adw::EntryRow) {
+        self.update_comparison();
+    }
+}
+
+/// Parses the date of a row, marking the row as an error if the text can't
+/// be parsed. Empty rows don't limit the range.
+fn parse_row(row: &adw::EntryRow) -> Option<chrono::NaiveDate> {
+    let text = row.text();
+    let date = data::parse_date(&text);
+    if date.is_none() && !text.trim().is_empty() {
+        row.add_css_class("error");
+    } else {
+        row.remove_css_class("error");
+    }
+    date
+}
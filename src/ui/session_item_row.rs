@@ -15,8 +15,35 @@ mod imp {
     #[template(resource = "/io/github/manenfu/PrismaTimer/ui/session_item_row.ui")]
     #[properties(wrapper_type = super::SessionItemRow)]
     pub struct SessionItemRow {
-        #[property(get, set)]
+        #[template_child]
+        pub tags_box: TemplateChild<gtk::Box>,
+
+        #[property(get, set = Self::set_item, nullable)]
         pub item: RefCell<Option<data::SessionItem>>,
+        item_handler: RefCell<Option<glib::SignalHandlerId>>,
+    }
+
+    impl SessionItemRow {
+        fn set_item(&self, v: Option<data::SessionItem>) {
+            let obj = self.obj();
+
+            if let Some(item) = self.item.take() {
+                if let Some(id) = self.item_handler.take() {
+                    item.disconnect(id);
+                }
+            }
+
+            if let Some(item) = &v {
+                self.item_handler
+                    .replace(Some(item.connect_tags_string_notify(
+                        glib::clone!(@weak obj => move |_| {
+                            obj.update_tags();
+                        }),
+                    )));
+            }
+            self.item.replace(v);
+            obj.update_tags();
+        }
     }
 
     #[glib::object_subclass]
@@ -51,6 +78,24 @@ impl SessionItemRow {
     pub fn new() -> Self {
         glib::Object::builder().build()
     }
+
+    /// Shows a chip for each tag of the item.
+    fn update_tags(&self) {
+        let imp = self.imp();
+        while let Some(child) = imp.tags_box.first_child() {
+            imp.tags_box.remove(&child);
+        }
+
+        let tags = self.item().map(|item| item.tags()).unwrap_or_default();
+        for tag in &tags {
+            let chip = gtk::Label::builder()
+                .label(tag)
+                .css_classes(["tag-chip", "caption"])
+                .build();
+            imp.tags_box.append(&chip);
+        }
+        imp.tags_box.set_visible(!tags.is_empty());
+    }
 }
 
 impl Default for SessionItemRow {
@@ -0,0 +1,247 @@
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+use gtk::prelude::*;
+use gtk::{cairo, glib};
+
+use crate::data;
+
+/// Size of regular text, in points.
+const FONT_SIZE: f64 = 9.0;
+/// Height of each block of the report, in points.
+const TITLE_HEIGHT: f64 = 32.0;
+const HEADING_HEIGHT: f64 = 26.0;
+const ROW_HEIGHT: f64 = 13.0;
+const CHART_HEIGHT: f64 = 170.0;
+
+/// A part of the report that is drawn on a single page.
+#[derive(Debug, Clone)]
+enum Block {
+    Title(String),
+    Heading(String),
+    /// Texts drawn at these offsets from the left of the page.
+    Row {
+        columns: Vec<(f64, String)>,
+        monospace: bool,
+    },
+    TimeGraph(data::TimeGraph),
+    Histogram(data::Histogram),
+}
+
+impl Block {
+    fn row(columns: &[(f64, &str)]) -> Self {
+        Self::Row {
+            columns: columns.iter().map(|&(x, s)| (x, s.to_string())).collect(),
+            monospace: false,
+        }
+    }
+
+    fn height(&self) -> f64 {
+        match self {
+            Self::Title(_) => TITLE_HEIGHT,
+            Self::Heading(_) => HEADING_HEIGHT,
+            Self::Row { .. } => ROW_HEIGHT,
+            Self::TimeGraph(_) | Self::Histogram(_) => CHART_HEIGHT,
+        }
+    }
+}
+
+/// Renders a session report to a PDF file, using a print operation that
+/// exports to the file instead of printing.
+pub fn export_session_report_pdf(
+    report: &data::SessionReport,
+    path: &Path,
+    parent: &impl IsA<gtk::Window>,
+) -> Result<(), glib::Error> {
+    let operation = gtk::PrintOperation::new();
+    operation.set_job_name(&report.title);
+    operation.set_export_filename(path);
+    operation.set_unit(gtk::Unit::Points);
+
+    let blocks = report_blocks(report);
+    let pages: Rc<RefCell<Vec<Vec<Block>>>> = Rc::default();
+
+    operation.connect_begin_print(glib::clone!(@strong pages => move |operation, context| {
+        let paginated = paginate(&blocks, context.height());
+        operation.set_n_pages(paginated.len() as i32);
+        pages.replace(paginated);
+    }));
+
+    operation.connect_draw_page(move |_, context, page| {
+        let pages = pages.borrow();
+        let Some(blocks) = pages.get(page as usize) else {
+            return;
+        };
+        if let Err(e) = draw_page(&context.cairo_context(), context.width(), blocks) {
+            log::error!("Failed to draw page {} of report. cause: {}", page, e);
+        }
+    });
+
+    operation.run(gtk::PrintOperationAction::Export, Some(parent))?;
+    Ok(())
+}
+
+/// Lays out the report as a sequence of blocks, in the order of the HTML
+/// report.
+fn report_blocks(report: &data::SessionReport) -> Vec<Block> {
+    let mut blocks = vec![Block::Title(report.title.clone())];
+
+    blocks.push(Block::Heading("Statistics".to_string()));
+    for (name, value) in &report.statistics {
+        blocks.push(Block::row(&[(0.0, name.as_str()), (120.0, value.as_str())]));
+    }
+
+    if !report.personal_bests.is_empty() {
+        blocks.push(Block::Heading("Personal Bests".to_string()));
+        for pb in &report.personal_bests {
+            let description = report.describe_personal_best(pb);
+            blocks.push(Block::row(&[(0.0, description.as_str())]));
+        }
+    }
+
+    let times = report.times();
+    if let Some(graph) = data::TimeGraph::new(&times) {
+        blocks.push(Block::Heading("Times".to_string()));
+        blocks.push(Block::TimeGraph(graph));
+    }
+    if let Some(histogram) = data::Histogram::new(&times, 12) {
+        blocks.push(Block::Heading("Distribution".to_string()));
+        blocks.push(Block::Histogram(histogram));
+    }
+
+    blocks.push(Block::Heading("Solves".to_string()));
+    for solve in &report.solves {
+        let mut scramble_lines = solve.scramble.lines();
+        blocks.push(Block::Row {
+            columns: vec![
+                (0.0, solve.number.to_string()),
+                (36.0, solve.time.format(report.precision)),
+                (96.0, scramble_lines.next().unwrap_or_default().to_string()),
+            ],
+            monospace: true,
+        });
+        for line in scramble_lines {
+            blocks.push(Block::Row {
+                columns: vec![(96.0, line.to_string())],
+                monospace: true,
+            });
+        }
+        if !solve.comment.is_empty() {
+            blocks.push(Block::row(&[(96.0, solve.comment.as_str())]));
+        }
+    }
+    blocks
+}
+
+/// Splits the blocks into pages of this height. Headings are moved to the
+/// next page if nothing else fits after them.
+fn paginate(blocks: &[Block], page_height: f64) -> Vec<Vec<Block>> {
+    let mut pages = vec![Vec::new()];
+    let mut y = 0.0;
+
+    for (i, block) in blocks.iter().enumerate() {
+        let mut height = block.height();
+        if matches!(block, Block::Heading(_)) {
+            height += blocks.get(i + 1).map_or(0.0, Block::height);
+        }
+        if y + height > page_height && y > 0.0 {
+            pages.push(Vec::new());
+            y = 0.0;
+        }
+        y += block.height();
+        pages
+            .last_mut()
+            .expect("`pages` should never be empty")
+            .push(block.clone());
+    }
+    pages
+}
+
+fn draw_page(cr: &cairo::Context, width: f64, blocks: &[Block]) -> Result<(), cairo::Error> {
+    let mut y = 0.0;
+    for block in blocks {
+        cr.set_source_rgb(0.14, 0.12, 0.19);
+        match block {
+            Block::Title(title) => {
+                cr.select_font_face("Sans", cairo::FontSlant::Normal, cairo::FontWeight::Bold);
+                cr.set_font_size(18.0);
+                cr.move_to(0.0, y + 20.0);
+                cr.show_text(title)?;
+            }
+            Block::Heading(heading) => {
+                cr.select_font_face("Sans", cairo::FontSlant::Normal, cairo::FontWeight::Bold);
+                cr.set_font_size(12.0);
+                cr.move_to(0.0, y + 20.0);
+                cr.show_text(heading)?;
+            }
+            Block::Row { columns, monospace } => {
+                let family = if *monospace { "Monospace" } else { "Sans" };
+                cr.select_font_face(family, cairo::FontSlant::Normal, cairo::FontWeight::Normal);
+                cr.set_font_size(FONT_SIZE);
+                for (x, text) in columns {
+                    cr.move_to(*x, y + FONT_SIZE);
+                    cr.show_text(text)?;
+                }
+            }
+            Block::TimeGraph(graph) => {
+                let height = CHART_HEIGHT - 20.0;
+                cr.save()?;
+                cr.translate(0.0, y + 10.0);
+                for (values, (r, g, b), line_width) in [
+                    (&graph.singles, (0.6, 0.76, 0.95), 0.75),
+                    (&graph.ao5, (0.11, 0.44, 0.85), 1.5),
+                ] {
+                    cr.set_source_rgb(r, g, b);
+                    cr.set_line_width(line_width);
+                    for line in graph.lines(values, width, height) {
+                        for (i, &(x, y)) in line.iter().enumerate() {
+                            if i == 0 {
+                                cr.move_to(x, y);
+                            } else {
+                                cr.line_to(x, y);
+                            }
+                        }
+                        cr.stroke()?;
+                    }
+                }
+                cr.set_source_rgb(0.37, 0.36, 0.39);
+                cr.select_font_face("Sans", cairo::FontSlant::Normal, cairo::FontWeight::Normal);
+                cr.set_font_size(7.0);
+                cr.move_to(2.0, 8.0);
+                cr.show_text(&format!("{:.2}", graph.max))?;
+                cr.move_to(2.0, height - 2.0);
+                cr.show_text(&format!("{:.2}", graph.min))?;
+                cr.restore()?;
+            }
+            Block::Histogram(histogram) => {
+                let height = CHART_HEIGHT - 30.0;
+                cr.save()?;
+                cr.translate(0.0, y + 10.0);
+                cr.set_source_rgb(0.38, 0.63, 0.92);
+                for (x, y, width, height) in histogram.bars(width, height) {
+                    cr.rectangle(x + 1.0, y, (width - 2.0).max(0.5), height);
+                }
+                cr.fill()?;
+
+                cr.set_source_rgb(0.37, 0.36, 0.39);
+                cr.select_font_face("Sans", cairo::FontSlant::Normal, cairo::FontWeight::Normal);
+                cr.set_font_size(7.0);
+                let bar_width = width / histogram.counts.len() as f64;
+                for (i, &count) in histogram.counts.iter().enumerate() {
+                    let start = histogram.start + histogram.bin_width * i as u32;
+                    let label = data::SolveTime::new(start, data::Penalty::Ok).to_string();
+                    cr.move_to(bar_width * i as f64 + 1.0, height + 10.0);
+                    cr.show_text(&label)?;
+                    if count > 0 {
+                        cr.move_to(bar_width * i as f64 + 1.0, height + 19.0);
+                        cr.show_text(&format!("×{}", count))?;
+                    }
+                }
+                cr.restore()?;
+            }
+        }
+        y += block.height();
+    }
+    Ok(())
+}
@@ -1,8 +1,11 @@
-use crate::data::{self, SolveStatistic};
-use crate::ui;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::util::TemplateCallbacks;
+use crate::{config, data, ui};
 use adw::prelude::*;
 use adw::subclass::prelude::*;
-use gtk::glib;
+use gtk::{gio, glib};
 
 #[doc(hidden)]
 mod imp {
@@ -16,6 +19,14 @@ mod imp {
     #[template(resource = "/io/github/manenfu/PrismaTimer/ui/solve_dialog.ui")]
     #[properties(wrapper_type = super::SolveDialog)]
     pub struct SolveDialog {
+        #[template_child]
+        pub scramble_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub scramble_entry_row: TemplateChild<adw::EntryRow>,
+        #[template_child]
+        pub comment_row: TemplateChild<adw::EntryRow>,
+        #[template_child]
+        pub tags_row: TemplateChild<adw::EntryRow>,
         #[template_child]
         pub average_group: TemplateChild<adw::PreferencesGroup>,
         #[template_child]
@@ -26,6 +37,12 @@ mod imp {
         pub ao12_expander_row: TemplateChild<adw::ExpanderRow>,
         #[template_child]
         pub ao12_report_area: TemplateChild<ui::TextAreaRow>,
+        #[template_child]
+        pub analysis_group: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        pub analysis_spinner: TemplateChild<gtk::Spinner>,
+        #[template_child]
+        pub analysis_button: TemplateChild<gtk::Button>,
 
         #[property(get, construct_only)]
         pub session: OnceCell<data::Session>,
@@ -34,8 +51,13 @@ mod imp {
         #[property(get, construct_only)]
         pub solve: RefCell<Option<data::SessionItem>>,
 
-        pub last_5_solves: RefCell<Option<Vec<data::SessionItem>>>,
-        pub last_12_solves: RefCell<Option<Vec<data::SessionItem>>>,
+        pub ao5_report: RefCell<Option<data::Report>>,
+        pub ao12_report: RefCell<Option<data::Report>>,
+
+        /// Rows of the solutions of each target of the analysis.
+        pub(super) analysis_rows: RefCell<Vec<adw::ActionRow>>,
+        /// Flag stopping the running analysis, if any.
+        pub(super) analysis_cancelled: RefCell<Option<Arc<AtomicBool>>>,
     }
 
     #[glib::object_subclass]
@@ -47,6 +69,7 @@ mod imp {
         fn class_init(klass: &mut Self::Class) {
             klass.bind_template();
             klass.bind_template_instance_callbacks();
+            TemplateCallbacks::bind_template_callbacks(klass);
         }
 
         fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
@@ -65,7 +88,16 @@ mod imp {
     }
 
     impl WidgetImpl for SolveDialog {}
-    impl WindowImpl for SolveDialog {}
+
+    impl WindowImpl for SolveDialog {
+        fn close_request(&self) -> glib::Propagation {
+            // The search runs on another thread, which would otherwise keep
+            // going after the dialog is gone.
+            self.obj().cancel_analysis();
+            self.parent_close_request()
+        }
+    }
+
     impl AdwWindowImpl for SolveDialog {}
 }
 
@@ -88,19 +120,27 @@ impl SolveDialog {
 
     fn setup(&self) {
         let imp = self.imp();
-        let session = self.session();
         let index = self.index();
         self.set_title(Some(&Self::create_window_title(index)));
         imp.average_group.set_visible(index >= 4);
+        imp.analysis_group
+            .set_visible(self.analysis_scramble().is_some());
 
-        imp.last_5_solves
-            .replace(session.get_solve_slice(index as usize, 5));
-        imp.last_12_solves
-            .replace(session.get_solve_slice(index as usize, 12));
         self.update_ao5_expander_row();
         self.update_ao12_expander_row();
 
         if let Some(solve) = self.solve() {
+            solve
+                .bind_property("comment", &*imp.comment_row, "text")
+                .sync_create()
+                .bidirectional()
+                .build();
+            imp.tags_row.set_text(&solve.tags_string());
+
+            let editable = self.scramble_size().is_some();
+            imp.scramble_row.set_visible(!editable);
+            imp.scramble_entry_row.set_visible(editable);
+            imp.scramble_entry_row.set_text(&solve.scramble());
             solve.connect_notify_local(
                 Some("solve-time-string"),
                 glib::clone!(@weak self as obj => move |_, _| {
@@ -111,39 +151,205 @@ impl SolveDialog {
         }
     }
 
+    /// Saves the tags entered in the tags row.
+    #[template_callback]
+    fn tags_applied_cb(&self, row: &adw::EntryRow) {
+        let Some(solve) = self.solve() else {
+            return;
+        };
+        self.session()
+            .set_tags(self.index() as usize, data::parse_tags(&row.text()));
+        row.set_text(&solve.tags_string());
+    }
+
+    /// Gets the size of the cube the scramble of the solve is written for,
+    /// if it can be edited. Fewest Moves scrambles are kept, since the
+    /// solution was checked against them.
+    fn scramble_size(&self) -> Option<u8> {
+        match self.session().kind() {
+            data::SessionKind::Standard | data::SessionKind::Blindfolded => Some(3),
+            _ => None,
+        }
+    }
+
+    /// Saves the scramble entered in the scramble row in the usual form of
+    /// its notation, or points out where it is wrong.
+    #[template_callback]
+    fn scramble_applied_cb(&self, row: &adw::EntryRow) {
+        let (Some(solve), Some(size)) = (self.solve(), self.scramble_size()) else {
+            return;
+        };
+        match data::normalize_scramble(size, &row.text()) {
+            Ok(scramble) => {
+                solve.set_scramble(scramble);
+                row.set_text(&solve.scramble());
+            }
+            Err(e) => {
+                row.add_css_class("error");
+                row.set_title(&e.to_string());
+                row.grab_focus();
+                row.set_position(e.position as i32);
+            }
+        }
+    }
+
+    #[template_callback]
+    fn scramble_changed_cb(&self, row: &adw::EntryRow) {
+        row.remove_css_class("error");
+        row.set_title("Scramble");
+    }
+
+    /// Gets the scramble of the solve if it is of a single 3x3x3 cube.
+    fn analysis_scramble(&self) -> Option<Vec<data::Move>> {
+        let kind = self.session().kind();
+        if kind.is_relay() || kind == data::SessionKind::MultiBlind {
+            return None;
+        }
+        let scramble = data::parse_moves(&self.solve()?.scramble()).ok()?;
+        (!scramble.is_empty()).then_some(scramble)
+    }
+
+    #[template_callback]
+    fn analysis_button_clicked_cb(&self) {
+        if self.imp().analysis_cancelled.borrow().is_some() {
+            self.cancel_analysis();
+        } else {
+            self.analyze();
+        }
+    }
+
+    /// Searches for the solution of each target one after the other on
+    /// another thread, showing each as soon as it is found.
+    fn analyze(&self) {
+        let imp = self.imp();
+        let Some(scramble) = self.analysis_scramble() else {
+            return;
+        };
+
+        for row in imp.analysis_rows.take() {
+            imp.analysis_group.remove(&row);
+        }
+        let rows: Vec<adw::ActionRow> = data::SolverTarget::ALL
+            .iter()
+            .map(|target| {
+                adw::ActionRow::builder()
+                    .title(target.label())
+                    .subtitle("Searching…")
+                    .subtitle_selectable(true)
+                    .build()
+            })
+            .collect();
+        for row in &rows {
+            imp.analysis_group.add(row);
+        }
+        imp.analysis_rows.replace(rows.clone());
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        imp.analysis_cancelled.replace(Some(cancelled.clone()));
+        imp.analysis_spinner.set_visible(true);
+        imp.analysis_spinner.set_spinning(true);
+        imp.analysis_button.set_label("_Cancel");
+
+        let obj = self.downgrade();
+        glib::MainContext::default().spawn_local(async move {
+            for (target, row) in data::SolverTarget::ALL.into_iter().zip(rows) {
+                let scramble = scramble.clone();
+                let flag = cancelled.clone();
+                let solution = gio::spawn_blocking(move || target.solve(&scramble, &flag)).await;
+                match solution {
+                    Ok(Some(solution)) => {
+                        let count = data::htm_count(&solution);
+                        let moves = if count == 1 { "move" } else { "moves" };
+                        row.set_subtitle(&format!(
+                            "{} ({} {})",
+                            data::format_moves(&solution),
+                            count,
+                            moves
+                        ));
+                    }
+                    _ => break,
+                }
+            }
+            if let Some(obj) = obj.upgrade() {
+                obj.analysis_finished(&cancelled);
+            }
+        });
+    }
+
+    /// Stops the running analysis, if any.
+    fn cancel_analysis(&self) {
+        if let Some(cancelled) = self.imp().analysis_cancelled.borrow().as_ref() {
+            cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+
+    fn analysis_finished(&self, cancelled: &Arc<AtomicBool>) {
+        let imp = self.imp();
+        // A new analysis may have started since this one was cancelled.
+        let current = imp.analysis_cancelled.borrow().clone();
+        if !current.is_some_and(|current| Arc::ptr_eq(&current, cancelled)) {
+            return;
+        }
+
+        imp.analysis_cancelled.replace(None);
+        imp.analysis_spinner.set_spinning(false);
+        imp.analysis_spinner.set_visible(false);
+        imp.analysis_button.set_label("_Analyze");
+        if cancelled.load(Ordering::Relaxed) {
+            for row in imp.analysis_rows.borrow().iter() {
+                if row.subtitle().as_deref() == Some("Searching…") {
+                    row.set_subtitle("Cancelled");
+                }
+            }
+        }
+    }
+
     fn update_ao5_expander_row(&self) {
         let imp = self.imp();
-        Self::update_average_expander(
-            &imp.ao5_expander_row,
-            &imp.ao5_report_area,
-            imp.last_5_solves.borrow().as_ref().map(Vec::as_slice),
-        );
+        let report = self.session().average_report(self.index() as usize, 5);
+        Self::update_average_expander(&imp.ao5_expander_row, &imp.ao5_report_area, &report);
+        imp.ao5_report.replace(report);
     }
 
     fn update_ao12_expander_row(&self) {
         let imp = self.imp();
-        Self::update_average_expander(
-            &imp.ao12_expander_row,
-            &imp.ao12_report_area,
-            imp.last_12_solves.borrow().as_ref().map(Vec::as_slice),
-        );
+        let report = self.session().average_report(self.index() as usize, 12);
+        Self::update_average_expander(&imp.ao12_expander_row, &imp.ao12_report_area, &report);
+        imp.ao12_report.replace(report);
     }
 
     fn update_average_expander(
         expander_row: &adw::ExpanderRow,
         text_area_row: &ui::TextAreaRow,
-        solves: Option<&[data::SessionItem]>,
+        report: &Option<data::Report>,
     ) {
-        if let Some(solves) = solves {
+        if let Some(report) = report {
+            let format = ui::report_format(&gio::Settings::new(config::APP_ID));
             expander_row.set_visible(true);
-            text_area_row
-                .buffer()
-                .set_text(&generate_average_of_n_report(solves).unwrap_or_default());
+            text_area_row.buffer().set_text(&report.to_text(format));
         } else {
             expander_row.set_visible(false);
         }
     }
 
+    fn present_report_dialog(&self, report: Option<data::Report>) {
+        if let Some(report) = report {
+            let dialog = ui::ReportDialog::new(report);
+            dialog.set_transient_for(Some(self));
+            dialog.present();
+        }
+    }
+
+    #[template_callback]
+    fn ao5_share_button_clicked_cb(&self, _button: &gtk::Button) {
+        self.present_report_dialog(self.imp().ao5_report.borrow().clone());
+    }
+
+    #[template_callback]
+    fn ao12_share_button_clicked_cb(&self, _button: &gtk::Button) {
+        self.present_report_dialog(self.imp().ao12_report.borrow().clone());
+    }
+
     #[template_callback(function)]
     fn create_window_title(index: u32) -> String {
         format!("Solve {}", index + 1)
@@ -180,25 +386,3 @@ impl SolveDialog {
         self.close();
     }
 }
-
-fn generate_average_of_n_report(solves: &[data::SessionItem]) -> Option<String> {
-    let ibest = solves.best_solve_index()?;
-    let iworst = solves.worst_solve_index()?;
-    let header = [
-        format!("avg of {}: {}\n\n", solves.len(), solves.average_of_n()?),
-        "Time list:\n".to_string(),
-    ]
-    .into_iter();
-    let time_list = solves
-        .iter()
-        .map(Into::<data::SolveTime>::into)
-        .enumerate()
-        .map(|(i, time)| {
-            if i == ibest || i == iworst {
-                format!("{}. ({})\n", i + 1, time)
-            } else {
-                format!("{}. {}\n", i + 1, time)
-            }
-        });
-    Some(String::from_iter(header.chain(time_list)))
-}
@@ -0,0 +1,155 @@
+use adw::subclass::prelude::*;
+use gtk::glib;
+use gtk::prelude::*;
+
+use crate::data;
+
+/// Penalties selectable in the penalty dropdown, after "Any Penalty".
+const PENALTIES: [data::Penalty; 3] = [data::Penalty::Ok, data::Penalty::Plus2, data::Penalty::Dnf];
+
+#[doc(hidden)]
+mod imp {
+    use std::cell::Cell;
+
+    use gtk::glib::subclass::{Signal, SignalType};
+    use once_cell::sync::Lazy;
+
+    use super::*;
+
+    #[derive(Debug, Default, gtk::CompositeTemplate, glib::Properties)]
+    #[template(resource = "/io/github/manenfu/PrismaTimer/ui/solve_filter_bar.ui")]
+    #[properties(wrapper_type = super::SolveFilterBar)]
+    pub struct SolveFilterBar {
+        #[template_child]
+        pub search_bar: TemplateChild<gtk::SearchBar>,
+        #[template_child]
+        pub search_entry: TemplateChild<gtk::SearchEntry>,
+        #[template_child]
+        pub tag_entry: TemplateChild<gtk::Entry>,
+        #[template_child]
+        pub penalty_dropdown: TemplateChild<gtk::DropDown>,
+        #[template_child]
+        pub min_time_entry: TemplateChild<gtk::Entry>,
+        #[template_child]
+        pub max_time_entry: TemplateChild<gtk::Entry>,
+        #[template_child]
+        pub from_date_entry: TemplateChild<gtk::Entry>,
+        #[template_child]
+        pub to_date_entry: TemplateChild<gtk::Entry>,
+
+        #[property(get, set)]
+        pub search_mode_enabled: Cell<bool>,
+        /// Whether statistics of the solves matching the filter are shown.
+        #[property(get, set)]
+        pub show_statistics: Cell<bool>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for SolveFilterBar {
+        const NAME: &'static str = "PtSolveFilterBar";
+        type Type = super::SolveFilterBar;
+        type ParentType = adw::Bin;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_instance_callbacks();
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for SolveFilterBar {
+        fn signals() -> &'static [Signal] {
+            static SIGNALS: Lazy<Vec<Signal>> = Lazy::new(|| {
+                vec![Signal::builder("changed")
+                    .param_types(Vec::<SignalType>::new())
+                    .build()]
+            });
+            SIGNALS.as_ref()
+        }
+    }
+
+    impl WidgetImpl for SolveFilterBar {}
+    impl BinImpl for SolveFilterBar {}
+}
+
+glib::wrapper! {
+    /// A search bar filtering solves by text, tag, penalty, time and date.
+    pub struct SolveFilterBar(ObjectSubclass<imp::SolveFilterBar>)
+        @extends gtk::Widget, adw::Bin,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+#[gtk::template_callbacks]
+impl SolveFilterBar {
+    pub fn new() -> Self {
+        glib::Object::builder().build()
+    }
+
+    /// Gets the filter entered in the bar. Every solve matches while the bar
+    /// is hidden, and criteria that can't be parsed are ignored.
+    pub fn filter(&self) -> data::SolveFilter {
+        let imp = self.imp();
+        if !self.search_mode_enabled() {
+            return data::SolveFilter::default();
+        }
+
+        data::SolveFilter {
+            text: imp.search_entry.text().to_string(),
+            tag: Some(imp.tag_entry.text().trim().to_string()).filter(|tag| !tag.is_empty()),
+            penalty: (imp.penalty_dropdown.selected() as usize)
+                .checked_sub(1)
+                .and_then(|i| PENALTIES.get(i).copied()),
+            min_time: parse_entry(&imp.min_time_entry, data::parse_time),
+            max_time: parse_entry(&imp.max_time_entry, data::parse_time),
+            from_date: parse_entry(&imp.from_date_entry, data::parse_date),
+            to_date: parse_entry(&imp.to_date_entry, data::parse_date),
+        }
+    }
+
+    fn emit_changed(&self) {
+        self.emit_by_name::<()>("changed", &[]);
+    }
+
+    #[template_callback]
+    fn search_changed_cb(&self, _entry: &gtk::SearchEntry) {
+        self.emit_changed();
+    }
+
+    #[template_callback]
+    fn entry_changed_cb(&self, _entry: &gtk::Entry) {
+        self.emit_changed();
+    }
+
+    #[template_callback]
+    fn penalty_changed_cb(&self, _pspec: &glib::ParamSpec, _dropdown: &gtk::DropDown) {
+        self.emit_changed();
+    }
+
+    #[template_callback]
+    fn search_mode_changed_cb(&self, _pspec: &glib::ParamSpec, _search_bar: &gtk::SearchBar) {
+        self.emit_changed();
+    }
+}
+
+impl Default for SolveFilterBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parses the text of an entry, marking the entry as an error if the text
+/// can't be parsed.
+fn parse_entry<T>(entry: &gtk::Entry, parse: impl Fn(&str) -> Option<T>) -> Option<T> {
+    let text = entry.text();
+    let value = parse(&text);
+    if value.is_none() && !text.trim().is_empty() {
+        entry.add_css_class("error");
+    } else {
+        entry.remove_css_class("error");
+    }
+    value
+}
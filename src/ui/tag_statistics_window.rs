@@ -0,0 +1,137 @@
+use crate::data;
+use adw::prelude::*;
+use adw::subclass::prelude::*;
+use gtk::{gdk, glib};
+
+#[doc(hidden)]
+mod imp {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[derive(Debug, Default, gtk::CompositeTemplate, glib::Properties)]
+    #[template(resource = "/io/github/manenfu/PrismaTimer/ui/tag_statistics_window.ui")]
+    #[properties(wrapper_type = super::TagStatisticsWindow)]
+    pub struct TagStatisticsWindow {
+        #[template_child]
+        pub stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub tags_group: TemplateChild<adw::PreferencesGroup>,
+
+        #[property(get, set = Self::set_session, nullable)]
+        pub session: RefCell<Option<data::Session>>,
+        session_handlers: RefCell<Vec<glib::SignalHandlerId>>,
+
+        /// Rows of the list, one for each tag.
+        pub(super) rows: RefCell<Vec<adw::ActionRow>>,
+    }
+
+    impl TagStatisticsWindow {
+        fn set_session(&self, v: Option<data::Session>) {
+            let obj = self.obj();
+
+            self.disconnect_session();
+
+            if let Some(session) = &v {
+                let items_handler =
+                    session.connect_items_changed(glib::clone!(@weak obj => move |_, _, _, _| {
+                        obj.update_statistics();
+                    }));
+                let tags_handler = session.connect_closure(
+                    "tags-changed",
+                    false,
+                    glib::closure_local!(@watch obj => move |_: &data::Session| {
+                        obj.update_statistics();
+                    }),
+                );
+                self.session_handlers
+                    .replace(vec![items_handler, tags_handler]);
+            }
+            self.session.replace(v);
+            obj.update_statistics();
+        }
+
+        fn disconnect_session(&self) {
+            if let Some(session) = self.session.take() {
+                for id in self.session_handlers.take() {
+                    session.disconnect(id);
+                }
+            }
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TagStatisticsWindow {
+        const NAME: &'static str = "PtTagStatisticsWindow";
+        type Type = super::TagStatisticsWindow;
+        type ParentType = adw::Window;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+
+            klass.add_binding_action(
+                gdk::Key::Escape,
+                gdk::ModifierType::empty(),
+                "window.close",
+                None,
+            );
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for TagStatisticsWindow {
+        fn dispose(&self) {
+            self.disconnect_session();
+        }
+    }
+
+    impl WidgetImpl for TagStatisticsWindow {}
+    impl WindowImpl for TagStatisticsWindow {}
+    impl AdwWindowImpl for TagStatisticsWindow {}
+}
+
+glib::wrapper! {
+    /// A window listing the statistics of the solves with each tag in a
+    /// session.
+    pub struct TagStatisticsWindow(ObjectSubclass<imp::TagStatisticsWindow>)
+        @extends gtk::Widget, gtk::Window, adw::Window;
+}
+
+impl TagStatisticsWindow {
+    pub fn new(session: &data::Session) -> Self {
+        glib::Object::builder().property("session", session).build()
+    }
+
+    /// Rebuilds the list of tags, sorted by name.
+    fn update_statistics(&self) {
+        let imp = self.imp();
+        for row in imp.rows.take() {
+            imp.tags_group.remove(&row);
+        }
+
+        let (statistics, precision) = self
+            .session()
+            .map(|session| (session.tag_statistics(), session.time_precision()))
+            .unwrap_or_default();
+        let rows: Vec<_> = statistics
+            .iter()
+            .map(|statistics| {
+                let row = adw::ActionRow::builder()
+                    .title(glib::markup_escape_text(&statistics.tag))
+                    .subtitle(statistics.describe(precision))
+                    .build();
+                imp.tags_group.add(&row);
+                row
+            })
+            .collect();
+
+        let has_tags = !rows.is_empty();
+        imp.rows.replace(rows);
+        imp.stack
+            .set_visible_child_name(if has_tags { "statistics" } else { "empty" });
+    }
+}
@@ -8,6 +8,7 @@ use gtk::glib;
 #[doc(hidden)]
 mod imp {
     use super::*;
+    use std::cell::Cell;
 
     #[derive(Debug, Default, gtk::CompositeTemplate)]
     #[template(resource = "/io/github/manenfu/PrismaTimer/ui/time_label.ui")]
@@ -27,9 +28,15 @@ mod imp {
         #[template_child]
         pub point: TemplateChild<gtk::Label>,
         #[template_child]
-        pub centis: TemplateChild<gtk::Label>,
+        pub fraction: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub inspection: TemplateChild<gtk::Label>,
         #[template_child]
         pub dnf: TemplateChild<gtk::Label>,
+
+        pub solve_time: Cell<data::SolveTime>,
+        /// Precision times are displayed with.
+        pub precision: Cell<data::TimePrecision>,
     }
 
     #[glib::object_subclass]
@@ -69,6 +76,7 @@ impl TimeLabel {
 
     pub fn set_solve_time(&self, solve_time: data::SolveTime) {
         let imp = self.imp();
+        imp.solve_time.set(solve_time);
 
         match solve_time.penalty {
             data::Penalty::Ok => {
@@ -85,11 +93,38 @@ impl TimeLabel {
             }
         }
 
-        let recorded_time = solve_time.recorded_time().unwrap_or_default();
-        let s = recorded_time.as_secs();
+        let decimals = imp.precision.get().decimals();
+        self.show_time(solve_time.recorded_time().unwrap_or_default(), decimals);
+    }
+
+    /// Shows the time of a running timer in this display mode.
+    pub fn set_running_duration(&self, duration: Duration, display: data::RunningDisplay) {
+        let imp = self.imp();
+        imp.plus.set_visible(false);
+
+        match display.decimals(imp.precision.get()) {
+            Some(decimals) => {
+                imp.stack.set_visible_child_name("time-label");
+                self.show_time(duration, decimals);
+            }
+            None => imp.stack.set_visible_child_name("solving"),
+        }
+    }
+
+    /// Shows the seconds of inspection left after this much inspection.
+    pub fn set_inspection(&self, elapsed: Duration) {
+        let imp = self.imp();
+        imp.inspection.set_label(&data::format_inspection(elapsed));
+        imp.stack.set_visible_child_name("inspection");
+    }
+
+    /// Shows a time truncated to this number of decimals.
+    fn show_time(&self, time: Duration, decimals: usize) {
+        let imp = self.imp();
+        let s = time.as_secs();
         let m = s / 60;
         let s = s % 60;
-        let c = recorded_time.subsec_millis() / 10;
+        let fraction = format!("{:03}", time.subsec_millis());
 
         if m > 0 {
             imp.minutes.set_visible(true);
@@ -101,7 +136,20 @@ impl TimeLabel {
             imp.colon.set_visible(false);
             imp.seconds.set_label(&format!("{:0>1}", s));
         }
-        imp.centis.set_label(&format!("{:0>2}", c));
+        imp.point.set_visible(decimals > 0);
+        imp.fraction.set_visible(decimals > 0);
+        imp.fraction.set_label(&fraction[..decimals]);
+    }
+
+    /// Shows the time again, e.g. after it was replaced by a running time.
+    pub fn refresh(&self) {
+        self.set_solve_time(self.imp().solve_time.get());
+    }
+
+    /// Sets the precision times are displayed with and shows the time again.
+    pub fn set_precision(&self, precision: data::TimePrecision) {
+        self.imp().precision.set(precision);
+        self.refresh();
     }
 }
 
@@ -1,14 +1,20 @@
 use std::time::Duration;
 
-use crate::data::{self, TimerState};
-use crate::ui;
+use crate::data::{self, TimerState, TriggerEvent};
+use crate::{config, ui};
 use adw::subclass::prelude::*;
+use gettextrs::gettext;
 use gtk::prelude::*;
-use gtk::{gdk, glib};
+use gtk::{gdk, gio, glib};
+
+/// Time an inspection cue is shown for, in milliseconds.
+const CUE_FLASH_DURATION: u64 = 600;
 
 #[doc(hidden)]
 mod imp {
-    use std::cell::RefCell;
+    use std::cell::{Cell, RefCell};
+
+    use once_cell::sync::OnceCell;
 
     use crate::util::TemplateCallbacks;
 
@@ -24,11 +30,18 @@ mod imp {
         #[template_child]
         pub time_label: TemplateChild<ui::TimeLabel>,
 
+        #[template_child]
+        pub split_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub scramble_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub relay_label: TemplateChild<gtk::Label>,
+
         #[template_child]
         pub penalty_selector: TemplateChild<ui::PenaltySelector>,
 
         #[template_child]
-        pub statistics_box: TemplateChild<gtk::Box>,
+        pub statistics_stack: TemplateChild<gtk::Stack>,
         #[template_child]
         pub last_ao5_label: TemplateChild<gtk::Label>,
         #[template_child]
@@ -38,12 +51,28 @@ mod imp {
         pub timer_state_machine: RefCell<Option<data::TimerStateMachine>>,
         timer_state_machine_handlers: RefCell<Vec<glib::SignalHandlerId>>,
 
-        #[property(get, set)]
+        #[property(get, set = Self::set_session)]
         pub session: RefCell<Option<data::Session>>,
 
         #[property(get, set = Self::set_last_solve, nullable)]
         pub last_solve: RefCell<Option<data::SessionItem>>,
         last_solve_handlers: RefCell<Vec<glib::SignalHandlerId>>,
+
+        #[property(get, set, nullable)]
+        pub competition: RefCell<Option<data::MockCompetition>>,
+
+        /// Scramble of the next solve, in sessions scrambled as a 3x3x3.
+        pub(super) scramble: RefCell<String>,
+        /// Scrambles of the next relay attempt.
+        pub(super) relay_scrambles: RefCell<Vec<data::RelayScramble>>,
+
+        pub(super) trigger_keys: RefCell<data::TriggerKeys>,
+        /// How the time is shown while the timer is running.
+        pub(super) running_display: Cell<data::RunningDisplay>,
+        /// Sound of the last inspection cue, kept alive while it plays.
+        pub(super) cue_media: RefCell<Option<gtk::MediaFile>>,
+
+        pub(super) settings: OnceCell<gio::Settings>,
     }
 
     impl TimerFace {
@@ -72,8 +101,29 @@ mod imp {
                         obj.tick_cb(sm);
                     }),
                 ));
+                handlers.push(sm.connect_closure(
+                    "inspection-cue",
+                    false,
+                    glib::closure_local!(@strong obj => move |_: &data::TimerStateMachine, cue: &str| {
+                        if let Some(cue) = data::InspectionCue::from_nick(cue) {
+                            obj.inspection_cue_cb(cue);
+                        }
+                    }),
+                ));
             }
             self.timer_state_machine.replace(v);
+            obj.update_session_kind();
+        }
+
+        fn set_session(&self, v: Option<data::Session>) {
+            let obj = self.obj();
+            self.session.replace(v);
+
+            obj.set_last_solve(None::<data::SessionItem>);
+            self.penalty_selector.set_solve(None::<data::SessionItem>);
+            self.time_label.set_duration(Duration::ZERO);
+            self.split_label.set_visible(false);
+            obj.update_session_kind();
         }
 
         fn set_last_solve(&self, v: Option<data::SessionItem>) {
@@ -141,8 +191,11 @@ mod imp {
             obj.add_css_class("timer-face");
 
             self.time_label.set_duration(Duration::ZERO);
+            obj.setup_settings();
             obj.setup_event_controllers();
             obj.setup_callbacks();
+
+            obj.connect_competition_notify(|obj| obj.update_scramble_label());
         }
     }
 
@@ -158,23 +211,88 @@ glib::wrapper! {
 
 #[gtk::template_callbacks]
 impl TimerFace {
+    fn setup_settings(&self) {
+        let imp = self.imp();
+
+        let settings = gio::Settings::new(config::APP_ID);
+        imp.settings
+            .set(settings.clone())
+            .expect("`settings` should not be set before `setup_settings` is called");
+
+        settings.connect_changed(
+            Some("trigger-keys"),
+            glib::clone!(@weak self as obj => move |_, _| {
+                obj.load_trigger_keys();
+            }),
+        );
+
+        settings.connect_changed(
+            Some("trigger-require-all"),
+            glib::clone!(@weak self as obj => move |_, _| {
+                obj.load_trigger_keys();
+            }),
+        );
+
+        settings.connect_changed(
+            Some("relay-splits"),
+            glib::clone!(@weak self as obj => move |_, _| {
+                obj.update_session_kind();
+            }),
+        );
+
+        settings.connect_changed(
+            Some("inspection"),
+            glib::clone!(@weak self as obj => move |_, _| {
+                obj.update_inspection();
+            }),
+        );
+
+        settings.connect_changed(
+            Some("running-display"),
+            glib::clone!(@weak self as obj => move |settings, _| {
+                obj.imp().running_display.set(ui::running_display(settings));
+            }),
+        );
+
+        settings.connect_changed(
+            Some("time-precision"),
+            glib::clone!(@weak self as obj => move |_, _| {
+                obj.update_time_precision();
+            }),
+        );
+
+        self.load_trigger_keys();
+        imp.running_display.set(ui::running_display(&settings));
+        self.update_time_precision();
+    }
+
+    fn settings(&self) -> &gio::Settings {
+        self.imp()
+            .settings
+            .get()
+            .expect("`settings` should be set by `setup_settings` first")
+    }
+
+    fn load_trigger_keys(&self) {
+        let settings = self.settings();
+
+        let names = settings.strv("trigger-keys");
+        let require_all = settings.boolean("trigger-require-all");
+        let names: Vec<&str> = names.iter().map(|s| s.as_str()).collect();
+
+        self.imp()
+            .trigger_keys
+            .replace(data::TriggerKeys::from_names(&names, require_all));
+    }
+
     fn setup_event_controllers(&self) {
         let key_events = gtk::EventControllerKey::new();
         key_events.connect_key_pressed(glib::clone!(@weak self as obj => @default-return glib::Propagation::Proceed, move |_, key, _, modifier| {
-            if modifier.is_empty() && key == gdk::Key::space {
-                obj.pressed_cb();
-                glib::Propagation::Stop
-            } else {
-                glib::Propagation::Proceed
-            }
+            obj.key_pressed_cb(key, modifier)
+        }));
+        key_events.connect_key_released(glib::clone!(@weak self as obj => move |_, key, _, _| {
+            obj.key_released_cb(key);
         }));
-        key_events.connect_key_released(
-            glib::clone!(@weak self as obj => move |_, key, _, modifier| {
-                if modifier.is_empty() && key == gdk::Key::space {
-                    obj.released_cb();
-                }
-            }),
-        );
         self.add_controller(key_events);
 
         let gestures = gtk::GestureClick::new();
@@ -190,6 +308,53 @@ impl TimerFace {
         self.add_controller(gestures);
     }
 
+    fn key_pressed_cb(&self, key: gdk::Key, modifier: gdk::ModifierType) -> glib::Propagation {
+        let imp = self.imp();
+        if key == gdk::Key::Escape {
+            if let Some(sm) = self
+                .timer_state_machine()
+                .filter(|sm| matches!(sm.state(), TimerState::Inspecting))
+            {
+                sm.cancel_inspection();
+                return glib::Propagation::Stop;
+            }
+        }
+
+        let timing = matches!(
+            self.timer_state_machine().map(|sm| sm.state()),
+            Some(TimerState::Timing { .. })
+        );
+
+        let event = {
+            let mut trigger_keys = imp.trigger_keys.borrow_mut();
+            if !trigger_keys.contains(key)
+                || !modifier.difference(trigger_keys.modifier_mask()).is_empty()
+            {
+                return glib::Propagation::Proceed;
+            }
+            trigger_keys.key_pressed(key, timing)
+        };
+
+        if let Some(event) = event {
+            self.trigger_event_cb(event);
+        }
+        glib::Propagation::Stop
+    }
+
+    fn key_released_cb(&self, key: gdk::Key) {
+        let event = self.imp().trigger_keys.borrow_mut().key_released(key);
+        if let Some(event) = event {
+            self.trigger_event_cb(event);
+        }
+    }
+
+    fn trigger_event_cb(&self, event: TriggerEvent) {
+        match event {
+            TriggerEvent::Press => self.pressed_cb(),
+            TriggerEvent::Release => self.released_cb(),
+        }
+    }
+
     fn pressed_cb(&self) {
         if let Some(sm) = self.timer_state_machine() {
             sm.press();
@@ -205,64 +370,317 @@ impl TimerFace {
     #[template_callback]
     fn notify_has_focus_cb(&self, _pspec: &glib::ParamSpec, _s: &Self) {
         if !self.has_focus() {
+            self.imp().trigger_keys.borrow_mut().reset();
             self.released_cb();
         }
     }
 
     fn setup_callbacks(&self) {}
 
+    /// Sets up the timer and statistics for the kind of the current session.
+    fn update_session_kind(&self) {
+        let imp = self.imp();
+        let kind = self.session().map(|s| s.kind()).unwrap_or_default();
+
+        self.update_inspection();
+        if let Some(sm) = self.timer_state_machine() {
+            let relay_splits = imp.settings.get().is_none_or(|s| s.boolean("relay-splits"));
+            if kind.is_relay() && !relay_splits {
+                sm.set_n_splits(0);
+            } else {
+                sm.set_n_splits(kind.n_splits());
+            }
+        }
+
+        if !kind.is_relay() {
+            imp.relay_scrambles.replace(Vec::new());
+            imp.relay_label.set_visible(false);
+        }
+        self.new_scramble();
+
+        match kind {
+            data::SessionKind::Standard
+            | data::SessionKind::FewestMoves
+            | data::SessionKind::Relay234
+            | data::SessionKind::Relay2To7 => {
+                imp.statistics_stack.set_visible_child_name("standard");
+            }
+            data::SessionKind::Blindfolded => {
+                imp.statistics_stack.set_visible_child_name("blindfolded");
+            }
+            data::SessionKind::MultiBlind => {
+                imp.statistics_stack.set_visible_child_name("multi-blind");
+            }
+        }
+    }
+
+    /// Turns inspection on if it is enabled and the current session has
+    /// inspection.
+    fn update_inspection(&self) {
+        let imp = self.imp();
+        let kind = self.session().map(|s| s.kind()).unwrap_or_default();
+        let enabled = imp.settings.get().is_some_and(|s| s.boolean("inspection"));
+
+        if let Some(sm) = self.timer_state_machine() {
+            sm.set_inspection_enabled(enabled && kind.has_inspection());
+        }
+    }
+
+    fn update_split_label(&self, splits: &[Duration], running: bool) {
+        let imp = self.imp();
+        let kind = self.session().map(|s| s.kind()).unwrap_or_default();
+        let precision = ui::time_precision(self.settings());
+
+        match (kind, splits.first()) {
+            (data::SessionKind::Blindfolded, Some(&memo)) => {
+                let memo = data::SolveTime::new(memo, data::Penalty::Ok);
+                imp.split_label
+                    .set_label(&gettext!("Memo: {}", memo.format(precision)));
+                imp.split_label.set_visible(true);
+            }
+            (data::SessionKind::Blindfolded, None) if running => {
+                imp.split_label.set_label(&gettext("Memorizing"));
+                imp.split_label.set_visible(true);
+            }
+            (kind, _) if kind.is_relay() && running => {
+                let sizes = kind.relay_sizes();
+                let n_splits = self.timer_state_machine().map_or(0, |sm| sm.n_splits());
+                match sizes.get(splits.len()) {
+                    Some(&size) if n_splits > 0 => {
+                        imp.split_label
+                            .set_label(&gettext!("Solving {}", data::cube_name(size)));
+                        imp.split_label.set_visible(true);
+                    }
+                    _ => imp.split_label.set_visible(false),
+                }
+            }
+            (kind, _) if kind.is_relay() => {
+                let total = self
+                    .timer_state_machine()
+                    .map(|sm| sm.last_solve().measured_time());
+                match total.and_then(|t| data::puzzle_times(kind.relay_sizes().len(), splits, t)) {
+                    Some(times) if !splits.is_empty() => {
+                        imp.split_label.set_label(&data::format_puzzle_times(
+                            kind.relay_sizes(),
+                            &times,
+                            precision,
+                        ));
+                        imp.split_label.set_visible(true);
+                    }
+                    _ => imp.split_label.set_visible(false),
+                }
+            }
+            _ => {
+                imp.split_label.set_visible(false);
+            }
+        }
+    }
+
     pub(self) fn timer_state_changed_cb(&self, state: TimerState) {
         let imp = self.imp();
 
         match state {
             TimerState::Idle => {
                 self.set_color_normal();
-                imp.statistics_box.set_visible(true);
+                imp.time_label.refresh();
+                imp.statistics_stack.set_visible(true);
                 imp.penalty_selector.set_visible(true);
+                self.update_scramble_label();
             }
             TimerState::Wait => {
+                let inspecting = self.inspection_elapsed().is_some();
                 self.set_color_wait();
-                imp.statistics_box.set_visible(true);
-                imp.penalty_selector.set_visible(true);
+                imp.statistics_stack.set_visible(!inspecting);
+                imp.penalty_selector.set_visible(!inspecting);
+            }
+            TimerState::Inspecting => {
+                self.set_color_normal();
+                imp.time_label.set_inspection(Duration::ZERO);
+                imp.statistics_stack.set_visible(false);
+                imp.penalty_selector.set_visible(false);
+                imp.split_label.set_visible(false);
             }
             TimerState::Ready => {
                 self.set_color_ready();
-                imp.time_label.set_duration(Duration::ZERO);
-                imp.statistics_box.set_visible(false);
+                if self.inspection_elapsed().is_none() {
+                    imp.time_label.set_duration(Duration::ZERO);
+                }
+                imp.statistics_stack.set_visible(false);
                 imp.penalty_selector.set_visible(false);
+                imp.split_label.set_visible(false);
+                imp.scramble_label.set_visible(false);
+                imp.relay_label.set_visible(false);
             }
-            TimerState::Timing { duration } => {
+            TimerState::Timing { duration, .. } => {
                 self.set_color_normal();
-                imp.time_label.set_duration(duration);
-                imp.statistics_box.set_visible(false);
+                imp.time_label
+                    .set_running_duration(duration, imp.running_display.get());
+                imp.statistics_stack.set_visible(false);
                 imp.penalty_selector.set_visible(false);
+                let splits = self
+                    .timer_state_machine()
+                    .map(|sm| sm.current_splits())
+                    .unwrap_or_default();
+                self.update_split_label(&splits, true);
             }
             TimerState::Finished { solve_time, .. } => {
                 self.set_color_wait();
                 imp.time_label.set_solve_time(solve_time);
-                imp.statistics_box.set_visible(true);
+                imp.statistics_stack.set_visible(true);
                 imp.penalty_selector.set_visible(true);
-                self.submit_solve(data::SolveData::new(solve_time, "".to_string()));
+                let splits = self
+                    .timer_state_machine()
+                    .map(|sm| sm.last_splits())
+                    .unwrap_or_default();
+                self.update_split_label(&splits, false);
+                let kind = self.session().map(|s| s.kind()).unwrap_or_default();
+                if kind == data::SessionKind::MultiBlind {
+                    self.present_multi_blind_dialog(solve_time.measured_time());
+                } else if kind.is_relay() {
+                    let scrambles = imp.relay_scrambles.take();
+                    self.submit_solve(
+                        data::SolveData::from_relay(solve_time, scrambles).with_splits(splits),
+                    );
+                    self.new_relay_scrambles();
+                } else if let Some(competition) = self.competition().filter(|c| !c.finished()) {
+                    let scramble = competition.scramble();
+                    let solve = data::SolveData::new(solve_time, scramble).with_splits(splits);
+                    if let Some(item) = self.submit_solve(solve) {
+                        competition.record(&item);
+                    }
+                } else {
+                    let scramble = imp.scramble.take();
+                    self.submit_solve(
+                        data::SolveData::new(solve_time, scramble).with_splits(splits),
+                    );
+                    self.new_scramble();
+                }
             }
         }
     }
 
-    pub(self) fn tick_cb(&self, sm: &data::TimerStateMachine) {
+    /// Shows times with the precision chosen in the settings.
+    fn update_time_precision(&self) {
+        let precision = ui::time_precision(self.settings());
+        self.imp().time_label.set_precision(precision);
+    }
+
+    /// Generates the scramble of the next solve and shows it. Relays get a
+    /// scramble for each puzzle, and sessions whose solves are not scrambled
+    /// as a 3x3x3 get none.
+    pub fn new_scramble(&self) {
         let imp = self.imp();
-        if let data::TimerState::Timing { duration } = sm.state() {
-            imp.time_label.set_duration(duration);
+        let kind = self.session().map(|s| s.kind()).unwrap_or_default();
+
+        if kind.is_relay() {
+            self.new_relay_scrambles();
+            return;
         }
+
+        let scramble = match kind {
+            data::SessionKind::Standard | data::SessionKind::Blindfolded => {
+                data::format_moves(&data::generate_scramble())
+            }
+            _ => String::new(),
+        };
+        imp.scramble.replace(scramble);
+        self.update_scramble_label();
+    }
+
+    /// The scramble is hidden during mock competitions, which show the
+    /// scramble of the attempt instead.
+    fn update_scramble_label(&self) {
+        let imp = self.imp();
+        let scramble = imp.scramble.borrow();
+        imp.scramble_label.set_label(&scramble);
+        imp.scramble_label
+            .set_visible(!scramble.is_empty() && self.competition().is_none());
+    }
+
+    /// Generates the scrambles of the next relay attempt and shows them.
+    fn new_relay_scrambles(&self) {
+        let imp = self.imp();
+        let kind = self.session().map(|s| s.kind()).unwrap_or_default();
+        let scrambles = data::generate_relay_scrambles(kind.relay_sizes());
+
+        imp.relay_label
+            .set_label(&data::format_relay_scrambles(&scrambles));
+        imp.relay_label.set_visible(true);
+        imp.relay_scrambles.replace(scrambles);
     }
 
-    fn submit_solve(&self, solve: data::SolveData) {
+    pub(self) fn tick_cb(&self, sm: &data::TimerStateMachine) {
         let imp = self.imp();
-        if let Some(session) = self.session() {
-            let session_item = session.add_solve(solve);
-            imp.penalty_selector.set_solve(Some(session_item.clone()));
-            self.set_last_solve(Some(session_item));
+        if let data::TimerState::Timing { duration, .. } = sm.state() {
+            imp.time_label
+                .set_running_duration(duration, imp.running_display.get());
+        } else if let Some(elapsed) = sm.inspection_elapsed() {
+            imp.time_label.set_inspection(elapsed);
         }
     }
 
+    fn inspection_elapsed(&self) -> Option<Duration> {
+        self.timer_state_machine()?.inspection_elapsed()
+    }
+
+    /// Flashes the time and plays the sound of an inspection cue.
+    fn inspection_cue_cb(&self, cue: data::InspectionCue) {
+        let imp = self.imp();
+
+        self.add_css_class("inspection-cue");
+        glib::timeout_add_local_once(
+            Duration::from_millis(CUE_FLASH_DURATION),
+            glib::clone!(@weak self as obj => move || {
+                obj.remove_css_class("inspection-cue");
+            }),
+        );
+
+        let settings = self.settings();
+        let sound =
+            data::CueSound::from_nick(&settings.string("inspection-cue-sound")).unwrap_or_default();
+        let Some(file_name) = sound.file_name(cue) else {
+            return;
+        };
+        let media = gtk::MediaFile::for_resource(&format!(
+            "/io/github/manenfu/PrismaTimer/sounds/{}",
+            file_name
+        ));
+        media.set_volume(settings.double("inspection-cue-volume"));
+        media.play();
+        imp.cue_media.replace(Some(media));
+    }
+
+    fn submit_solve(&self, solve: data::SolveData) -> Option<data::SessionItem> {
+        let imp = self.imp();
+        let session_item = self.session()?.add_solve(solve);
+        imp.penalty_selector.set_solve(Some(session_item.clone()));
+        self.set_last_solve(Some(session_item.clone()));
+        Some(session_item)
+    }
+
+    /// Asks for the result of a Multi-Blind attempt timed with the timer.
+    fn present_multi_blind_dialog(&self, time: Duration) {
+        let Some(session) = self.session() else {
+            return;
+        };
+
+        let dialog = ui::MultiBlindDialog::new(&session);
+        dialog.set_time(time);
+        dialog.set_transient_for(self.root().and_downcast_ref::<gtk::Window>());
+        dialog.set_modal(true);
+        dialog.connect_closure(
+            "solve-added",
+            false,
+            glib::closure_local!(@watch self as obj => move |_: &ui::MultiBlindDialog, item: data::SessionItem| {
+                let imp = obj.imp();
+                imp.time_label.set_solve_time(item.time());
+                imp.penalty_selector.set_solve(Some(item.clone()));
+                obj.set_last_solve(Some(item));
+            }),
+        );
+        dialog.present();
+    }
+
     fn last_solve_time_changed_cb(&self, solve: &data::SessionItem) {
         let imp = self.imp();
         imp.time_label.set_solve_time(solve.time());
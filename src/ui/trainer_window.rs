@@ -0,0 +1,588 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use crate::data::{self, TimerState};
+use crate::{config, ui, util};
+use adw::prelude::*;
+use adw::subclass::prelude::*;
+use gtk::{gdk, gio, glib};
+
+/// Name of the data file the latest times of each case are saved in.
+const CASE_TIMES_FILE: &str = "trainer-times";
+
+#[doc(hidden)]
+mod imp {
+    use std::cell::RefCell;
+
+    use once_cell::sync::OnceCell;
+
+    use super::*;
+
+    #[derive(Debug, Default, gtk::CompositeTemplate)]
+    #[template(resource = "/io/github/manenfu/PrismaTimer/ui/trainer_window.ui")]
+    pub struct TrainerWindow {
+        #[template_child]
+        pub timer_box: TemplateChild<gtk::Box>,
+        #[template_child]
+        pub scramble_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub time_label: TemplateChild<ui::TimeLabel>,
+        #[template_child]
+        pub result_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub case_set_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub cases_group: TemplateChild<adw::PreferencesGroup>,
+
+        pub(super) settings: OnceCell<gio::Settings>,
+        pub(super) timer_state_machine: OnceCell<data::TimerStateMachine>,
+        timer_state_machine_handlers: RefCell<Vec<glib::SignalHandlerId>>,
+        pub(super) trigger_keys: RefCell<data::TriggerKeys>,
+
+        /// Cases of the chosen set.
+        pub(super) cases: RefCell<Vec<data::LastLayerCase>>,
+        /// Identifiers of the cases left out of training, in every set.
+        pub(super) excluded: RefCell<HashSet<String>>,
+        pub(super) case_times: RefCell<data::CaseTimes>,
+        /// The case the current scramble leads to.
+        pub(super) case: RefCell<Option<data::LastLayerCase>>,
+
+        /// Rows of the groups of cases.
+        pub(super) group_rows: RefCell<Vec<adw::ExpanderRow>>,
+        /// Rows of the cases, by case identifier.
+        pub(super) case_rows: RefCell<HashMap<String, adw::ActionRow>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TrainerWindow {
+        const NAME: &'static str = "PtTrainerWindow";
+        type Type = super::TrainerWindow;
+        type ParentType = adw::Window;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+            klass.bind_template_instance_callbacks();
+
+            klass.add_binding_action(
+                gdk::Key::Escape,
+                gdk::ModifierType::empty(),
+                "window.close",
+                None,
+            );
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for TrainerWindow {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            obj.setup_settings();
+            obj.setup_timer_state_machine();
+            obj.setup_event_controllers();
+            obj.setup_case_set_row();
+            obj.load_cases();
+        }
+
+        fn dispose(&self) {
+            let Some(sm) = self.timer_state_machine.get() else {
+                return;
+            };
+            for id in self.timer_state_machine_handlers.take() {
+                sm.disconnect(id);
+            }
+            // A running timer keeps ticking until it is stopped.
+            if matches!(sm.state(), TimerState::Timing { .. }) {
+                sm.press();
+                sm.release();
+            }
+        }
+    }
+
+    impl TrainerWindow {
+        pub(super) fn connect_timer_state_machine(&self, sm: &data::TimerStateMachine) {
+            let obj = self.obj();
+            let mut handlers = self.timer_state_machine_handlers.borrow_mut();
+            handlers.push(sm.connect_closure(
+                "state-changed",
+                false,
+                glib::closure_local!(@watch obj => move |sm: &data::TimerStateMachine| {
+                    obj.timer_state_changed_cb(sm.state());
+                }),
+            ));
+            handlers.push(sm.connect_closure(
+                "tick",
+                false,
+                glib::closure_local!(@watch obj => move |sm: &data::TimerStateMachine| {
+                    obj.tick_cb(sm.state());
+                }),
+            ));
+        }
+    }
+
+    impl WidgetImpl for TrainerWindow {}
+    impl WindowImpl for TrainerWindow {}
+    impl AdwWindowImpl for TrainerWindow {}
+}
+
+glib::wrapper! {
+    /// A window drilling last layer cases, which times each case from a
+    /// scramble leading to it and brings slow cases up more often.
+    pub struct TrainerWindow(ObjectSubclass<imp::TrainerWindow>)
+        @extends gtk::Widget, gtk::Window, adw::Window;
+}
+
+#[gtk::template_callbacks]
+impl TrainerWindow {
+    pub fn new() -> Self {
+        glib::Object::builder().build()
+    }
+
+    fn settings(&self) -> &gio::Settings {
+        self.imp()
+            .settings
+            .get()
+            .expect("`settings` should be set by `setup_settings` first")
+    }
+
+    fn timer_state_machine(&self) -> &data::TimerStateMachine {
+        self.imp()
+            .timer_state_machine
+            .get()
+            .expect("`timer_state_machine` should be set by `setup_timer_state_machine` first")
+    }
+
+    fn setup_settings(&self) {
+        let imp = self.imp();
+
+        let settings = gio::Settings::new(config::APP_ID);
+        imp.settings
+            .set(settings.clone())
+            .expect("`settings` should not be set before `setup_settings` is called");
+
+        settings.connect_changed(
+            Some("trigger-keys"),
+            glib::clone!(@weak self as obj => move |_, _| {
+                obj.load_trigger_keys();
+            }),
+        );
+        self.load_trigger_keys();
+        imp.time_label.set_precision(ui::time_precision(&settings));
+
+        imp.excluded.replace(
+            settings
+                .strv("trainer-excluded-cases")
+                .iter()
+                .map(|id| id.to_string())
+                .collect(),
+        );
+
+        match util::read_data_file(CASE_TIMES_FILE) {
+            Ok(text) => {
+                let times = data::CaseTimes::from_text(&text.unwrap_or_default());
+                imp.case_times.replace(times);
+            }
+            Err(e) => log::error!("Failed to load trainer times. cause: {}", e),
+        }
+    }
+
+    fn load_trigger_keys(&self) {
+        let settings = self.settings();
+
+        let names = settings.strv("trigger-keys");
+        let require_all = settings.boolean("trigger-require-all");
+        let names: Vec<&str> = names.iter().map(|s| s.as_str()).collect();
+
+        self.imp()
+            .trigger_keys
+            .replace(data::TriggerKeys::from_names(&names, require_all));
+    }
+
+    fn setup_timer_state_machine(&self) {
+        let imp = self.imp();
+        let sm = data::TimerStateMachine::new();
+        imp.connect_timer_state_machine(&sm);
+        imp.timer_state_machine
+            .set(sm)
+            .expect("`timer_state_machine` should only be set once");
+    }
+
+    fn setup_event_controllers(&self) {
+        // Keys are caught before the list of cases, so the trigger keys
+        // always start the timer.
+        let key_events = gtk::EventControllerKey::new();
+        key_events.set_propagation_phase(gtk::PropagationPhase::Capture);
+        key_events.connect_key_pressed(glib::clone!(@weak self as obj => @default-return glib::Propagation::Proceed, move |_, key, _, modifier| {
+            obj.key_pressed_cb(key, modifier)
+        }));
+        key_events.connect_key_released(glib::clone!(@weak self as obj => move |_, key, _, _| {
+            obj.key_released_cb(key);
+        }));
+        self.add_controller(key_events);
+
+        let gestures = gtk::GestureClick::new();
+        gestures.set_button(gdk::BUTTON_PRIMARY);
+        gestures.connect_pressed(glib::clone!(@weak self as obj => move |_, _, _, _| {
+            obj.pressed_cb();
+        }));
+        gestures.connect_released(glib::clone!(@weak self as obj => move |_, _, _, _| {
+            obj.released_cb();
+        }));
+        self.imp().timer_box.add_controller(gestures);
+    }
+
+    fn setup_case_set_row(&self) {
+        let imp = self.imp();
+
+        let labels: Vec<&str> = data::CaseSet::ALL.iter().map(|set| set.label()).collect();
+        imp.case_set_row
+            .set_model(Some(&gtk::StringList::new(&labels)));
+        let position = data::CaseSet::ALL
+            .iter()
+            .position(|&set| set == self.case_set())
+            .unwrap_or(0);
+        imp.case_set_row.set_selected(position as u32);
+        imp.case_set_row
+            .connect_selected_notify(glib::clone!(@weak self as obj => move |row| {
+                let Some(set) = data::CaseSet::ALL.get(row.selected() as usize) else {
+                    return;
+                };
+                if let Err(e) = obj.settings().set_string("trainer-case-set", set.nick()) {
+                    log::error!("Failed to save trainer case set. cause: {}", e);
+                }
+                obj.load_cases();
+            }));
+    }
+
+    /// Gets the set of cases chosen in the settings.
+    fn case_set(&self) -> data::CaseSet {
+        data::CaseSet::from_nick(&self.settings().string("trainer-case-set")).unwrap_or_default()
+    }
+
+    /// Lists the cases of the chosen set and scrambles one of them.
+    fn load_cases(&self) {
+        let imp = self.imp();
+        let cases = data::last_layer_cases(self.case_set());
+
+        for row in imp.group_rows.take() {
+            imp.cases_group.remove(&row);
+        }
+        let mut case_rows = HashMap::new();
+        let mut group_rows: Vec<adw::ExpanderRow> = Vec::new();
+        for case in &cases {
+            if group_rows.last().map(|row| row.title()).as_deref() != Some(case.group) {
+                let group_row = adw::ExpanderRow::builder().title(case.group).build();
+                imp.cases_group.add(&group_row);
+                group_rows.push(group_row);
+            }
+            let row = self.case_row(case);
+            if let Some(group_row) = group_rows.last() {
+                group_row.add_row(&row);
+            }
+            case_rows.insert(case.id(), row);
+        }
+        if let [group_row] = group_rows.as_slice() {
+            group_row.set_expanded(true);
+        }
+
+        imp.group_rows.replace(group_rows);
+        imp.case_rows.replace(case_rows);
+        imp.cases.replace(cases);
+        imp.case.replace(None);
+        self.update_group_rows();
+        self.next_case();
+    }
+
+    /// Creates the row of a case, with a check button to train it or not.
+    fn case_row(&self, case: &data::LastLayerCase) -> adw::ActionRow {
+        let id = case.id();
+        let check_button = gtk::CheckButton::builder()
+            .active(!self.imp().excluded.borrow().contains(&id))
+            .valign(gtk::Align::Center)
+            .build();
+        check_button.connect_toggled(
+            glib::clone!(@weak self as obj, @strong id => move |button| {
+                obj.set_case_trained(&id, button.is_active());
+            }),
+        );
+
+        let row = adw::ActionRow::builder()
+            .title(&case.name)
+            .subtitle(self.describe_case_times(&id))
+            .activatable_widget(&check_button)
+            .build();
+        row.add_prefix(&check_button);
+        row
+    }
+
+    /// Describes the average of a case, e.g. `Average 1.52 of 3`.
+    fn describe_case_times(&self, id: &str) -> String {
+        let case_times = self.imp().case_times.borrow();
+        match case_times.average(id) {
+            Some(average) => format!(
+                "Average {} of {}",
+                data::SolveTime::new(average, data::Penalty::Ok)
+                    .format_average(ui::time_precision(self.settings())),
+                case_times.times()[id].len()
+            ),
+            None => "Not timed yet".to_string(),
+        }
+    }
+
+    /// Shows how many cases of each group are trained.
+    fn update_group_rows(&self) {
+        let imp = self.imp();
+        let cases = imp.cases.borrow();
+        let excluded = imp.excluded.borrow();
+        for row in imp.group_rows.borrow().iter() {
+            let group: Vec<&data::LastLayerCase> = cases
+                .iter()
+                .filter(|case| Some(case.group) == Some(row.title().as_str()))
+                .collect();
+            let trained = group
+                .iter()
+                .filter(|case| !excluded.contains(&case.id()))
+                .count();
+            row.set_subtitle(&format!("{} of {} trained", trained, group.len()));
+        }
+    }
+
+    fn set_case_trained(&self, id: &str, trained: bool) {
+        let imp = self.imp();
+        let changed = if trained {
+            imp.excluded.borrow_mut().remove(id)
+        } else {
+            imp.excluded.borrow_mut().insert(id.to_string())
+        };
+        if !changed {
+            return;
+        }
+
+        self.save_excluded();
+        self.update_group_rows();
+        let current = imp.case.borrow().as_ref().map(|case| case.id());
+        if current.is_none() || current.as_deref() == Some(id) {
+            self.next_case();
+        }
+    }
+
+    fn save_excluded(&self) {
+        let mut excluded: Vec<String> = self.imp().excluded.borrow().iter().cloned().collect();
+        excluded.sort();
+        if let Err(e) = self
+            .settings()
+            .set_value("trainer-excluded-cases", &excluded.to_variant())
+        {
+            log::error!("Failed to save trainer cases. cause: {}", e);
+        }
+    }
+
+    fn save_case_times(&self) {
+        let text = self.imp().case_times.borrow().to_text();
+        if let Err(e) = util::write_data_file(CASE_TIMES_FILE, &text) {
+            log::error!("Failed to save trainer times. cause: {}", e);
+        }
+    }
+
+    /// Chooses the next case among the trained ones, weighted towards slow
+    /// cases, and shows a scramble leading to it.
+    fn next_case(&self) {
+        let imp = self.imp();
+        let trained: Vec<data::LastLayerCase> = {
+            let excluded = imp.excluded.borrow();
+            imp.cases
+                .borrow()
+                .iter()
+                .filter(|case| !excluded.contains(&case.id()))
+                .cloned()
+                .collect()
+        };
+        let ids: Vec<String> = trained.iter().map(data::LastLayerCase::id).collect();
+        let previous = imp.case.borrow().as_ref().map(|case| case.id());
+
+        let choice = imp
+            .case_times
+            .borrow()
+            .choose(&ids, previous.as_deref(), |n| {
+                glib::random_int_range(0, n as i32) as u32
+            });
+        let case = choice.and_then(|i| trained.get(i).cloned());
+        match &case {
+            Some(case) => {
+                let scramble = data::generate_case_scramble(case);
+                imp.scramble_label.set_label(&data::format_moves(&scramble));
+            }
+            None => imp.scramble_label.set_label("Choose cases to train"),
+        }
+        imp.case.replace(case);
+    }
+
+    #[template_callback]
+    fn select_all_cb(&self) {
+        let imp = self.imp();
+        {
+            let mut excluded = imp.excluded.borrow_mut();
+            for case in imp.cases.borrow().iter() {
+                excluded.remove(&case.id());
+            }
+        }
+        self.update_check_buttons();
+    }
+
+    #[template_callback]
+    fn select_none_cb(&self) {
+        let imp = self.imp();
+        {
+            let mut excluded = imp.excluded.borrow_mut();
+            for case in imp.cases.borrow().iter() {
+                excluded.insert(case.id());
+            }
+        }
+        self.update_check_buttons();
+    }
+
+    /// Shows which cases are trained after many changed at once.
+    fn update_check_buttons(&self) {
+        let imp = self.imp();
+        let excluded = imp.excluded.borrow().clone();
+        for (id, row) in imp.case_rows.borrow().iter() {
+            if let Some(button) = row.activatable_widget().and_downcast::<gtk::CheckButton>() {
+                // The cases left out are already updated, so toggling
+                // changes nothing more.
+                button.set_active(!excluded.contains(id));
+            }
+        }
+        self.save_excluded();
+        self.update_group_rows();
+        self.next_case();
+    }
+
+    #[template_callback]
+    fn clear_times_cb(&self) {
+        let imp = self.imp();
+        imp.case_times.borrow_mut().clear();
+        self.save_case_times();
+        for (id, row) in imp.case_rows.borrow().iter() {
+            row.set_subtitle(&self.describe_case_times(id));
+        }
+    }
+
+    fn key_pressed_cb(&self, key: gdk::Key, modifier: gdk::ModifierType) -> glib::Propagation {
+        let imp = self.imp();
+        let timing = matches!(
+            self.timer_state_machine().state(),
+            TimerState::Timing { .. }
+        );
+
+        let event = {
+            let mut trigger_keys = imp.trigger_keys.borrow_mut();
+            if !trigger_keys.contains(key)
+                || !modifier.difference(trigger_keys.modifier_mask()).is_empty()
+            {
+                return glib::Propagation::Proceed;
+            }
+            trigger_keys.key_pressed(key, timing)
+        };
+
+        match event {
+            Some(data::TriggerEvent::Press) => self.pressed_cb(),
+            Some(data::TriggerEvent::Release) => self.released_cb(),
+            None => {}
+        }
+        glib::Propagation::Stop
+    }
+
+    fn key_released_cb(&self, key: gdk::Key) {
+        let event = self.imp().trigger_keys.borrow_mut().key_released(key);
+        match event {
+            Some(data::TriggerEvent::Press) => self.pressed_cb(),
+            Some(data::TriggerEvent::Release) => self.released_cb(),
+            None => {}
+        }
+    }
+
+    fn pressed_cb(&self) {
+        // There is nothing to time until a case is chosen.
+        let idle = matches!(self.timer_state_machine().state(), TimerState::Idle);
+        if idle && self.imp().case.borrow().is_none() {
+            return;
+        }
+        self.timer_state_machine().press();
+    }
+
+    fn released_cb(&self) {
+        self.timer_state_machine().release();
+    }
+
+    fn timer_state_changed_cb(&self, state: TimerState) {
+        let imp = self.imp();
+        let timer_box = &*imp.timer_box;
+
+        match state {
+            TimerState::Wait => {
+                timer_box.remove_css_class("ready");
+                timer_box.add_css_class("wait");
+            }
+            TimerState::Ready => {
+                timer_box.remove_css_class("wait");
+                timer_box.add_css_class("ready");
+                imp.time_label.set_duration(Duration::ZERO);
+            }
+            TimerState::Finished { solve_time } => {
+                timer_box.remove_css_class("wait");
+                timer_box.remove_css_class("ready");
+                imp.time_label.set_solve_time(solve_time);
+                self.record_solve(solve_time);
+                self.next_case();
+            }
+            state => {
+                timer_box.remove_css_class("wait");
+                timer_box.remove_css_class("ready");
+                self.tick_cb(state);
+            }
+        }
+    }
+
+    fn tick_cb(&self, state: TimerState) {
+        if let TimerState::Timing { duration, .. } = state {
+            self.imp()
+                .time_label
+                .set_running_duration(duration, ui::running_display(self.settings()));
+        }
+    }
+
+    /// Records the time of the current case and shows it with the new
+    /// average of the case.
+    fn record_solve(&self, solve_time: data::SolveTime) {
+        let imp = self.imp();
+        let Some(case) = imp.case.borrow().clone() else {
+            return;
+        };
+        let id = case.id();
+        if let Some(time) = solve_time.recorded_time() {
+            imp.case_times.borrow_mut().record(&id, time);
+            self.save_case_times();
+        }
+
+        let description = self.describe_case_times(&id);
+        if let Some(row) = imp.case_rows.borrow().get(&id) {
+            row.set_subtitle(&description);
+        }
+        imp.result_label.set_label(&format!(
+            "{}: {} · {}",
+            id,
+            solve_time.format(ui::time_precision(self.settings())),
+            description
+        ));
+    }
+}
+
+impl Default for TrainerWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
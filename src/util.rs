@@ -1,5 +1,11 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::{fs, io};
+
 use gtk::glib;
 
+use crate::config;
+
 pub struct TemplateCallbacks {}
 
 #[gtk::template_callbacks(functions)]
@@ -29,3 +35,31 @@ impl TemplateCallbacks {
         obj.is_some()
     }
 }
+
+/// Gets the path of a file in the data directory of the app.
+fn data_file_path(name: &str) -> PathBuf {
+    glib::user_data_dir().join(config::APP_ID).join(name)
+}
+
+/// Reads a file in the data directory of the app, or `None` if it was never
+/// written.
+pub fn read_data_file(name: &str) -> io::Result<Option<String>> {
+    match fs::read_to_string(data_file_path(name)) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Writes a file in the data directory of the app, creating the directory
+/// if needed. The contents are written to a temporary file first, so a
+/// failed write never leaves the file half written.
+pub fn write_data_file(name: &str, contents: &str) -> io::Result<()> {
+    let path = data_file_path(name);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, contents)?;
+    fs::rename(temp_path, path)
+}
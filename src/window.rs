@@ -1,7 +1,7 @@
 use crate::data::TimerState;
 use crate::{config, data, ui};
+use adw::prelude::*;
 use adw::subclass::prelude::*;
-use gtk::prelude::*;
 use gtk::{gdk, gio, glib};
 
 #[doc(hidden)]
@@ -25,7 +25,11 @@ mod imp {
         #[template_child]
         pub content_header_bar: TemplateChild<adw::HeaderBar>,
         #[template_child]
+        pub content_stack: TemplateChild<gtk::Stack>,
+        #[template_child]
         pub timer_face: TemplateChild<ui::TimerFace>,
+        #[template_child]
+        pub fewest_moves_view: TemplateChild<ui::FewestMovesView>,
 
         #[template_child]
         pub split_view: TemplateChild<adw::OverlaySplitView>,
@@ -34,6 +38,18 @@ mod imp {
         pub sidebar_stack: TemplateChild<gtk::Stack>,
         #[template_child]
         pub list_view: TemplateChild<gtk::ListView>,
+        #[template_child]
+        pub session_dropdown: TemplateChild<gtk::DropDown>,
+        #[template_child]
+        pub filter_bar: TemplateChild<ui::SolveFilterBar>,
+        #[template_child]
+        pub filtered_statistics_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub practice_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub selection_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub transfer_button: TemplateChild<gtk::MenuButton>,
 
         /// The state machine is shared between widgets within this window.
         #[property(get, set = Self::set_timer_state_machine)]
@@ -41,11 +57,25 @@ mod imp {
         timer_state_machine_handlers: RefCell<Vec<glib::SignalHandlerId>>,
 
         #[property(get, set)]
+        pub sessions: RefCell<Option<gio::ListStore>>,
+        #[property(get, set = Self::set_session)]
         pub session: RefCell<Option<data::Session>>,
+        session_handlers: RefCell<Vec<glib::SignalHandlerId>>,
+        #[property(get, set = Self::set_competition, nullable)]
+        pub competition: RefCell<Option<data::MockCompetition>>,
+        competition_handler: RefCell<Option<glib::SignalHandlerId>>,
+        #[property(get, set)]
+        pub session_filter_model: RefCell<Option<gtk::FilterListModel>>,
         #[property(get, set)]
         pub session_sort_model: RefCell<Option<gtk::SortListModel>>,
         #[property(get, set)]
         pub session_selection_model: RefCell<Option<gtk::NoSelection>>,
+        #[property(get, set)]
+        pub session_multi_selection_model: RefCell<Option<gtk::MultiSelection>>,
+        /// If set to true, clicking solves in the list selects them for bulk
+        /// operations instead of opening them.
+        #[property(get, set = Self::set_selection_mode)]
+        pub selection_mode: Cell<bool>,
 
         /// If set to true, this would hide most widgets aside from ones
         /// related to timing (i.e. sidebar).
@@ -54,6 +84,12 @@ mod imp {
         #[property(get, set)]
         pub should_collapse: Cell<bool>,
 
+        /// The filter of the solve list, entered in the filter bar.
+        pub(super) filter: RefCell<data::SolveFilter>,
+        /// The order of the solve list, as the nick of a `SolveSort`.
+        #[property(name = "sort", type = String, get = Self::get_sort, set = Self::set_sort)]
+        pub(super) sort: Cell<data::SolveSort>,
+
         pub(super) settings: OnceCell<gio::Settings>,
     }
 
@@ -79,6 +115,92 @@ mod imp {
             }
             self.timer_state_machine.replace(v);
         }
+
+        fn set_session(&self, v: Option<data::Session>) {
+            let obj = self.obj();
+            let mut handlers = self.session_handlers.borrow_mut();
+
+            if let Some(os) = self.session.take() {
+                for id in handlers.drain(..) {
+                    os.disconnect(id);
+                }
+            }
+
+            if let Some(session) = &v {
+                handlers.append(&mut obj.connect_session_signals(session));
+            }
+            let kind = v.as_ref().map(|s| s.kind()).unwrap_or_default();
+            obj.action_set_enabled(
+                "session.add-multi-blind",
+                kind == data::SessionKind::MultiBlind,
+            );
+            self.content_stack.set_visible_child_name(match kind {
+                data::SessionKind::FewestMoves => "fewest-moves",
+                _ => "timer",
+            });
+            self.session.replace(v.clone());
+            obj.update_competition_actions();
+
+            if let Some(filter_model) = obj.session_filter_model() {
+                filter_model.set_model(v.as_ref());
+            }
+            obj.session_items_changed_cb(0, 0, 0);
+        }
+
+        fn set_selection_mode(&self, v: bool) {
+            let obj = self.obj();
+            self.selection_mode.set(v);
+            self.list_view.set_single_click_activate(!v);
+            if v {
+                self.list_view
+                    .set_model(obj.session_multi_selection_model().as_ref());
+            } else {
+                if let Some(model) = obj.session_multi_selection_model() {
+                    model.unselect_all();
+                }
+                self.list_view
+                    .set_model(obj.session_selection_model().as_ref());
+            }
+            obj.update_selection_actions();
+        }
+
+        fn get_sort(&self) -> String {
+            self.sort.get().nick()
+        }
+
+        fn set_sort(&self, v: String) {
+            let Some(sort) = data::SolveSort::from_nick(&v) else {
+                log::warn!("Unknown sort order: {}", v);
+                return;
+            };
+            self.sort.set(sort);
+            if let Some(sorter) = self.obj().session_sort_model().and_then(|m| m.sorter()) {
+                sorter.changed(gtk::SorterChange::Different);
+            }
+        }
+
+        fn set_competition(&self, v: Option<data::MockCompetition>) {
+            let obj = self.obj();
+
+            if let Some(competition) = self.competition.take() {
+                if let Some(id) = self.competition_handler.take() {
+                    competition.disconnect(id);
+                }
+            }
+
+            if let Some(competition) = &v {
+                self.competition_handler
+                    .replace(Some(competition.connect_finished_notify(
+                        glib::clone!(@weak obj => move |competition| {
+                            if competition.finished() {
+                                obj.competition_finished_cb(competition);
+                            }
+                        }),
+                    )));
+            }
+            self.competition.replace(v);
+            obj.update_competition_actions();
+        }
     }
 
     #[glib::object_subclass]
@@ -88,6 +210,8 @@ mod imp {
         type ParentType = adw::ApplicationWindow;
 
         fn class_init(klass: &mut Self::Class) {
+            data::Session::ensure_type();
+
             klass.bind_template();
             klass.bind_template_instance_callbacks();
             TemplateCallbacks::bind_template_callbacks(klass);
@@ -97,7 +221,157 @@ mod imp {
                 if imp.split_view.is_collapsed() {
                     imp.split_view.set_show_sidebar(false);
                 }
-            })
+            });
+
+            klass.install_action("session.new", Some("s"), move |obj, _, param| {
+                let kind = param
+                    .and_then(|p| p.get::<String>())
+                    .and_then(|nick| data::SessionKind::from_nick(&nick))
+                    .unwrap_or_default();
+                obj.new_session(kind);
+            });
+
+            klass.install_action("session.add-multi-blind", None, move |obj, _, _| {
+                obj.present_multi_blind_dialog();
+            });
+
+            klass.install_action("session.show-goals", None, move |obj, _, _| {
+                obj.present_goals_window();
+            });
+
+            klass.install_action("session.show-pb-history", None, move |obj, _, _| {
+                obj.present_pb_history_window();
+            });
+
+            klass.install_action("session.show-tag-statistics", None, move |obj, _, _| {
+                obj.present_tag_statistics_window();
+            });
+
+            klass.install_property_action("list.sort", "sort");
+
+            klass.install_action("selection.select-all", None, move |obj, _, _| {
+                if let Some(model) = obj.session_multi_selection_model() {
+                    model.select_all();
+                }
+            });
+
+            klass.install_action("selection.remove", None, move |obj, _, _| {
+                obj.confirm_remove_selected_solves();
+            });
+
+            klass.install_action("selection.set-penalty", Some("s"), move |obj, _, param| {
+                let Some(penalty) = penalty_from_param(param) else {
+                    return;
+                };
+                if let Some(session) = obj.session() {
+                    session.set_penalties(&obj.selected_indices(), penalty);
+                }
+            });
+
+            klass.install_action("selection.move-to", Some("u"), move |obj, _, param| {
+                if let Some(position) = param.and_then(|p| p.get::<u32>()) {
+                    obj.transfer_selected_solves(position, true);
+                }
+            });
+
+            klass.install_action("selection.copy-to", Some("u"), move |obj, _, param| {
+                if let Some(position) = param.and_then(|p| p.get::<u32>()) {
+                    obj.transfer_selected_solves(position, false);
+                }
+            });
+
+            klass.install_action("selection.copy", None, move |obj, _, _| {
+                let Some(session) = obj.session() else {
+                    return;
+                };
+                let report = session.solves_report(&obj.selected_indices());
+                obj.clipboard()
+                    .set_text(&report.to_text(ui::report_format(obj.settings())));
+                obj.imp()
+                    .toast_overlay
+                    .add_toast(adw::Toast::new("Solves Copied"));
+            });
+
+            klass.install_action("last-solve.set-penalty", Some("s"), move |obj, _, param| {
+                let Some(penalty) = penalty_from_param(param) else {
+                    return;
+                };
+                if let Some(solve) = obj.session().and_then(|s| s.last_solve()) {
+                    solve.set_penalty(penalty);
+                }
+            });
+
+            klass.install_action("last-solve.remove", None, move |obj, _, _| {
+                obj.confirm_remove_last_solve();
+            });
+
+            klass.install_action("last-solve.show", None, move |obj, _, _| {
+                if let Some(solve) = obj.session().and_then(|s| s.last_solve()) {
+                    obj.present_solve_dialog(solve.index());
+                }
+            });
+
+            klass.install_action("last-solve.copy-ao5", None, move |obj, _, _| {
+                obj.copy_last_average_report(5);
+            });
+
+            klass.install_action("last-solve.copy-ao12", None, move |obj, _, _| {
+                obj.copy_last_average_report(12);
+            });
+
+            klass.install_action("timer.new-scramble", None, move |obj, _, _| {
+                let timing = matches!(
+                    obj.timer_state_machine().map(|sm| sm.state()),
+                    Some(TimerState::Inspecting | TimerState::Ready | TimerState::Timing { .. })
+                );
+                if !timing {
+                    obj.imp().timer_face.new_scramble();
+                }
+            });
+
+            klass.install_action("selection.share", None, move |obj, _, _| {
+                if let Some(session) = obj.session() {
+                    obj.present_report_dialog(session.solves_report(&obj.selected_indices()));
+                }
+            });
+
+            klass.install_action("session.show-report", None, move |obj, _, _| {
+                if let Some(session) = obj.session() {
+                    obj.present_report_dialog(session.summary_report());
+                }
+            });
+
+            klass.install_action("session.export-printable-report", None, move |obj, _, _| {
+                obj.export_printable_report();
+            });
+
+            klass.install_action("session.show-date-statistics", None, move |obj, _, _| {
+                obj.present_date_statistics_window();
+            });
+
+            klass.install_action("session.compare", None, move |obj, _, _| {
+                obj.present_session_comparison_window();
+            });
+
+            klass.install_action("practice.show-statistics", None, move |obj, _, _| {
+                obj.present_practice_window();
+            });
+
+            klass.install_action("trainer.show", None, move |obj, _, _| {
+                obj.present_trainer_window();
+            });
+
+            klass.install_action("trainer.show-recognition", None, move |obj, _, _| {
+                obj.present_recognition_window();
+            });
+
+            klass.install_action("competition.start", None, move |obj, _, _| {
+                obj.present_mock_competition_dialog();
+            });
+
+            klass.install_action("competition.stop", None, move |obj, _, _| {
+                obj.set_competition(None::<data::MockCompetition>);
+            });
         }
 
         fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
@@ -117,6 +391,8 @@ mod imp {
             obj.setup_gactions();
             obj.setup_event_controllers();
             obj.setup_list();
+            obj.update_selection_actions();
+            obj.setup_sessions();
 
             obj.load_window_size();
         }
@@ -159,6 +435,18 @@ impl PrismaTimerWindow {
         let imp = self.imp();
 
         let settings = gio::Settings::new(config::APP_ID);
+        settings.connect_changed(
+            Some("idle-cutoff"),
+            glib::clone!(@weak self as obj => move |_, _| {
+                obj.update_practice_summary();
+            }),
+        );
+        settings.connect_changed(
+            Some("time-precision"),
+            glib::clone!(@weak self as obj => move |_, _| {
+                obj.update_time_precision();
+            }),
+        );
         imp.settings
             .set(settings)
             .expect("`settings` should not be set before `setup_settings` is called");
@@ -180,7 +468,10 @@ impl PrismaTimerWindow {
         gestures.set_button(gdk::BUTTON_PRIMARY);
         gestures.set_propagation_phase(gtk::PropagationPhase::Capture);
         gestures.connect_pressed(glib::clone!(@weak self as obj => move |_, _, _, _| {
-            obj.imp().timer_face.grab_focus();
+            let imp = obj.imp();
+            if imp.content_stack.visible_child_name().as_deref() == Some("timer") {
+                imp.timer_face.grab_focus();
+            }
         }));
         self.add_controller(gestures);
     }
@@ -188,78 +479,553 @@ impl PrismaTimerWindow {
     fn setup_list(&self) {
         let imp = self.imp();
 
-        let session = data::Session::new();
-        self.set_session(session.clone());
-
-        session.connect_items_changed(
+        let filter_model = gtk::FilterListModel::new(
+            None::<data::Session>,
+            Some(gtk::CustomFilter::new(
+                glib::clone!(@weak self as obj => @default-return true, move |item| {
+                    let item = item.downcast_ref::<data::SessionItem>().unwrap();
+                    let matches = obj.imp().filter.borrow().matches(
+                        &item.comment(),
+                        &item.scramble(),
+                        &item.tags(),
+                        item.time(),
+                        data::local_date(item.timestamp()),
+                    );
+                    matches
+                }),
+            )),
+        );
+        filter_model.connect_items_changed(
             glib::clone!(@weak self as obj => move |_, position, removed, added| {
                 obj.session_items_changed_cb(position, removed, added);
             }),
         );
+        self.set_session_filter_model(filter_model.clone());
 
-        session.connect_closure(
-            "solve-added",
-            false,
-            glib::closure_local!(@strong self as obj => move |_: &data::Session| {
-                obj.session_solve_added_cb();
+        let sort_model = gtk::SortListModel::new(
+            Some(filter_model),
+            Some(gtk::CustomSorter::new(
+                glib::clone!(@weak self as obj => @default-return gtk::Ordering::Equal, move |a, b| {
+                    let a = a.downcast_ref::<data::SessionItem>().unwrap();
+                    let b = b.downcast_ref::<data::SessionItem>().unwrap();
+                    obj.imp()
+                        .sort
+                        .get()
+                        .compare(
+                            (a.index(), a.time(), a.timestamp()),
+                            (b.index(), b.time(), b.timestamp()),
+                        )
+                        .into()
+                }),
+            )),
+        );
+        self.set_session_sort_model(sort_model.clone());
+
+        let selection_model = gtk::NoSelection::new(Some(sort_model.clone()));
+        self.set_session_selection_model(selection_model.clone());
+
+        let multi_selection_model = gtk::MultiSelection::new(Some(sort_model));
+        multi_selection_model.connect_selection_changed(
+            glib::clone!(@weak self as obj => move |_, _, _| {
+                obj.update_selection_actions();
             }),
         );
+        self.set_session_multi_selection_model(multi_selection_model);
 
-        session.connect_closure(
-            "solve-removed",
-            false,
-            glib::closure_local!(@strong self as obj => move |_: &data::Session| {
-                obj.session_solve_removed_cb();
+        imp.transfer_button.set_create_popup_func(
+            glib::clone!(@weak self as obj => move |button| {
+                button.set_menu_model(Some(&obj.transfer_menu()));
             }),
         );
 
-        session.connect_closure(
-            "new-best-solve",
-            false,
-            glib::closure_local!(@strong self as obj => move |_: &data::Session| {
-                obj.session_new_best_solve_cb();
+        let factory = gtk::SignalListItemFactory::new();
+        factory.connect_setup(glib::clone!(@weak self as view => move |_, item| {
+            let row = ui::SessionItemRow::new();
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(Some(&row));
+            item.bind_property("item", &row, "item").sync_create().build();
+        }));
+
+        imp.list_view.set_model(Some(&selection_model));
+        imp.list_view.set_factory(Some(&factory));
+    }
+
+    fn setup_sessions(&self) {
+        let sessions = gio::ListStore::new::<data::Session>();
+        self.set_sessions(sessions);
+        self.new_session(data::SessionKind::Standard);
+    }
+
+    /// Creates a new session and switches to it.
+    fn new_session(&self, kind: data::SessionKind) {
+        let imp = self.imp();
+        let Some(sessions) = self.sessions() else {
+            return;
+        };
+
+        let n = sessions.n_items() + 1;
+        let name = match kind {
+            data::SessionKind::Standard => format!("Session {}", n),
+            _ => format!("Session {} ({})", n, kind.label()),
+        };
+        let session = data::Session::with_kind(&name, kind);
+        session.set_time_precision(ui::time_precision(self.settings()));
+        sessions.append(&session);
+        imp.session_dropdown.set_selected(n - 1);
+    }
+
+    /// Asks for the result of a Multi-Blind attempt timed elsewhere.
+    fn present_multi_blind_dialog(&self) {
+        let Some(session) = self.session() else {
+            return;
+        };
+
+        let dialog = ui::MultiBlindDialog::new(&session);
+        dialog.set_transient_for(Some(self));
+        dialog.set_modal(true);
+        dialog.present();
+    }
+
+    /// Shows the personal best history of the current session.
+    fn present_pb_history_window(&self) {
+        let window = ui::PbHistoryWindow::new(&self.session().unwrap_or_default());
+        self.bind_property("session", &window, "session")
+            .sync_create()
+            .build();
+        window.set_transient_for(Some(self));
+        window.present();
+    }
+
+    /// Shows the goals of the current session.
+    fn present_goals_window(&self) {
+        let window = ui::GoalsWindow::new(&self.session().unwrap_or_default());
+        self.bind_property("session", &window, "session")
+            .sync_create()
+            .build();
+        window.set_transient_for(Some(self));
+        window.present();
+    }
+
+    /// Shows the statistics of the solves with each tag in the current
+    /// session.
+    fn present_tag_statistics_window(&self) {
+        let window = ui::TagStatisticsWindow::new(&self.session().unwrap_or_default());
+        self.bind_property("session", &window, "session")
+            .sync_create()
+            .build();
+        window.set_transient_for(Some(self));
+        window.present();
+    }
+
+    /// Shows a report that can be copied or saved in any format.
+    fn present_report_dialog(&self, report: data::Report) {
+        let dialog = ui::ReportDialog::new(report);
+        dialog.set_transient_for(Some(self));
+        dialog.present();
+    }
+
+    /// Saves a report of the session as HTML, or as PDF if the file is
+    /// named with a `.pdf` extension.
+    fn export_printable_report(&self) {
+        let Some(session) = self.session() else {
+            return;
+        };
+
+        let filters = gio::ListStore::new::<gtk::FileFilter>();
+        for (name, mime_type) in [("HTML", "text/html"), ("PDF", "application/pdf")] {
+            let filter = gtk::FileFilter::new();
+            filter.set_name(Some(name));
+            filter.add_mime_type(mime_type);
+            filters.append(&filter);
+        }
+
+        let dialog = gtk::FileDialog::builder()
+            .title("Export Printable Report")
+            .initial_name(format!("{} report.html", session.name()))
+            .filters(&filters)
+            .modal(true)
+            .build();
+        let report = session.session_report();
+        dialog.save(
+            Some(self),
+            gio::Cancellable::NONE,
+            glib::clone!(@weak self as obj => move |result| {
+                let Ok(file) = result else {
+                    return;
+                };
+                let is_pdf = file
+                    .path()
+                    .and_then(|path| path.extension().map(|e| e.eq_ignore_ascii_case("pdf")))
+                    .unwrap_or(false);
+                let result = match file.path() {
+                    Some(path) if is_pdf => ui::export_session_report_pdf(&report, &path, &obj),
+                    _ => file
+                        .replace_contents(
+                            report.to_html().as_bytes(),
+                            None,
+                            false,
+                            gio::FileCreateFlags::REPLACE_DESTINATION,
+                            gio::Cancellable::NONE,
+                        )
+                        .map(|_| ()),
+                };
+                let toast = match result {
+                    Ok(_) => adw::Toast::new("Report Exported"),
+                    Err(e) => {
+                        log::error!("Failed to export report. cause: {}", e);
+                        adw::Toast::new("Failed to Export Report")
+                    }
+                };
+                obj.imp().toast_overlay.add_toast(toast);
             }),
         );
+    }
+
+    /// Shows the statistics of each day, week and month of the current
+    /// session.
+    fn present_date_statistics_window(&self) {
+        let window = ui::DateStatisticsWindow::new(&self.session().unwrap_or_default());
+        self.bind_property("session", &window, "session")
+            .sync_create()
+            .build();
+        window.set_transient_for(Some(self));
+        window.present();
+    }
+
+    /// Shows a comparison of the current session with another session.
+    fn present_session_comparison_window(&self) {
+        let Some(sessions) = self.sessions() else {
+            return;
+        };
+        let session = self.session();
+        let position = sessions
+            .iter::<data::Session>()
+            .position(|s| s.ok() == session)
+            .unwrap_or(0);
+        let window = ui::SessionComparisonWindow::new(&sessions, position as u32);
+        window.set_transient_for(Some(self));
+        window.present();
+    }
+
+    /// Shows the streaks and time spent practicing over every session.
+    fn present_practice_window(&self) {
+        let Some(sessions) = self.sessions() else {
+            return;
+        };
+        let window = ui::PracticeWindow::new(&sessions);
+        window.set_transient_for(Some(self));
+        window.present();
+    }
+
+    /// Drills last layer cases, timed apart from the sessions.
+    fn present_trainer_window(&self) {
+        let window = ui::TrainerWindow::new();
+        window.set_transient_for(Some(self));
+        window.present();
+    }
+
+    /// Drills naming PLL cases at a glance.
+    fn present_recognition_window(&self) {
+        let window = ui::RecognitionWindow::new();
+        window.set_transient_for(Some(self));
+        window.present();
+    }
 
-        session.connect_closure(
-            "new-best-ao5",
+    /// Asks for the round of a mock competition and starts it.
+    fn present_mock_competition_dialog(&self) {
+        let dialog = ui::MockCompetitionDialog::new();
+        dialog.set_transient_for(Some(self));
+        dialog.set_modal(true);
+        dialog.connect_closure(
+            "started",
             false,
-            glib::closure_local!(@strong self as obj => move |_: &data::Session| {
-                obj.session_new_best_ao5_cb();
+            glib::closure_local!(@watch self as obj => move |_: &ui::MockCompetitionDialog, competition: data::MockCompetition| {
+                obj.set_competition(Some(competition));
+                obj.imp().timer_face.grab_focus();
             }),
         );
+        dialog.present();
+    }
 
-        session.connect_closure(
-            "new-best-ao12",
-            false,
-            glib::closure_local!(@strong self as obj => move |_: &data::Session| {
-                obj.session_new_best_ao12_cb();
+    /// Gets the indices in the session of the selected solves, in order.
+    fn selected_indices(&self) -> Vec<usize> {
+        let Some(model) = self.session_multi_selection_model() else {
+            return Vec::new();
+        };
+        let selection = model.selection();
+        let mut indices: Vec<usize> = (0..selection.size() as u32)
+            .filter_map(|i| model.item(selection.nth(i)))
+            .filter_map(|item| item.downcast::<data::SessionItem>().ok())
+            .map(|item| item.index())
+            .collect();
+        indices.sort_unstable();
+        indices
+    }
+
+    /// Bulk operations are available while solves are selected.
+    fn update_selection_actions(&self) {
+        let n_selected = self.selected_indices().len();
+        let enabled = self.selection_mode() && n_selected > 0;
+        for action in [
+            "selection.remove",
+            "selection.set-penalty",
+            "selection.move-to",
+            "selection.copy-to",
+            "selection.copy",
+            "selection.share",
+        ] {
+            self.action_set_enabled(action, enabled);
+        }
+        self.imp().selection_label.set_label(&match n_selected {
+            0 => "No Solves Selected".to_string(),
+            1 => "1 Solve Selected".to_string(),
+            n => format!("{} Solves Selected", n),
+        });
+    }
+
+    /// Builds the menu of the sessions that selected solves can be moved or
+    /// copied to.
+    fn transfer_menu(&self) -> gio::Menu {
+        let menu = gio::Menu::new();
+        let Some(sessions) = self.sessions() else {
+            return menu;
+        };
+        let current = self.session();
+
+        for (action, verb) in [
+            ("selection.move-to", "Move to"),
+            ("selection.copy-to", "Copy to"),
+        ] {
+            let section = gio::Menu::new();
+            for (position, session) in sessions.iter::<data::Session>().enumerate() {
+                let Ok(session) = session else {
+                    continue;
+                };
+                if Some(&session) == current.as_ref() {
+                    continue;
+                }
+                let item = gio::MenuItem::new(Some(&format!("{} {}", verb, session.name())), None);
+                item.set_action_and_target_value(
+                    Some(action),
+                    Some(&(position as u32).to_variant()),
+                );
+                section.append_item(&item);
+            }
+            menu.append_section(None, &section);
+        }
+        menu
+    }
+
+    /// Adds the selected solves to the session at this position in the
+    /// session list, removing them from the current session if `remove` is
+    /// true.
+    fn transfer_selected_solves(&self, position: u32, remove: bool) {
+        let (Some(session), Some(target)) = (
+            self.session(),
+            self.sessions()
+                .and_then(|s| s.item(position))
+                .and_downcast::<data::Session>(),
+        ) else {
+            return;
+        };
+        if session == target {
+            return;
+        }
+
+        let indices = self.selected_indices();
+        let solves: Vec<data::SolveData> = indices
+            .iter()
+            .filter_map(|&i| session.get_solve(i))
+            .map(|item| item.solve_data())
+            .collect();
+        let n_solves = solves.len();
+        target.add_solves(solves);
+        if remove {
+            session.remove_solves(&indices);
+        }
+
+        let verb = if remove { "Moved" } else { "Copied" };
+        let toast = adw::Toast::new(&format!(
+            "{} {} Solves to {}",
+            verb,
+            n_solves,
+            target.name()
+        ));
+        self.imp().toast_overlay.add_toast(toast);
+    }
+
+    /// Asks before removing the selected solves.
+    fn confirm_remove_selected_solves(&self) {
+        let n_solves = self.selected_indices().len();
+        let builder = gtk::Builder::from_resource(
+            "/io/github/manenfu/PrismaTimer/ui/confirm_remove_dialog.ui",
+        );
+        let dialog = builder
+            .object::<adw::MessageDialog>("dialog")
+            .expect("Expected dialog");
+        if n_solves > 1 {
+            dialog.set_heading(Some(&format!("Remove {} Solves?", n_solves)));
+        }
+        dialog.set_transient_for(Some(self));
+        dialog.connect_response(
+            Some("remove"),
+            glib::clone!(@weak self as obj => move |_, _| {
+                let Some(session) = obj.session() else {
+                    return;
+                };
+                let removed = session.remove_solves(&obj.selected_indices());
+                let toast = match removed.len() {
+                    1 => adw::Toast::new("Solve Removed"),
+                    n => adw::Toast::new(&format!("{} Solves Removed", n)),
+                };
+                obj.imp().toast_overlay.add_toast(toast);
             }),
         );
+        dialog.present();
+    }
 
-        let sort_model = gtk::SortListModel::new(
-            Some(session),
-            Some(gtk::CustomSorter::new(|a, b| {
-                let a = a.downcast_ref::<data::SessionItem>().unwrap();
-                let b = b.downcast_ref::<data::SessionItem>().unwrap();
-                b.timestamp().cmp(&a.timestamp()).into()
-            })),
+    /// Asks before removing the last solve of the session.
+    fn confirm_remove_last_solve(&self) {
+        if self.session().and_then(|s| s.last_solve()).is_none() {
+            return;
+        }
+        let builder = gtk::Builder::from_resource(
+            "/io/github/manenfu/PrismaTimer/ui/confirm_remove_dialog.ui",
         );
-        self.set_session_sort_model(sort_model.clone());
+        let dialog = builder
+            .object::<adw::MessageDialog>("dialog")
+            .expect("Expected dialog");
+        dialog.set_transient_for(Some(self));
+        dialog.connect_response(
+            Some("remove"),
+            glib::clone!(@weak self as obj => move |_, _| {
+                if let Some(session) = obj.session() {
+                    if let Some(solve) = session.last_solve() {
+                        session.remove_solve_by_object(&solve);
+                    }
+                }
+            }),
+        );
+        dialog.present();
+    }
 
-        let selection_model = gtk::NoSelection::new(Some(sort_model));
-        self.set_session_selection_model(selection_model.clone());
+    /// Copies the report of the average of the last `n` solves.
+    fn copy_last_average_report(&self, n: usize) {
+        let report = self.session().and_then(|session| {
+            let last = (session.n_items() as usize).checked_sub(1)?;
+            session.average_report(last, n)
+        });
+        let toast = match report {
+            Some(report) => {
+                self.clipboard()
+                    .set_text(&report.to_text(ui::report_format(self.settings())));
+                adw::Toast::new(&format!("Ao{} Report Copied", n))
+            }
+            None => adw::Toast::new(&format!("Not Enough Solves for an Ao{}", n)),
+        };
+        self.imp().toast_overlay.add_toast(toast);
+    }
 
-        let factory = gtk::SignalListItemFactory::new();
-        factory.connect_setup(glib::clone!(@weak self as view => move |_, item| {
-            let row = ui::SessionItemRow::new();
-            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
-            item.set_child(Some(&row));
-            item.bind_property("item", &row, "item").sync_create().build();
-        }));
+    /// Mock competitions are run on the timer, for sessions where a solve
+    /// is a single timed attempt.
+    fn update_competition_actions(&self) {
+        let kind = self.session().map(|s| s.kind()).unwrap_or_default();
+        let running = self.competition().is_some();
+        self.action_set_enabled(
+            "competition.start",
+            !running
+                && matches!(
+                    kind,
+                    data::SessionKind::Standard | data::SessionKind::Blindfolded
+                ),
+        );
+        self.action_set_enabled("competition.stop", running);
+    }
 
-        imp.list_view.set_model(Some(&selection_model));
-        imp.list_view.set_factory(Some(&factory));
+    /// Shows the results sheet of a finished round and ends the competition.
+    fn competition_finished_cb(&self, competition: &data::MockCompetition) {
+        let sheet = competition.results_sheet(ui::time_precision(self.settings()));
+        let dialog = adw::MessageDialog::builder()
+            .heading("Round Finished")
+            .body(&sheet)
+            .transient_for(self)
+            .modal(true)
+            .build();
+        dialog.add_responses(&[("copy", "_Copy Results"), ("close", "_Close")]);
+        dialog.set_default_response(Some("close"));
+        dialog.set_close_response("close");
+        dialog.connect_response(
+            Some("copy"),
+            glib::clone!(@weak self as obj => move |_, _| {
+                obj.clipboard().set_text(&sheet);
+            }),
+        );
+        dialog.present();
+
+        self.set_competition(None::<data::MockCompetition>);
+    }
+
+    fn connect_session_signals(&self, session: &data::Session) -> Vec<glib::SignalHandlerId> {
+        vec![
+            session.connect_items_changed(
+                glib::clone!(@weak self as obj => move |_, position, removed, added| {
+                    obj.session_items_changed_cb(position, removed, added);
+                }),
+            ),
+            session.connect_closure(
+                "solve-added",
+                false,
+                glib::closure_local!(@strong self as obj => move |_: &data::Session| {
+                    obj.session_solve_added_cb();
+                }),
+            ),
+            session.connect_closure(
+                "solve-removed",
+                false,
+                glib::closure_local!(@strong self as obj => move |_: &data::Session| {
+                    obj.session_solve_removed_cb();
+                }),
+            ),
+            session.connect_closure(
+                "new-best-solve",
+                false,
+                glib::closure_local!(@strong self as obj => move |_: &data::Session| {
+                    obj.session_new_best_solve_cb();
+                }),
+            ),
+            session.connect_closure(
+                "new-best-ao5",
+                false,
+                glib::closure_local!(@strong self as obj => move |_: &data::Session| {
+                    obj.session_new_best_ao5_cb();
+                }),
+            ),
+            session.connect_closure(
+                "new-best-ao12",
+                false,
+                glib::closure_local!(@strong self as obj => move |_: &data::Session| {
+                    obj.session_new_best_ao12_cb();
+                }),
+            ),
+            session.connect_closure(
+                "goal-met",
+                false,
+                glib::closure_local!(@strong self as obj => move |_: &data::Session, goal: String| {
+                    obj.session_goal_met_cb(&goal);
+                }),
+            ),
+        ]
+    }
+
+    /// Shows the times of every session with the precision chosen in the
+    /// settings.
+    fn update_time_precision(&self) {
+        let precision = ui::time_precision(self.settings());
+        if let Some(sessions) = self.sessions() {
+            for session in sessions.iter::<data::Session>().filter_map(Result::ok) {
+                session.set_time_precision(precision);
+            }
+        }
     }
 
     fn settings(&self) -> &gio::Settings {
@@ -298,7 +1064,7 @@ impl PrismaTimerWindow {
         let imp = self.imp();
 
         match state {
-            TimerState::Ready | TimerState::Timing { .. } => {
+            TimerState::Inspecting | TimerState::Ready | TimerState::Timing { .. } => {
                 if imp.split_view.is_collapsed() {
                     imp.split_view.set_show_sidebar(false);
                 }
@@ -308,15 +1074,93 @@ impl PrismaTimerWindow {
                 self.set_focus_mode(false);
             }
         }
+        self.update_minimal_focus();
+    }
+
+    /// Dims the window and hides the header bar in focus mode, if the
+    /// minimal focus layout is enabled.
+    fn update_minimal_focus(&self) {
+        let minimal = self.focus_mode() && self.settings().boolean("minimal-focus");
+        self.imp().content_header_bar.set_visible(!minimal);
+        if minimal {
+            self.add_css_class("minimal-focus");
+        } else {
+            self.remove_css_class("minimal-focus");
+        }
     }
 
     fn session_items_changed_cb(&self, _position: u32, _removed: u32, _added: u32) {
         let imp = self.imp();
-        if self.session().map_or(0, |s| s.n_items()) > 0 {
-            imp.sidebar_stack.set_visible_child_name("list");
-        } else {
+        let n_solves = self.session().map_or(0, |s| s.n_items());
+        let n_shown = self.session_filter_model().map_or(0, |m| m.n_items());
+        if n_solves == 0 {
             imp.sidebar_stack.set_visible_child_name("empty");
+        } else if n_shown == 0 {
+            imp.sidebar_stack.set_visible_child_name("no-results");
+        } else {
+            imp.sidebar_stack.set_visible_child_name("list");
         }
+        self.update_filtered_statistics();
+        self.update_practice_summary();
+    }
+
+    /// Shows the current streak and the time practiced today in every
+    /// session.
+    fn update_practice_summary(&self) {
+        let Some(sessions) = self.sessions() else {
+            return;
+        };
+        let summary = ui::practice_summary(&sessions, self.settings());
+        self.imp().practice_label.set_label(&summary.description());
+    }
+
+    /// Shows statistics of the solves matching the filter, if requested.
+    fn update_filtered_statistics(&self) {
+        let imp = self.imp();
+        let (Some(session), Some(filter_model)) = (self.session(), self.session_filter_model())
+        else {
+            return;
+        };
+
+        let visible = imp.filter_bar.show_statistics() && !imp.filter.borrow().is_empty();
+        imp.filtered_statistics_label.set_visible(visible);
+        if !visible {
+            return;
+        }
+
+        let times: Vec<data::SolveTime> = filter_model
+            .iter::<data::SessionItem>()
+            .filter_map(Result::ok)
+            .map(|item| item.time())
+            .collect();
+        imp.filtered_statistics_label
+            .set_label(&data::describe_filtered_solves(
+                &times,
+                session.n_items() as usize,
+                session.time_precision(),
+            ));
+    }
+
+    #[template_callback]
+    fn filter_bar_changed_cb(&self, filter_bar: &ui::SolveFilterBar) {
+        let filter = filter_bar.filter();
+        if *self.imp().filter.borrow() == filter {
+            return;
+        }
+        self.imp().filter.replace(filter);
+        if let Some(filter) = self.session_filter_model().and_then(|m| m.filter()) {
+            filter.changed(gtk::FilterChange::Different);
+        }
+        self.update_filtered_statistics();
+    }
+
+    #[template_callback]
+    fn filter_bar_show_statistics_cb(
+        &self,
+        _pspec: &glib::ParamSpec,
+        _filter_bar: &ui::SolveFilterBar,
+    ) {
+        self.update_filtered_statistics();
     }
 
     fn session_solve_added_cb(&self) {
@@ -347,13 +1191,48 @@ impl PrismaTimerWindow {
             .add_toast(adw::Toast::new("New Best Ao12"));
     }
 
+    fn session_goal_met_cb(&self, goal: &str) {
+        let imp = self.imp();
+        imp.toast_overlay
+            .add_toast(adw::Toast::new(&format!("Goal Met: {}", goal)));
+    }
+
+    #[template_callback]
+    fn session_dropdown_selected_cb(&self, _pspec: &glib::ParamSpec, dropdown: &gtk::DropDown) {
+        if let Some(session) = dropdown.selected_item().and_downcast::<data::Session>() {
+            self.set_session(session);
+        }
+    }
+
     #[template_callback]
     fn list_view_activated_cb(&self, position: u32, _list_view: &gtk::ListView) {
-        let session = self.session().unwrap();
-        let index = session.n_items() - position - 1;
+        let Some(item) = self
+            .session_sort_model()
+            .and_then(|m| m.item(position))
+            .and_downcast::<data::SessionItem>()
+        else {
+            return;
+        };
+
+        self.present_solve_dialog(item.index());
+    }
 
-        let dialog = ui::SolveDialog::new(session, index);
+    fn present_solve_dialog(&self, index: usize) {
+        let Some(session) = self.session() else {
+            return;
+        };
+        let dialog = ui::SolveDialog::new(session, index as u32);
         dialog.set_transient_for(Some(self));
         dialog.present();
     }
 }
+
+/// Gets the penalty named by the string target of a `set-penalty` action.
+fn penalty_from_param(param: Option<&glib::Variant>) -> Option<data::Penalty> {
+    match param.and_then(|p| p.get::<String>()).as_deref() {
+        Some("ok") => Some(data::Penalty::Ok),
+        Some("plus-two") => Some(data::Penalty::Plus2),
+        Some("dnf") => Some(data::Penalty::Dnf),
+        _ => None,
+    }
+}